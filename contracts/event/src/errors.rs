@@ -66,8 +66,8 @@ pub enum EventError {
     /// The event `requires_verification` is `true` but the ZkVerificationConfig
     /// has not been enabled by the organizer.
     ZkVerificationRequired = 39,
-    /// Reserved for future on-chain verifier integration. Currently signals that
-    /// the provided proof bytes are structurally invalid.
+    /// The claim's proof did not verify against the configured ZkPassport
+    /// verifier for this event's scope.
     ZkProofInvalid = 40,
     /// The submitted `ZkPassportClaim.claim_type` does not match the type
     /// required by the event's `ZkVerificationConfig`.
@@ -83,4 +83,32 @@ pub enum EventError {
     AnonymousNullifierReused = 46,
     AnonymousClaimVerifierAlreadyConfigured = 47,
     AnonymousProofExpiryTooFar = 48,
+    /// No anonymous ticket has been claimed under this nullifier for the event.
+    AnonymousTicketNotFound = 49, // CommonErrorCode::NotFound
+    /// The anonymous ticket under this nullifier has already been checked in.
+    AnonymousTicketAlreadyCheckedIn = 50, // CommonErrorCode::AlreadyProcessed
+    /// Only valid (unused, not cancelled or revoked) tickets can be revoked.
+    TicketNotRevocable = 51,
+    /// Only valid, unused tickets can change tier.
+    TicketNotUpgradable = 52,
+    /// The event caps tickets per identity; register with an identity claim.
+    IdentityRequired = 53,
+    /// This identity already holds the maximum tickets allowed for the event.
+    IdentityLimitReached = 54,
+    /// The organizer has not enabled installment plans for the event.
+    InstallmentsDisabled = 55,
+    /// The schedule breaks the event's installment terms.
+    InvalidInstallmentSchedule = 56, // CommonErrorCode::InvalidInput
+    /// No open installment plan exists for the holder.
+    InstallmentPlanNotFound = 57, // CommonErrorCode::NotFound
+    /// The ticket has no linked payment to refund a downgrade against.
+    DowngradeRefundUnavailable = 58,
+    /// No ZkPassport verifier has been configured by the admin.
+    ZkPassportVerifierNotConfigured = 59,
+    /// The ZkPassport verifier is set once and cannot be replaced.
+    ZkPassportVerifierAlreadyConfigured = 60,
+    /// Too many events were passed to one call.
+    BatchTooLarge = 61,
+    /// The ticket contract rejected a call that keeps it in step with the event.
+    TicketSyncFailed = 62,
 }
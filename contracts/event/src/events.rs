@@ -1,7 +1,8 @@
 use soroban_sdk::{contractevent, Address, Env, Symbol};
 
 use crate::types::{
-    mask_address, CreateEventParams, Event, EventStatus, MaskedAddress, PrivacyLevel, ZkClaimType,
+    mask_address, CreateEventParams, Event, EventStatus, MaskedAddress, PrivacyLevel,
+    RevocationReason, ZkClaimType,
 };
 
 #[contractevent(data_format = "vec", topics = ["created"])]
@@ -186,6 +187,179 @@ pub fn emit_anon_registration(env: &Env, event_id: &Symbol, tier_id: u32, ticket
     }
     .publish(env);
 }
+#[contractevent(data_format = "vec", topics = ["anon_chk"])]
+pub struct AnonCheckIn {
+    pub event_id: Symbol,
+    pub checked_in_at: u64,
+}
+pub fn emit_anon_check_in(env: &Env, event_id: &Symbol) {
+    AnonCheckIn {
+        event_id: event_id.clone(),
+        checked_in_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+#[contractevent(data_format = "vec", topics = ["revoke"])]
+pub struct TicketRevoked {
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub revoked_by: Address,
+    pub reason: RevocationReason,
+    pub tier_id: u32,
+    pub refund_amount: i128,
+    pub revoked_at: u64,
+}
+pub fn emit_ticket_revoked(
+    env: &Env,
+    event_id: &Symbol,
+    ticket_id: u64,
+    revoked_by: &Address,
+    reason: &RevocationReason,
+    tier_id: u32,
+    refund_amount: i128,
+) {
+    TicketRevoked {
+        event_id: event_id.clone(),
+        ticket_id,
+        revoked_by: revoked_by.clone(),
+        reason: reason.clone(),
+        tier_id,
+        refund_amount,
+        revoked_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+#[contractevent(data_format = "vec", topics = ["upgrade"])]
+pub struct TicketUpgraded {
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub owner: Address,
+    pub from_tier_id: u32,
+    pub to_tier_id: u32,
+    /// Positive when the holder paid the difference, negative when refunded.
+    pub price_delta: i128,
+    pub upgraded_at: u64,
+}
+pub fn emit_ticket_upgraded(
+    env: &Env,
+    event_id: &Symbol,
+    ticket_id: u64,
+    owner: &Address,
+    from_tier_id: u32,
+    to_tier_id: u32,
+    price_delta: i128,
+) {
+    TicketUpgraded {
+        event_id: event_id.clone(),
+        ticket_id,
+        owner: owner.clone(),
+        from_tier_id,
+        to_tier_id,
+        price_delta,
+        upgraded_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+#[contractevent(data_format = "vec", topics = ["inst_start"])]
+pub struct InstallmentPlanStarted {
+    pub event_id: Symbol,
+    pub holder: Address,
+    pub tier_id: u32,
+    pub deposit: i128,
+    pub installments: u32,
+    pub started_at: u64,
+}
+pub fn emit_installment_plan_started(
+    env: &Env,
+    event_id: &Symbol,
+    holder: &Address,
+    tier_id: u32,
+    deposit: i128,
+    installments: u32,
+) {
+    InstallmentPlanStarted {
+        event_id: event_id.clone(),
+        holder: holder.clone(),
+        tier_id,
+        deposit,
+        installments,
+        started_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+#[contractevent(data_format = "vec", topics = ["inst_paid"])]
+pub struct InstallmentPaid {
+    pub event_id: Symbol,
+    pub holder: Address,
+    pub amount: i128,
+    /// Top-ups still due; 0 once the ticket is minted.
+    pub remaining: u32,
+    pub paid_at: u64,
+}
+pub fn emit_installment_paid(
+    env: &Env,
+    event_id: &Symbol,
+    holder: &Address,
+    amount: i128,
+    remaining: u32,
+) {
+    InstallmentPaid {
+        event_id: event_id.clone(),
+        holder: holder.clone(),
+        amount,
+        remaining,
+        paid_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+#[contractevent(data_format = "vec", topics = ["inst_default"])]
+pub struct InstallmentPlanDefaulted {
+    pub event_id: Symbol,
+    pub holder: Address,
+    pub tier_id: u32,
+    pub refunded: i128,
+    pub defaulted_at: u64,
+}
+pub fn emit_installment_plan_defaulted(
+    env: &Env,
+    event_id: &Symbol,
+    holder: &Address,
+    tier_id: u32,
+    refunded: i128,
+) {
+    InstallmentPlanDefaulted {
+        event_id: event_id.clone(),
+        holder: holder.clone(),
+        tier_id,
+        refunded,
+        defaulted_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+#[contractevent(data_format = "vec", topics = ["inst_cancel"])]
+pub struct InstallmentPlanCancelled {
+    pub event_id: Symbol,
+    pub holder: Address,
+    pub tier_id: u32,
+    pub refunded: i128,
+    pub cancelled_at: u64,
+}
+pub fn emit_installment_plan_cancelled(
+    env: &Env,
+    event_id: &Symbol,
+    holder: &Address,
+    tier_id: u32,
+    refunded: i128,
+) {
+    InstallmentPlanCancelled {
+        event_id: event_id.clone(),
+        holder: holder.clone(),
+        tier_id,
+        refunded,
+        cancelled_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
 #[contractevent(data_format = "vec", topics = ["zk_attend"])]
 pub struct ZkVerifiedAttendance {
     pub event_id: Symbol,
@@ -210,3 +384,15 @@ pub fn emit_zk_verified_attendance(
     }
     .publish(env);
 }
+#[contractevent(data_format = "vec", topics = ["tk_lnk_f"])]
+pub struct TicketLinkFailed {
+    pub ticket_contract: Address,
+    pub failed_at: u64,
+}
+pub fn emit_ticket_link_failed(env: &Env, ticket_contract: &Address) {
+    TicketLinkFailed {
+        ticket_contract: ticket_contract.clone(),
+        failed_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
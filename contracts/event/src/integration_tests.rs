@@ -1,7 +1,26 @@
-use crate::types::{CreateEventParams, EventStatus, PrivacyLevel, TicketTierParams};
+use crate::types::{
+    CreateEventParams, EventStatus, PrivacyLevel, RevocationReason, TicketTierParams,
+    TransferPolicy, UpdateEventParams, ZkClaimType, ZkPassportClaim,
+};
 use crate::{EventContract, EventContractClient, EventError};
-use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::{token, vec, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger};
+use soroban_sdk::xdr::ContractEventBody;
+use soroban_sdk::{
+    contract, contractimpl, token, vec, Address, Bytes, BytesN, Env, String, Symbol, TryFromVal,
+    Val, Vec,
+};
+
+#[contract]
+struct MockZkPassportVerifier;
+
+#[contractimpl]
+impl MockZkPassportVerifier {
+    /// Accepts a proof equal to the hash of its public inputs.
+    pub fn verify(env: Env, proof: Bytes, public_inputs: Bytes) -> bool {
+        let digest: BytesN<32> = env.crypto().sha256(&public_inputs).into();
+        proof == Bytes::from_array(&env, &digest.to_array())
+    }
+}
 
 fn setup_env() -> Env {
     let env = Env::default();
@@ -12,14 +31,13 @@ fn setup_env() -> Env {
     env
 }
 
-fn create_active_event(
+fn active_event_params(
     env: &Env,
-    client: &EventContractClient,
     organizer: &Address,
     payout_token: &Address,
     event_id: Symbol,
-) {
-    let params = CreateEventParams {
+) -> CreateEventParams {
+    CreateEventParams {
         organizer: organizer.clone(),
         payout_token: payout_token.clone(),
         event_id: event_id.clone(),
@@ -46,9 +64,22 @@ fn create_active_event(
         resale_royalty_bps: 0,
         max_resale_price: None,
         allow_free_ticket_transfer: false,
-    };
+    }
+}
 
-    client.create_event(&params);
+fn create_active_event(
+    env: &Env,
+    client: &EventContractClient,
+    organizer: &Address,
+    payout_token: &Address,
+    event_id: Symbol,
+) {
+    client.create_event(&active_event_params(
+        env,
+        organizer,
+        payout_token,
+        event_id.clone(),
+    ));
     client.update_event_status(organizer, &event_id, &EventStatus::Active);
 }
 
@@ -154,7 +185,10 @@ fn test_registration_reverts_if_minting_fails() {
         &platform_wallet,
         &event_contract_id,
     );
-    event_client.initialize(&organizer, &payments_contract_id, &payments_contract_id);
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    ticket_contract::TicketContractClient::new(&env, &ticket_contract_id)
+        .initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
 
     let price = 100_000_000i128;
     token_admin_client.mint(&token_admin, &price);
@@ -168,6 +202,13 @@ fn test_registration_reverts_if_minting_fails() {
         &token_address,
         event_id.clone(),
     );
+    // Point the event at a contract that cannot mint once the event is set up.
+    env.as_contract(&event_contract_id, || {
+        env.storage().persistent().set(
+            &crate::storage::DataKey::TicketContract,
+            &payments_contract_id,
+        );
+    });
 
     let result = event_client.try_register_for_event(&1, &attendee, &event_id, &0, &false, &None);
     assert!(result.is_err());
@@ -188,6 +229,50 @@ fn test_registration_reverts_if_minting_fails() {
     assert!(!registered);
 }
 
+#[test]
+fn test_rejected_ticket_sync_is_reported() {
+    let env = setup_env();
+    let organizer = Address::generate(&env);
+    let token_address = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    payments_contract::PaymentsContractClient::new(&env, &payments_contract_id).initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &Address::generate(&env),
+        &event_contract_id,
+    );
+
+    // The ticket contract is not initialized, so it cannot be linked.
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    let link_failed = Symbol::new(&env, "tk_lnk_f");
+    assert!(env.events().all().events().iter().any(|event| {
+        let ContractEventBody::V0(body) = &event.body;
+        body.topics.first().is_some_and(|topic| {
+            Val::try_from_val(&env, topic)
+                .ok()
+                .and_then(|topic| Symbol::try_from_val(&env, &topic).ok())
+                == Some(link_failed.clone())
+        })
+    }));
+
+    let event_id = Symbol::new(&env, "evt_sync");
+    let params = active_event_params(&env, &organizer, &token_address, event_id.clone());
+    let result = event_client.try_create_event(&params);
+    assert_eq!(result.err(), Some(Ok(EventError::TicketSyncFailed)));
+
+    // Once linked, the same event syncs its transfer policy.
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    ticket_client.set_event_contract(&organizer, &event_contract_id);
+    event_client.create_event(&params);
+    assert!(ticket_client.get_event_transfer_config(&event_id).is_some());
+}
+
 #[test]
 fn test_cancel_event_triggers_refunds() {
     let env = setup_env();
@@ -1169,13 +1254,14 @@ fn test_resale_royalty_exceeding_proceeds_leaves_seller_unpaid() {
         &15_000, // 150% royalty: exceeds total resale proceeds.
         &None,
         &false,
+        &Vec::new(&env),
     );
 
     let owner_tickets = payments_client.get_owner_tickets(&attendee);
     let ticket_id = owner_tickets.get(0).unwrap();
 
     let resale_price = 50_000_000i128;
-    payments_client.list_ticket_for_resale(&attendee, &ticket_id, &resale_price);
+    payments_client.list_ticket_for_resale(&attendee, &ticket_id, &resale_price, &None, &None);
 
     token_admin_client.mint(&buyer, &resale_price);
     payments_client.buy_resale_ticket(&buyer, &ticket_id);
@@ -1196,3 +1282,1554 @@ fn test_resale_royalty_exceeding_proceeds_leaves_seller_unpaid() {
     let ticket = payments_client.get_ticket(&ticket_id);
     assert_eq!(ticket.owner, Some(buyer));
 }
+
+#[test]
+fn test_transfer_settings_synced_to_ticket_contract() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let attendee = Address::generate(&env);
+    let friend = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    token_admin_client.mint(&attendee, &100_000_000);
+
+    let event_id = Symbol::new(&env, "evt_xfer");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    // No royalty or price cap: tickets default to an open policy.
+    let settings = event_client.get_transfer_settings(&event_id);
+    assert_eq!(settings.policy, TransferPolicy::Open);
+    assert_eq!(
+        ticket_client
+            .get_event_transfer_config(&event_id)
+            .unwrap()
+            .policy,
+        TransferPolicy::Open
+    );
+
+    assert_eq!(
+        event_client.try_set_transfer_settings(
+            &friend,
+            &event_id,
+            &TransferPolicy::MarketplaceOnly,
+            &0
+        ),
+        Err(Ok(EventError::Unauthorized))
+    );
+    event_client.set_transfer_settings(&organizer, &event_id, &TransferPolicy::MarketplaceOnly, &0);
+
+    event_client.register_for_event(&1, &attendee, &event_id, &0, &false, &None);
+    let ticket_id = ticket_client
+        .get_tickets_by_owner(&attendee)
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        ticket_client.get_ticket_transfer_policy(&ticket_id),
+        TransferPolicy::MarketplaceOnly
+    );
+    assert_eq!(
+        ticket_client.try_transfer_ticket(&attendee, &friend, &ticket_id),
+        Err(Ok(ticket_contract::TicketError::TransferPolicyViolation))
+    );
+
+    // The lock window is relative to the event start and applies to
+    // tickets minted before the change.
+    event_client.set_transfer_settings(&organizer, &event_id, &TransferPolicy::Open, &100);
+    let second_attendee = Address::generate(&env);
+    token_admin_client.mint(&second_attendee, &100_000_000);
+    event_client.register_for_event(&2, &second_attendee, &event_id, &0, &false, &None);
+    let open_ticket_id = ticket_client
+        .get_tickets_by_owner(&second_attendee)
+        .get(0)
+        .unwrap();
+    assert_eq!(
+        ticket_client.get_ticket_transfer_policy(&open_ticket_id),
+        TransferPolicy::Open
+    );
+    assert_eq!(
+        ticket_client.try_transfer_ticket(&second_attendee, &friend, &open_ticket_id),
+        Err(Ok(ticket_contract::TicketError::TransferLocked))
+    );
+}
+
+#[test]
+fn test_free_ticket_transfer_flag_restricts_default_policy() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+    let token_address = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &Address::generate(&env),
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+
+    let event_id = Symbol::new(&env, "evt_free");
+    event_client.create_event(&CreateEventParams {
+        organizer: organizer.clone(),
+        payout_token: token_address.clone(),
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Free Meetup"),
+        description: String::from_str(&env, "Free entry"),
+        venue: String::from_str(&env, "Main Hall"),
+        event_date: env.ledger().timestamp() + 86_401,
+        initial_tiers: vec![
+            &env,
+            TicketTierParams {
+                name: String::from_str(&env, "Free"),
+                price: 0,
+                capacity: 10,
+            },
+        ],
+        allow_anonymous: false,
+        requires_verification: false,
+        privacy_level: PrivacyLevel::Standard,
+        max_tickets_per_user: 0,
+        event_start_ledger: 0,
+        event_end_ledger: 1000,
+        withdrawal_delay_ledgers: 17280,
+        revenue_splits: Vec::new(&env),
+        resale_royalty_bps: 0,
+        max_resale_price: None,
+        allow_free_ticket_transfer: false,
+    });
+
+    // Only free tickets and transfer disabled: they cannot move at all.
+    assert_eq!(
+        event_client.get_transfer_settings(&event_id).policy,
+        TransferPolicy::Disallowed
+    );
+    assert_eq!(
+        ticket_client
+            .get_event_transfer_config(&event_id)
+            .unwrap()
+            .policy,
+        TransferPolicy::Disallowed
+    );
+
+    // A paid tier moves to the marketplace, which refuses free resales.
+    event_client.add_ticket_tier(
+        &organizer,
+        &event_id,
+        &String::from_str(&env, "Paid"),
+        &100,
+        &10,
+    );
+    assert_eq!(
+        ticket_client
+            .get_event_transfer_config(&event_id)
+            .unwrap()
+            .policy,
+        TransferPolicy::MarketplaceOnly
+    );
+
+    event_client.update_event_details(&UpdateEventParams {
+        organizer: organizer.clone(),
+        event_id: event_id.clone(),
+        name: None,
+        description: None,
+        venue: None,
+        event_date: None,
+        allow_anonymous: None,
+        requires_verification: None,
+        max_tickets_per_user: None,
+        resale_royalty_bps: None,
+        max_resale_price: None,
+        allow_free_ticket_transfer: Some(true),
+    });
+    assert_eq!(
+        ticket_client
+            .get_event_transfer_config(&event_id)
+            .unwrap()
+            .policy,
+        TransferPolicy::Open
+    );
+}
+
+#[test]
+fn test_payments_ownership_follows_ticket_contract() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let attendee = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let buyer = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    payments_client.set_ticket_contract(&organizer, &ticket_contract_id);
+    token_admin_client.mint(&attendee, &100_000_000);
+    token_admin_client.mint(&buyer, &100_000_000);
+
+    let event_id = Symbol::new(&env, "evt_own");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    // A ticket minted outside the payments flow shifts the two id sequences.
+    ticket_client.mint_ticket(&Symbol::new(&env, "other"), &organizer, &organizer, &0);
+
+    event_client.register_for_event(&1, &attendee, &event_id, &0, &false, &None);
+    let ledger_ticket_id = payments_client.get_owner_tickets(&attendee).get(0).unwrap();
+    let minted_ticket_id = ticket_client
+        .get_tickets_by_owner(&attendee)
+        .get(0)
+        .unwrap();
+    assert_ne!(ledger_ticket_id, minted_ticket_id);
+    assert_eq!(
+        payments_client.get_minted_ticket_id(&ledger_ticket_id),
+        Some(minted_ticket_id)
+    );
+
+    // A direct transfer leaves the payments copy stale until it is reconciled.
+    ticket_client.transfer_ticket(&attendee, &friend, &minted_ticket_id);
+    assert_eq!(
+        payments_client.get_ticket(&ledger_ticket_id).owner,
+        Some(attendee.clone())
+    );
+    assert_eq!(
+        payments_client.reconcile_ticket_owners(&vec![&env, ledger_ticket_id]),
+        1
+    );
+    assert_eq!(
+        payments_client.get_ticket(&ledger_ticket_id).owner,
+        Some(friend.clone())
+    );
+    assert_eq!(
+        payments_client.get_owner_tickets(&friend),
+        vec![&env, ledger_ticket_id]
+    );
+    assert_eq!(payments_client.get_owner_tickets(&attendee).len(), 0);
+    assert_eq!(
+        payments_client.reconcile_ticket_owners(&vec![&env, ledger_ticket_id]),
+        0
+    );
+    let mut oversized = Vec::new(&env);
+    for _ in 0..101 {
+        oversized.push_back(ledger_ticket_id);
+    }
+    assert_eq!(
+        payments_client.try_reconcile_ticket_owners(&oversized),
+        Err(Ok(payments_contract::PaymentError::BatchTooLarge))
+    );
+
+    // Resale checks the live owner and moves the linked minted ticket.
+    assert_eq!(
+        payments_client.try_list_ticket_for_resale(
+            &attendee,
+            &ledger_ticket_id,
+            &50_000_000,
+            &None,
+            &None
+        ),
+        Err(Ok(payments_contract::PaymentError::Unauthorized))
+    );
+    payments_client.list_ticket_for_resale(&friend, &ledger_ticket_id, &50_000_000, &None, &None);
+    payments_client.buy_resale_ticket(&buyer, &ledger_ticket_id);
+    assert_eq!(ticket_client.owner_of(&minted_ticket_id), buyer);
+    assert_eq!(
+        payments_client.get_ticket(&ledger_ticket_id).owner,
+        Some(buyer.clone())
+    );
+}
+
+#[test]
+fn test_revoke_ticket_refunds_payer_and_returns_capacity() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let attendee = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    payments_client.set_ticket_contract(&organizer, &ticket_contract_id);
+    token_admin_client.mint(&attendee, &100_000_000);
+
+    let event_id = Symbol::new(&env, "evt_rev");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    event_client.register_for_event(&1, &attendee, &event_id, &0, &false, &None);
+    assert_eq!(token_client.balance(&attendee), 0);
+    let ticket_id = ticket_client
+        .get_tickets_by_owner(&attendee)
+        .get(0)
+        .unwrap();
+
+    assert_eq!(
+        event_client.try_revoke_ticket(&stranger, &ticket_id, &RevocationReason::Fraud),
+        Err(Ok(EventError::Unauthorized))
+    );
+
+    event_client.revoke_ticket(&organizer, &ticket_id, &RevocationReason::Chargeback);
+
+    assert_eq!(token_client.balance(&attendee), 100_000_000);
+    assert_eq!(
+        ticket_client.get_ticket(&ticket_id).status,
+        ticket_contract::TicketStatus::Revoked
+    );
+    assert!(!ticket_client.has_valid_ticket(&attendee, &event_id, &None));
+    assert!(!event_client.is_registered(&event_id, &attendee));
+
+    let event = event_client.get_event(&event_id);
+    assert_eq!(event.sold_count, 0);
+    assert_eq!(event.tiers.get(0).unwrap().sold, 0);
+
+    assert_eq!(
+        event_client.try_revoke_ticket(&organizer, &ticket_id, &RevocationReason::Other),
+        Err(Ok(EventError::TicketNotRevocable))
+    );
+}
+
+#[test]
+fn test_revoke_second_ticket_of_batch_refunds_its_share() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let attendee = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    payments_client.set_ticket_contract(&organizer, &ticket_contract_id);
+    token_admin_client.mint(&attendee, &300_000_000);
+
+    let event_id = Symbol::new(&env, "evt_brev");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    // Registering first keeps the batch within the ledger footprint limit.
+    event_client.register_for_event(&1, &attendee, &event_id, &0, &false, &None);
+    event_client.batch_register_for_event(&2, &attendee, &event_id, &0, &2, &false, &None);
+    assert_eq!(token_client.balance(&attendee), 0);
+    let ticket_ids = ticket_client.get_tickets_by_owner(&attendee);
+    assert_eq!(ticket_ids.len(), 3);
+    let first_ticket_id = ticket_ids.get(1).unwrap();
+    let second_ticket_id = ticket_ids.get(2).unwrap();
+    let payment_id = payments_client
+        .get_minted_ticket_payment(&first_ticket_id)
+        .unwrap();
+    assert_eq!(
+        payments_client.get_minted_ticket_payment(&second_ticket_id),
+        Some(payment_id)
+    );
+
+    event_client.revoke_ticket(&organizer, &second_ticket_id, &RevocationReason::Fraud);
+
+    assert_eq!(token_client.balance(&attendee), 100_000_000);
+    assert_eq!(
+        ticket_client.get_ticket(&second_ticket_id).status,
+        ticket_contract::TicketStatus::Revoked
+    );
+    let payment = payments_client.get_payment(&payment_id);
+    assert_eq!(payment.refunded_amount, 100_000_000);
+    assert_eq!(payment.status, payments_contract::PaymentStatus::Held);
+    assert_eq!(event_client.get_event(&event_id).sold_count, 2);
+
+    // The first ticket refunds what is left of the same payment.
+    event_client.revoke_ticket(&organizer, &first_ticket_id, &RevocationReason::Fraud);
+    assert_eq!(token_client.balance(&attendee), 200_000_000);
+    assert_eq!(
+        payments_client.get_payment(&payment_id).status,
+        payments_contract::PaymentStatus::Refunded
+    );
+    assert!(event_client.is_registered(&event_id, &attendee));
+}
+
+#[test]
+fn test_upgrade_ticket_charges_difference_and_moves_capacity() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let attendee = Address::generate(&env);
+    let rival = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    token_admin_client.mint(&attendee, &500_000_000);
+    token_admin_client.mint(&rival, &500_000_000);
+
+    let event_id = Symbol::new(&env, "evt_upg");
+    event_client.create_event(&CreateEventParams {
+        organizer: organizer.clone(),
+        payout_token: token_address.clone(),
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Tiered Event"),
+        description: String::from_str(&env, "Upgrade test event"),
+        venue: String::from_str(&env, "Main Hall"),
+        event_date: env.ledger().timestamp() + 86_401,
+        initial_tiers: vec![
+            &env,
+            TicketTierParams {
+                name: String::from_str(&env, "General"),
+                price: 100_000_000,
+                capacity: 10,
+            },
+            TicketTierParams {
+                name: String::from_str(&env, "VIP"),
+                price: 250_000_000,
+                capacity: 1,
+            },
+        ],
+        allow_anonymous: true,
+        requires_verification: false,
+        privacy_level: PrivacyLevel::Standard,
+        max_tickets_per_user: 1,
+        event_start_ledger: 0,
+        event_end_ledger: 1000,
+        withdrawal_delay_ledgers: 17280,
+        revenue_splits: Vec::new(&env),
+        resale_royalty_bps: 0,
+        max_resale_price: None,
+        allow_free_ticket_transfer: false,
+    });
+    event_client.update_event_status(&organizer, &event_id, &EventStatus::Active);
+
+    event_client.register_for_event(&1, &attendee, &event_id, &0, &false, &None);
+    event_client.register_for_event(&1, &rival, &event_id, &0, &false, &None);
+    let ticket_id = ticket_client
+        .get_tickets_by_owner(&attendee)
+        .get(0)
+        .unwrap();
+    let rival_ticket_id = ticket_client.get_tickets_by_owner(&rival).get(0).unwrap();
+    assert_eq!(ticket_client.get_ticket_tier(&ticket_id), 0);
+
+    assert_eq!(
+        event_client.try_upgrade_ticket(&2, &rival, &ticket_id, &1),
+        Err(Ok(EventError::Unauthorized))
+    );
+
+    // The per-user ticket cap does not block paying the difference.
+    event_client.upgrade_ticket(&2, &attendee, &ticket_id, &1);
+    assert_eq!(token_client.balance(&attendee), 250_000_000);
+    assert_eq!(ticket_client.get_ticket_tier(&ticket_id), 1);
+    let event = event_client.get_event(&event_id);
+    assert_eq!(event.sold_count, 2);
+    assert_eq!(event.tiers.get(0).unwrap().sold, 1);
+    assert_eq!(event.tiers.get(1).unwrap().sold, 1);
+
+    assert_eq!(
+        event_client.try_upgrade_ticket(&2, &rival, &rival_ticket_id, &1),
+        Err(Ok(EventError::TierSoldOut))
+    );
+
+    // Moving back refunds the whole difference, top-up first.
+    event_client.upgrade_ticket(&3, &attendee, &ticket_id, &0);
+    assert_eq!(token_client.balance(&attendee), 400_000_000);
+    assert_eq!(ticket_client.get_ticket_tier(&ticket_id), 0);
+    let event = event_client.get_event(&event_id);
+    assert_eq!(event.tiers.get(0).unwrap().sold, 2);
+    assert_eq!(event.tiers.get(1).unwrap().sold, 0);
+
+    event_client.upgrade_ticket(&2, &rival, &rival_ticket_id, &1);
+    assert_eq!(ticket_client.get_ticket_tier(&rival_ticket_id), 1);
+
+    // A ticket minted outside the payments flow has nothing to refund a
+    // downgrade against, so the downgrade is refused rather than paying 0.
+    let unlinked_id = ticket_client.mint_ticket(&event_id, &organizer, &attendee, &1);
+    assert_eq!(
+        event_client.try_upgrade_ticket(&4, &attendee, &unlinked_id, &0),
+        Err(Ok(EventError::DowngradeRefundUnavailable))
+    );
+    assert_eq!(ticket_client.get_ticket_tier(&unlinked_id), 1);
+}
+
+#[test]
+fn test_identity_limit_spans_wallets_but_not_events() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let wallet_a = Address::generate(&env);
+    let wallet_b = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    payments_client.set_ticket_contract(&organizer, &ticket_contract_id);
+    token_admin_client.mint(&wallet_a, &100_000_000);
+    token_admin_client.mint(&wallet_b, &300_000_000);
+
+    let event_id = Symbol::new(&env, "evt_idc");
+    let other_event_id = Symbol::new(&env, "evt_idc2");
+    for id in [event_id.clone(), other_event_id.clone()] {
+        create_active_event(&env, &event_client, &organizer, &token_address, id.clone());
+        event_client.set_identity_limit(&organizer, &id, &1);
+    }
+    assert_eq!(payments_client.get_identity_limit(&event_id), 1);
+    let verifier = env.register(MockZkPassportVerifier, ());
+    event_client.set_zk_passport_verifier(&organizer, &verifier);
+
+    // Proofs are bound to the event scope, the buying wallet and the nullifier.
+    let claim_for = |event_id: &Symbol, holder: &Address, nullifier: u8| {
+        let mut claim = ZkPassportClaim {
+            claim_type: ZkClaimType::Age,
+            proof: Bytes::new(&env),
+            nullifier: BytesN::from_array(&env, &[nullifier; 32]),
+            expiry_ledger: env.ledger().sequence() + 100,
+        };
+        let inputs = env.as_contract(&event_contract_id, || {
+            crate::zk_passport_public_inputs(&env, event_id, holder, &claim)
+        });
+        let digest: BytesN<32> = env.crypto().sha256(&inputs).into();
+        claim.proof = Bytes::from_array(&env, &digest.to_array());
+        claim
+    };
+    let claim = |holder: &Address, nullifier: u8| claim_for(&event_id, holder, nullifier);
+
+    assert_eq!(
+        event_client.try_register_for_event(&1, &wallet_a, &event_id, &0, &false, &None),
+        Err(Ok(EventError::IdentityRequired))
+    );
+
+    event_client.register_with_identity(&1, &wallet_a, &event_id, &0, &claim(&wallet_a, 7), &None);
+    assert_eq!(
+        payments_client.get_identity_purchases(&event_id, &claim(&wallet_a, 7).nullifier),
+        1
+    );
+
+    // A second wallet presenting the same identity is capped.
+    assert_eq!(
+        event_client.try_register_with_identity(
+            &1,
+            &wallet_b,
+            &event_id,
+            &0,
+            &claim(&wallet_b, 7),
+            &None
+        ),
+        Err(Ok(EventError::IdentityLimitReached))
+    );
+    // Going straight to the payments contract is capped too.
+    assert_eq!(
+        payments_client.try_pay_for_tier(
+            &2,
+            &wallet_b,
+            &event_id,
+            &0,
+            &1,
+            &100_000_000,
+            &None,
+            &token_address,
+            &None,
+        ),
+        Err(Ok(payments_contract::PaymentError::IdentityRequired))
+    );
+    assert_eq!(
+        payments_client.try_pay_for_tier(
+            &2,
+            &wallet_b,
+            &event_id,
+            &0,
+            &1,
+            &100_000_000,
+            &None,
+            &token_address,
+            &Some(claim(&wallet_b, 7).nullifier),
+        ),
+        Err(Ok(payments_contract::PaymentError::IdentityLimitReached))
+    );
+
+    // A fresh nullifier needs its own proof: unproven or borrowed proofs fail.
+    let mut forged = claim(&wallet_b, 9);
+    forged.proof = Bytes::new(&env);
+    assert_eq!(
+        event_client.try_register_with_identity(&3, &wallet_b, &event_id, &0, &forged, &None),
+        Err(Ok(EventError::ZkProofInvalid))
+    );
+    forged.proof = claim(&wallet_b, 8).proof;
+    assert_eq!(
+        event_client.try_register_with_identity(&3, &wallet_b, &event_id, &0, &forged, &None),
+        Err(Ok(EventError::ZkProofInvalid))
+    );
+    assert_eq!(
+        event_client.try_register_with_identity(
+            &3,
+            &wallet_b,
+            &event_id,
+            &0,
+            &claim_for(&other_event_id, &wallet_b, 9),
+            &None
+        ),
+        Err(Ok(EventError::ZkProofInvalid))
+    );
+
+    // A claim observed for one wallet cannot be replayed from another.
+    assert_eq!(
+        event_client.try_register_with_identity(
+            &3,
+            &wallet_b,
+            &event_id,
+            &0,
+            &claim(&wallet_a, 8),
+            &None
+        ),
+        Err(Ok(EventError::ZkProofInvalid))
+    );
+    assert_ne!(
+        event_client.get_zk_passport_holder(&wallet_a),
+        event_client.get_zk_passport_holder(&wallet_b)
+    );
+
+    // Another identity, and the same identity on another event, are unaffected.
+    event_client.register_with_identity(&3, &wallet_b, &event_id, &0, &claim(&wallet_b, 8), &None);
+    event_client.register_with_identity(
+        &4,
+        &wallet_b,
+        &other_event_id,
+        &0,
+        &claim_for(&other_event_id, &wallet_b, 7),
+        &None,
+    );
+    assert_eq!(event_client.get_event(&event_id).sold_count, 2);
+    assert_eq!(event_client.get_event(&other_event_id).sold_count, 1);
+}
+
+#[test]
+fn test_resale_auctions_offers_and_listing_expiry() {
+    let env = setup_env();
+
+    let organizer = Address::generate(&env);
+    let seller_a = Address::generate(&env);
+    let seller_b = Address::generate(&env);
+    let seller_c = Address::generate(&env);
+    let bidder_1 = Address::generate(&env);
+    let bidder_2 = Address::generate(&env);
+    let dutch_buyer = Address::generate(&env);
+    let offerer = Address::generate(&env);
+
+    let event_contract_id = env.register(EventContract, ());
+    let event_client = EventContractClient::new(&env, &event_contract_id);
+
+    let ticket_contract_id = env.register(ticket_contract::TicketContract, ());
+    let ticket_client = ticket_contract::TicketContractClient::new(&env, &ticket_contract_id);
+
+    let payments_contract_id = env.register(payments_contract::PaymentsContract, ());
+    let payments_client =
+        payments_contract::PaymentsContractClient::new(&env, &payments_contract_id);
+
+    let token_admin = Address::generate(&env);
+    let token_address = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
+    let token_admin_client = token::StellarAssetClient::new(&env, &token_address);
+    let token_client = token::Client::new(&env, &token_address);
+
+    let platform_wallet = Address::generate(&env);
+    payments_client.initialize(
+        &organizer,
+        &token_address,
+        &0,
+        &platform_wallet,
+        &event_contract_id,
+    );
+    ticket_client.initialize(&organizer, &payments_contract_id);
+    event_client.initialize(&organizer, &ticket_contract_id, &payments_contract_id);
+    payments_client.set_ticket_contract(&organizer, &ticket_contract_id);
+
+    let event_id = Symbol::new(&env, "evt_market");
+    event_client.create_event(&CreateEventParams {
+        organizer: organizer.clone(),
+        payout_token: token_address.clone(),
+        event_id: event_id.clone(),
+        name: String::from_str(&env, "Market"),
+        description: String::from_str(&env, "Resale market"),
+        venue: String::from_str(&env, "Main Hall"),
+        event_date: env.ledger().timestamp() + 86_401,
+        initial_tiers: vec![
+            &env,
+            TicketTierParams {
+                name: String::from_str(&env, "General"),
+                price: 100_000_000,
+                capacity: 10,
+            },
+        ],
+        allow_anonymous: true,
+        requires_verification: false,
+        privacy_level: PrivacyLevel::Standard,
+        max_tickets_per_user: 0,
+        event_start_ledger: 0,
+        event_end_ledger: 100_000,
+        withdrawal_delay_ledgers: 17280,
+        revenue_splits: Vec::new(&env),
+        resale_royalty_bps: 1000,
+        max_resale_price: Some(150_000_000),
+        allow_free_ticket_transfer: false,
+    });
+    event_client.update_event_status(&organizer, &event_id, &EventStatus::Active);
+
+    let mut ticket_ids = Vec::new(&env);
+    for (nonce, seller) in [(1u64, &seller_a), (2, &seller_b), (3, &seller_c)] {
+        token_admin_client.mint(seller, &100_000_000);
+        event_client.register_for_event(&nonce, seller, &event_id, &0, &false, &None);
+        ticket_ids.push_back(payments_client.get_owner_tickets(seller).get(0).unwrap());
+    }
+    let (ticket_a, ticket_b, ticket_c) = (
+        ticket_ids.get(0).unwrap(),
+        ticket_ids.get(1).unwrap(),
+        ticket_ids.get(2).unwrap(),
+    );
+    token_admin_client.mint(&bidder_1, &200_000_000);
+    token_admin_client.mint(&bidder_2, &200_000_000);
+    token_admin_client.mint(&dutch_buyer, &100_000_000);
+    token_admin_client.mint(&offerer, &90_000_000);
+
+    let start = env.ledger().sequence();
+    payments_client.list_ticket_for_auction(
+        &seller_a,
+        &ticket_a,
+        &payments_contract::ListingKind::EnglishAuction,
+        &100_000_000,
+        &120_000_000,
+        &(start + 100),
+        &None,
+    );
+    payments_client.list_ticket_for_auction(
+        &seller_b,
+        &ticket_b,
+        &payments_contract::ListingKind::DutchAuction,
+        &150_000_000,
+        &50_000_000,
+        &(start + 100),
+        &None,
+    );
+    payments_client.list_ticket_for_resale(
+        &seller_c,
+        &ticket_c,
+        &110_000_000,
+        &Some(start + 10),
+        &None,
+    );
+    assert_eq!(
+        payments_client.try_list_ticket_for_resale(
+            &seller_c,
+            &ticket_c,
+            &160_000_000,
+            &None,
+            &None
+        ),
+        Err(Ok(payments_contract::PaymentError::InvalidAmount))
+    );
+
+    let page = payments_client.get_event_listings(&event_id, &0, &2);
+    assert_eq!(page.listings.len(), 2);
+    assert_eq!(page.next_cursor, Some(2));
+    let page = payments_client.get_event_listings(&event_id, &2, &2);
+    assert_eq!(page.listings.len(), 1);
+    assert_eq!(page.next_cursor, None);
+    let seller_page = payments_client.get_seller_listings(&seller_a, &0, &10);
+    assert_eq!(seller_page.listings.get(0).unwrap().ticket_id, ticket_a);
+
+    // English auction: bids are capped, escrowed and refunded when outbid.
+    assert_eq!(
+        payments_client.try_place_bid(&bidder_1, &ticket_a, &160_000_000),
+        Err(Ok(payments_contract::PaymentError::InvalidAmount))
+    );
+    payments_client.place_bid(&bidder_1, &ticket_a, &110_000_000);
+    assert_eq!(token_client.balance(&bidder_1), 90_000_000);
+    assert_eq!(
+        payments_client.try_place_bid(&bidder_2, &ticket_a, &110_000_000),
+        Err(Ok(payments_contract::PaymentError::BidTooLow))
+    );
+    payments_client.place_bid(&bidder_2, &ticket_a, &130_000_000);
+    assert_eq!(token_client.balance(&bidder_1), 200_000_000);
+    assert_eq!(
+        payments_client.try_delist_ticket(&seller_a, &ticket_a),
+        Err(Ok(payments_contract::PaymentError::AuctionHasBids))
+    );
+    assert_eq!(
+        payments_client.try_settle_listing(&ticket_a),
+        Err(Ok(payments_contract::PaymentError::ListingStillActive))
+    );
+
+    // Dutch auction: halfway through, the price has fallen halfway to the reserve.
+    env.ledger().with_mut(|li| li.sequence_number = start + 50);
+    assert_eq!(payments_client.get_listing_price(&ticket_b), 100_000_000);
+    payments_client.buy_resale_ticket(&dutch_buyer, &ticket_b);
+    assert_eq!(
+        payments_client.get_ticket(&ticket_b).owner,
+        Some(dutch_buyer.clone())
+    );
+    assert_eq!(token_client.balance(&seller_b), 90_000_000);
+
+    // The fixed-price listing has expired and can only be cleared.
+    assert_eq!(
+        payments_client.try_buy_resale_ticket(&dutch_buyer, &ticket_c),
+        Err(Ok(payments_contract::PaymentError::ListingExpired))
+    );
+    payments_client.settle_listing(&ticket_c);
+    assert_eq!(payments_client.get_resale_listing(&ticket_c), None);
+    assert_eq!(
+        payments_client
+            .get_event_listings(&event_id, &0, &10)
+            .listings
+            .len(),
+        1
+    );
+
+    // The auction ends above its reserve and goes to the leading bidder.
+    env.ledger().with_mut(|li| li.sequence_number = start + 101);
+    payments_client.settle_listing(&ticket_a);
+    assert_eq!(
+        payments_client.get_ticket(&ticket_a).owner,
+        Some(bidder_2.clone())
+    );
+    assert_eq!(token_client.balance(&seller_a), 117_000_000);
+    assert!(payments_client
+        .get_seller_listings(&seller_a, &0, &10)
+        .listings
+        .is_empty());
+
+    // Offers work on unlisted tickets and stay escrowed until accepted.
+    payments_client.make_offer(&offerer, &ticket_c, &90_000_000, &(start + 200), &None);
+    assert_eq!(token_client.balance(&offerer), 0);
+    assert_eq!(payments_client.get_ticket_offers(&ticket_c).len(), 1);
+    payments_client.accept_offer(&seller_c, &ticket_c, &offerer);
+    assert_eq!(
+        payments_client.get_ticket(&ticket_c).owner,
+        Some(offerer.clone())
+    );
+    assert_eq!(token_client.balance(&seller_c), 81_000_000);
+    assert!(payments_client.get_ticket_offers(&ticket_c).is_empty());
+
+    payments_client.make_offer(&bidder_1, &ticket_a, &50_000_000, &(start + 200), &None);
+    payments_client.cancel_offer(&bidder_1, &ticket_a);
+    assert_eq!(token_client.balance(&bidder_1), 200_000_000);
+    assert_eq!(
+        payments_client.try_accept_offer(&bidder_2, &ticket_a, &bidder_1),
+        Err(Ok(payments_contract::PaymentError::OfferNotFound))
+    );
+
+    // A full offer list frees a slot from expired offers first, then from
+    // the lowest live offer that a new one beats.
+    let mut spammers = Vec::new(&env);
+    for i in 0..20u32 {
+        let spammer = Address::generate(&env);
+        token_admin_client.mint(&spammer, &1_000_000);
+        let expiry = if i == 0 { start + 150 } else { start + 10_000 };
+        payments_client.make_offer(&spammer, &ticket_a, &1_000_000, &expiry, &None);
+        spammers.push_back(spammer);
+    }
+    env.ledger().with_mut(|li| li.sequence_number = start + 151);
+    payments_client.make_offer(&bidder_1, &ticket_a, &5_000_000, &(start + 300), &None);
+    assert_eq!(token_client.balance(&spammers.get(0).unwrap()), 1_000_000);
+    assert_eq!(payments_client.get_ticket_offers(&ticket_a).len(), 20);
+
+    let late = Address::generate(&env);
+    token_admin_client.mint(&late, &2_500_000);
+    assert_eq!(
+        payments_client.try_make_offer(&late, &ticket_a, &500_000, &(start + 300), &None),
+        Err(Ok(payments_contract::PaymentError::TooManyOffers))
+    );
+    payments_client.make_offer(&late, &ticket_a, &2_000_000, &(start + 300), &None);
+    assert_eq!(token_client.balance(&spammers.get(1).unwrap()), 1_000_000);
+    assert_eq!(payments_client.get_ticket_offers(&ticket_a).len(), 20);
+
+    // Listings stored before auctions existed have no terms entry and read
+    // as fixed-price payout-token listings without any migration.
+    env.as_contract(&payments_contract_id, || {
+        env.storage().persistent().set(
+            &payments_contract::DataKey::ResaleListing(ticket_c),
+            &payments_contract::ListingRecord {
+                price: 95_000_000,
+                seller: offerer.clone(),
+            },
+        );
+    });
+    let listing = payments_client.get_resale_listing(&ticket_c).unwrap();
+    assert_eq!(listing.seller, offerer);
+    assert_eq!(listing.price, 95_000_000);
+    assert_eq!(listing.kind, payments_contract::ListingKind::FixedPrice);
+    assert_eq!(listing.token, token_address);
+    assert_eq!(listing.expires_at_ledger, None);
+
+    let legacy_buyer = Address::generate(&env);
+    token_admin_client.mint(&legacy_buyer, &95_000_000);
+    payments_client.buy_resale_ticket(&legacy_buyer, &ticket_c);
+    assert_eq!(
+        payments_client.get_ticket(&ticket_c).owner,
+        Some(legacy_buyer.clone())
+    );
+    assert_eq!(token_client.balance(&legacy_buyer), 0);
+    assert_eq!(payments_client.get_resale_listing(&ticket_c), None);
+}
+
+#[test]
+fn test_accepted_tokens_sync_to_payments_allowlist() {
+    let env = setup_env();
+    let (event_client, payments_client, _, _, _, token_address, organizer, _) = setup_linked(&env);
+    let event_id = Symbol::new(&env, "evt_tok");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    let usdc = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    // Organizers can only pick tokens the payments admin has allowlisted.
+    assert!(event_client
+        .try_set_accepted_tokens(&organizer, &event_id, &vec![&env, usdc.clone()])
+        .is_err());
+    payments_client.allow_token(&usdc, &6);
+    event_client.set_accepted_tokens(
+        &organizer,
+        &event_id,
+        &vec![&env, usdc.clone(), token_address.clone(), usdc.clone()],
+    );
+    assert_eq!(
+        payments_client.get_accepted_tokens(&event_id),
+        vec![&env, token_address.clone(), usdc.clone()]
+    );
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc).mint(&buyer, &200);
+    // The token now clears the event's token check and only fails for lack
+    // of a price feed to convert the tier price.
+    assert_eq!(
+        payments_client.try_pay_for_ticket(
+            &1,
+            &buyer,
+            &event_id,
+            &100,
+            &None,
+            &usdc,
+            &payments_contract::PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ),
+        Err(Ok(payments_contract::PaymentError::OracleNotConfigured))
+    );
+
+    // Delisting stops new payments even for events that accepted the token.
+    payments_client.disallow_token(&usdc);
+    assert_eq!(
+        payments_client.try_pay_for_ticket(
+            &2,
+            &buyer,
+            &event_id,
+            &100,
+            &None,
+            &usdc,
+            &payments_contract::PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ),
+        Err(Ok(payments_contract::PaymentError::TokenNotAllowed))
+    );
+    assert_eq!(
+        payments_client.try_disallow_token(&token_address),
+        Err(Ok(payments_contract::PaymentError::InvalidPayoutToken))
+    );
+}
+
+#[test]
+fn test_resync_payments_config_prices_legacy_events() {
+    let env = setup_env();
+    let (event_client, payments_client, _, _, _, token_address, organizer, _) = setup_linked(&env);
+    let event_id = Symbol::new(&env, "evt_old");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+    // An event synced before the payments contract learned tier prices
+    // still sells at any amount.
+    env.as_contract(&payments_client.address, || {
+        env.storage()
+            .persistent()
+            .remove(&payments_contract::DataKey::EventTierPrices(
+                event_id.clone(),
+            ));
+    });
+
+    let buyer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token_address).mint(&buyer, &100_000_001);
+    let payment_id = payments_client.pay_for_ticket(
+        &1,
+        &buyer,
+        &event_id,
+        &1,
+        &None,
+        &token_address,
+        &payments_contract::PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+    assert_eq!(payments_client.get_payment_tier(&payment_id), None);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        event_client.try_resync_payments_config(&stranger, &vec![&env, event_id.clone()]),
+        Err(Ok(EventError::Unauthorized))
+    );
+    let mut too_many = vec![&env];
+    for _ in 0..9 {
+        too_many.push_back(event_id.clone());
+    }
+    assert_eq!(
+        event_client.try_resync_payments_config(&organizer, &too_many),
+        Err(Ok(EventError::BatchTooLarge))
+    );
+    // Unknown events are skipped.
+    assert_eq!(
+        event_client.resync_payments_config(
+            &organizer,
+            &vec![&env, event_id.clone(), Symbol::new(&env, "missing")]
+        ),
+        1
+    );
+    assert_eq!(payments_client.get_tier_prices(&event_id).len(), 1);
+
+    assert_eq!(
+        payments_client.try_pay_for_ticket(
+            &2,
+            &buyer,
+            &event_id,
+            &1,
+            &None,
+            &token_address,
+            &payments_contract::PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ),
+        Err(Ok(payments_contract::PaymentError::PriceMismatch))
+    );
+    let payment_id = payments_client.pay_for_ticket(
+        &2,
+        &buyer,
+        &event_id,
+        &100_000_000,
+        &None,
+        &token_address,
+        &payments_contract::PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+    assert_eq!(payments_client.get_payment_tier(&payment_id), Some(0));
+}
+
+#[test]
+fn test_tier_reference_price_syncs_to_payments() {
+    let env = setup_env();
+    let (event_client, payments_client, _, _, _, token_address, organizer, _) = setup_linked(&env);
+    let event_id = Symbol::new(&env, "evt_usd");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+    let usd = 100_000_000_000_000i128;
+
+    assert_eq!(
+        event_client.try_set_tier_reference_price(
+            &Address::generate(&env),
+            &event_id,
+            &0,
+            &Some(20 * usd)
+        ),
+        Err(Ok(EventError::Unauthorized))
+    );
+    assert_eq!(
+        event_client.try_set_tier_reference_price(&organizer, &event_id, &7, &Some(20 * usd)),
+        Err(Ok(EventError::TierNotFound))
+    );
+    assert_eq!(
+        event_client.try_set_tier_reference_price(&organizer, &event_id, &0, &Some(0)),
+        Err(Ok(EventError::InvalidPrice))
+    );
+
+    event_client.set_tier_reference_price(&organizer, &event_id, &0, &Some(20 * usd));
+    assert_eq!(
+        payments_client.get_tier_prices(&event_id),
+        vec![
+            &env,
+            payments_contract::TierPrice {
+                tier_id: 0,
+                price: 20 * usd,
+                reference_priced: true,
+            }
+        ]
+    );
+
+    event_client.set_tier_reference_price(&organizer, &event_id, &0, &None);
+    assert_eq!(
+        payments_client.get_tier_prices(&event_id),
+        vec![
+            &env,
+            payments_contract::TierPrice {
+                tier_id: 0,
+                price: 100_000_000,
+                reference_priced: false,
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_installment_plan_mints_on_final_payment_and_defaults_with_penalty() {
+    let env = setup_env();
+    let (
+        event_client,
+        payments_client,
+        ticket_client,
+        token_client,
+        token_admin_client,
+        token_address,
+        organizer,
+        _,
+    ) = setup_linked(&env);
+    let event_id = Symbol::new(&env, "evt_inst");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    let buyer = Address::generate(&env);
+    let laggard = Address::generate(&env);
+    fund(&token_admin_client, &buyer, 100_000_000);
+    fund(&token_admin_client, &laggard, 100_000_000);
+
+    let now = env.ledger().timestamp();
+    let schedule = |deposit: i128, top_ups: &[i128]| {
+        let mut installments = Vec::new(&env);
+        for (i, amount) in top_ups.iter().enumerate() {
+            installments.push_back(payments_contract::Installment {
+                amount: *amount,
+                due_at: now + 1_000 * (i as u64 + 1),
+            });
+        }
+        payments_contract::InstallmentSchedule {
+            deposit,
+            installments,
+        }
+    };
+
+    assert_eq!(
+        event_client.try_start_installment_plan(
+            &1,
+            &buyer,
+            &event_id,
+            &0,
+            &schedule(40_000_000, &[30_000_000, 30_000_000])
+        ),
+        Err(Ok(EventError::InstallmentsDisabled))
+    );
+    event_client.set_installment_settings(&organizer, &event_id, &2, &2_500, &5_000);
+    assert_eq!(
+        event_client.try_start_installment_plan(
+            &1,
+            &buyer,
+            &event_id,
+            &0,
+            &schedule(20_000_000, &[40_000_000, 40_000_000])
+        ),
+        Err(Ok(EventError::InvalidInstallmentSchedule))
+    );
+    assert_eq!(
+        event_client.try_start_installment_plan(
+            &1,
+            &buyer,
+            &event_id,
+            &0,
+            &schedule(40_000_000, &[20_000_000, 20_000_000, 20_000_000])
+        ),
+        Err(Ok(EventError::InvalidInstallmentSchedule))
+    );
+    // The schedule must add up to the tier price.
+    assert!(event_client
+        .try_start_installment_plan(
+            &1,
+            &buyer,
+            &event_id,
+            &0,
+            &schedule(40_000_000, &[30_000_000, 20_000_000])
+        )
+        .is_err());
+
+    event_client.start_installment_plan(
+        &1,
+        &buyer,
+        &event_id,
+        &0,
+        &schedule(40_000_000, &[30_000_000, 30_000_000]),
+    );
+    event_client.start_installment_plan(
+        &1,
+        &laggard,
+        &event_id,
+        &0,
+        &schedule(40_000_000, &[30_000_000, 30_000_000]),
+    );
+    assert_eq!(
+        event_client
+            .get_event(&event_id)
+            .tiers
+            .get(0)
+            .unwrap()
+            .reserved,
+        2
+    );
+
+    event_client.pay_installment(&2, &buyer, &event_id);
+    assert!(!event_client.is_registered(&event_id, &buyer));
+    event_client.pay_installment(&3, &buyer, &event_id);
+    assert!(event_client.is_registered(&event_id, &buyer));
+    assert_eq!(token_client.balance(&buyer), 0);
+
+    let plan = payments_client
+        .get_installment_plan(&event_id, &buyer)
+        .unwrap();
+    assert_eq!(plan.status, payments_contract::InstallmentStatus::Completed);
+    let final_payment = payments_client.get_payment(&plan.payment_ids.last().unwrap());
+    assert_eq!(
+        payments_client.get_payment_tier(&final_payment.payment_id),
+        Some(0)
+    );
+    let minted = ticket_client.get_tickets_by_owner(&buyer).get(0).unwrap();
+    assert_eq!(
+        payments_client.get_minted_ticket_payment(&minted),
+        Some(final_payment.payment_id)
+    );
+
+    // The laggard misses the first top-up; the plan can only be released.
+    env.ledger().with_mut(|li| li.timestamp = now + 1_001);
+    assert!(event_client
+        .try_pay_installment(&2, &laggard, &event_id)
+        .is_err());
+    event_client.release_installment_plan(&event_id, &laggard);
+    // Half the deposit is kept as the penalty.
+    assert_eq!(token_client.balance(&laggard), 80_000_000);
+    assert_eq!(
+        event_client.try_release_installment_plan(&event_id, &laggard),
+        Err(Ok(EventError::InstallmentPlanNotFound))
+    );
+
+    let tier = event_client.get_event(&event_id).tiers.get(0).unwrap();
+    assert_eq!((tier.sold, tier.reserved), (1, 0));
+    assert_eq!(event_client.get_event(&event_id).sold_count, 1);
+}
+
+#[test]
+fn test_cancelled_event_releases_installment_plans_without_penalty() {
+    let env = setup_env();
+    let (
+        event_client,
+        payments_client,
+        _,
+        token_client,
+        token_admin_client,
+        token_address,
+        organizer,
+        _,
+    ) = setup_linked(&env);
+    let event_id = Symbol::new(&env, "evt_inst_x");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+    event_client.set_installment_settings(&organizer, &event_id, &2, &2_500, &5_000);
+
+    let buyer = Address::generate(&env);
+    fund(&token_admin_client, &buyer, 100_000_000);
+    let now = env.ledger().timestamp();
+    event_client.start_installment_plan(
+        &1,
+        &buyer,
+        &event_id,
+        &0,
+        &payments_contract::InstallmentSchedule {
+            deposit: 40_000_000,
+            installments: vec![
+                &env,
+                payments_contract::Installment {
+                    amount: 30_000_000,
+                    due_at: now + 1_000,
+                },
+                payments_contract::Installment {
+                    amount: 30_000_000,
+                    due_at: now + 2_000,
+                },
+            ],
+        },
+    );
+    event_client.pay_installment(&2, &buyer, &event_id);
+    assert_eq!(token_client.balance(&buyer), 30_000_000);
+
+    // A plan on an active event cannot be released before a missed deadline.
+    assert!(event_client
+        .try_release_installment_plan(&event_id, &buyer)
+        .is_err());
+
+    event_client.cancel_event(&organizer, &event_id);
+    assert!(event_client
+        .try_pay_installment(&3, &buyer, &event_id)
+        .is_err());
+    // No deadline has passed, and the whole deposit comes back.
+    event_client.release_installment_plan(&event_id, &buyer);
+    assert_eq!(token_client.balance(&buyer), 100_000_000);
+    assert_eq!(
+        payments_client
+            .get_installment_plan(&event_id, &buyer)
+            .unwrap()
+            .status,
+        payments_contract::InstallmentStatus::Cancelled
+    );
+    assert_eq!(
+        event_client
+            .get_event(&event_id)
+            .tiers
+            .get(0)
+            .unwrap()
+            .reserved,
+        0
+    );
+}
+
+#[test]
+fn test_create_event_locks_organizer_bond() {
+    let env = setup_env();
+    let (
+        event_client,
+        payments_client,
+        _ticket_client,
+        token_client,
+        token_admin_client,
+        token_address,
+        organizer,
+        payments_id,
+    ) = setup_linked(&env);
+
+    // 5% of 10 seats at 100_000_000 each.
+    payments_client.set_bond_policy(&500, &1_000);
+    fund(&token_admin_client, &organizer, 50_000_000);
+    let event_id = Symbol::new(&env, "evt_bond");
+    create_active_event(
+        &env,
+        &event_client,
+        &organizer,
+        &token_address,
+        event_id.clone(),
+    );
+
+    let bond = payments_client.get_event_bond(&event_id);
+    assert_eq!(bond.status, payments_contract::BondStatus::Locked);
+    assert_eq!(bond.amount, 50_000_000);
+    assert_eq!(bond.slash_threshold_bps, 1_000);
+    assert_eq!(token_client.balance(&organizer), 0);
+    assert_eq!(token_client.balance(&payments_id), 50_000_000);
+
+    // A face value beyond i128 is rejected rather than overflowing.
+    let mut params = active_event_params(
+        &env,
+        &organizer,
+        &token_address,
+        Symbol::new(&env, "evt_huge"),
+    );
+    params.initial_tiers = vec![
+        &env,
+        TicketTierParams {
+            name: String::from_str(&env, "General"),
+            price: i128::MAX / 2,
+            capacity: 3,
+        },
+    ];
+    assert_eq!(
+        event_client.try_create_event(&params),
+        Err(Ok(EventError::InvalidPrice))
+    );
+}
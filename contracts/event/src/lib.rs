@@ -1,5 +1,7 @@
 #![no_std]
-use payments_contract::{PaymentPrivacy, PaymentsContractClient};
+use payments_contract::{
+    InstallmentSchedule, InstallmentStatus, PaymentsContractClient, TierPrice,
+};
 use soroban_sdk::{
     contract, contractclient, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol,
 };
@@ -18,9 +20,11 @@ pub use storage::*;
 pub use types::*;
 
 use events::{
-    emit_anon_registration, emit_event_cancelled, emit_event_created, emit_event_postponed,
-    emit_event_resumed, emit_event_updated, emit_registration, emit_status_changed,
-    emit_zk_verified_attendance,
+    emit_anon_check_in, emit_anon_registration, emit_event_cancelled, emit_event_created,
+    emit_event_postponed, emit_event_resumed, emit_event_updated, emit_installment_paid,
+    emit_installment_plan_cancelled, emit_installment_plan_defaulted,
+    emit_installment_plan_started, emit_registration, emit_status_changed, emit_ticket_link_failed,
+    emit_ticket_revoked, emit_ticket_upgraded, emit_zk_verified_attendance,
 };
 
 // Import common utilities
@@ -32,6 +36,11 @@ const MAX_POSTPONEMENT_CHOICE_WINDOW_LEDGERS: u32 = 518_400;
 const MAX_POSTPONEMENTS: u32 = 3;
 const MAX_ANONYMOUS_PROOF_TTL_LEDGERS: u32 = 17_280;
 const ANONYMOUS_CLAIM_DOMAIN: &[u8] = b"zicket:anonymous-ticket-claim:v1";
+const ZK_PASSPORT_DOMAIN: &[u8] = b"zicket:zkpassport-identity:v1";
+/// Page size used when walking the ticket contract's (owner, event) index.
+const MAX_TICKET_PAGE: u32 = 100;
+/// Events `resync_payments_config` mirrors in one call.
+const MAX_RESYNC_BATCH: u32 = 8;
 
 #[allow(dead_code)]
 #[contractclient(name = "AnonymousClaimVerifierClient")]
@@ -39,8 +48,22 @@ trait AnonymousClaimVerifier {
     fn verify(env: Env, proof: Bytes, public_inputs: Bytes) -> bool;
 }
 
+#[allow(dead_code)]
+#[contractclient(name = "ZkPassportVerifierClient")]
+trait ZkPassportVerifier {
+    fn verify(env: Env, proof: Bytes, public_inputs: Bytes) -> bool;
+}
+
 fn anonymous_claim_scope(env: &Env, event_id: &Symbol) -> BytesN<32> {
-    let mut preimage = Bytes::from_slice(env, ANONYMOUS_CLAIM_DOMAIN);
+    claim_scope(env, ANONYMOUS_CLAIM_DOMAIN, event_id)
+}
+
+fn zk_passport_scope(env: &Env, event_id: &Symbol) -> BytesN<32> {
+    claim_scope(env, ZK_PASSPORT_DOMAIN, event_id)
+}
+
+fn claim_scope(env: &Env, domain: &[u8], event_id: &Symbol) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, domain);
     preimage.extend_from_slice(&env.ledger().network_id().to_array());
     preimage.append(&env.current_contract_address().to_xdr(env));
     preimage.append(&event_id.to_xdr(env));
@@ -51,6 +74,18 @@ fn anonymous_claim_scope(env: &Env, event_id: &Symbol) -> BytesN<32> {
     BytesN::from_array(env, &scope)
 }
 
+/// Field-sized commitment to the wallet a ZkPassport proof is made for, so a
+/// claim seen on-chain cannot be reused from another wallet.
+fn zk_passport_holder(env: &Env, holder: &Address) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, ZK_PASSPORT_DOMAIN);
+    preimage.append(&holder.to_xdr(env));
+
+    let digest: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let mut commitment = digest.to_array();
+    commitment[..16].fill(0);
+    BytesN::from_array(env, &commitment)
+}
+
 fn append_u32_field(bytes: &mut Bytes, value: u32) {
     let mut field = [0u8; 32];
     field[28..].copy_from_slice(&value.to_be_bytes());
@@ -72,6 +107,39 @@ fn anonymous_claim_public_inputs(
     inputs
 }
 
+fn zk_passport_public_inputs(
+    env: &Env,
+    event_id: &Symbol,
+    holder: &Address,
+    claim: &ZkPassportClaim,
+) -> Bytes {
+    let mut inputs = Bytes::new(env);
+    inputs.extend_from_slice(&zk_passport_scope(env, event_id).to_array());
+    append_u32_field(&mut inputs, claim.claim_type.clone() as u32);
+    append_u32_field(&mut inputs, claim.expiry_ledger);
+    inputs.extend_from_slice(&claim.nullifier.to_array());
+    inputs.extend_from_slice(&zk_passport_holder(env, holder).to_array());
+    inputs
+}
+
+/// Check a ZkPassport proof against this event's scope and the wallet the
+/// ticket goes to. The nullifier is a public input, so a verified claim cannot
+/// be replayed under a fresh one, nor from another wallet.
+fn verify_zk_passport_claim(
+    env: &Env,
+    event_id: &Symbol,
+    holder: &Address,
+    claim: &ZkPassportClaim,
+) -> Result<(), EventError> {
+    let verifier = storage::get_zk_passport_verifier(env)?;
+    let verifier_client = ZkPassportVerifierClient::new(env, &verifier);
+    let public_inputs = zk_passport_public_inputs(env, event_id, holder, claim);
+    match verifier_client.try_verify(&claim.proof, &public_inputs) {
+        Ok(Ok(true)) => Ok(()),
+        _ => Err(EventError::ZkProofInvalid),
+    }
+}
+
 #[contract]
 pub struct EventContract;
 
@@ -90,7 +158,12 @@ impl EventContract {
         storage::set_payments_contract(&env, &payments_contract);
 
         let ticket_client = TicketContractClient::new(&env, &ticket_contract);
-        let _ = ticket_client.try_set_event_contract(&admin, &env.current_contract_address());
+        // A ticket contract that is not ready yet must not block setup, but
+        // later syncs will fail until the link is made, so report it.
+        let linked = ticket_client.try_set_event_contract(&admin, &env.current_contract_address());
+        if !matches!(linked, Ok(Ok(()))) {
+            emit_ticket_link_failed(&env, &ticket_contract);
+        }
 
         Ok(())
     }
@@ -198,22 +271,19 @@ impl EventContract {
         if has_linked_contracts(&env) {
             let payments_contract = get_payments_contract(&env)?;
             let payments_client = PaymentsContractClient::new(&env, &payments_contract);
-            payments_client.sync_event_config(
-                &env.current_contract_address(),
-                &params.event_id,
-                &params.organizer,
-                &params.payout_token,
-                &params.allow_anonymous,
-                &params.requires_verification,
-                &params.max_tickets_per_user,
-                &event.max_supply,
-                &event.event_start_ledger,
-                &event.event_end_ledger,
-                &event.withdrawal_delay_ledgers,
-                &event.resale_royalty_bps,
-                &event.max_resale_price,
-                &event.allow_free_ticket_transfer,
-            );
+            sync_payments_config(&env, &payments_client, &event);
+            sync_transfer_policy(&env, &event)?;
+            // Lock the organizer's bond against the event's full face value;
+            // the payments contract takes nothing while bonds are disabled.
+            let mut face_value = 0i128;
+            for tier in event.tiers.iter() {
+                face_value = tier
+                    .price
+                    .checked_mul(tier.capacity as i128)
+                    .and_then(|tier_value| face_value.checked_add(tier_value))
+                    .ok_or(EventError::InvalidPrice)?;
+            }
+            payments_client.lock_event_bond(&params.event_id, &face_value);
             // Register the (immutable) revenue split alongside the event config so
             // the payments contract can pay each recipient independently.
             if !params.revenue_splits.is_empty() {
@@ -308,22 +378,8 @@ impl EventContract {
         if has_linked_contracts(&env) {
             let payments_contract = get_payments_contract(&env)?;
             let payments_client = PaymentsContractClient::new(&env, &payments_contract);
-            payments_client.sync_event_config(
-                &env.current_contract_address(),
-                &params.event_id,
-                &event.organizer,
-                &event.payout_token,
-                &event.allow_anonymous,
-                &event.requires_verification,
-                &event.max_tickets_per_user,
-                &event.max_supply,
-                &event.event_start_ledger,
-                &event.event_end_ledger,
-                &event.withdrawal_delay_ledgers,
-                &event.resale_royalty_bps,
-                &event.max_resale_price,
-                &event.allow_free_ticket_transfer,
-            );
+            sync_payments_config(&env, &payments_client, &event);
+            sync_transfer_policy(&env, &event)?;
         }
         emit_event_updated(&env, &event);
 
@@ -386,6 +442,12 @@ impl EventContract {
         event.tiers.push_back(new_tier.clone());
 
         save_event(&env, &event_id, &event);
+        if has_linked_contracts(&env) {
+            let payments_contract = get_payments_contract(&env)?;
+            let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+            sync_payments_config(&env, &payments_client, &event);
+            sync_transfer_policy(&env, &event)?;
+        }
 
         Ok(new_tier)
     }
@@ -455,6 +517,12 @@ impl EventContract {
         }
 
         save_event(&env, &event_id, &event);
+        if has_linked_contracts(&env) {
+            let payments_contract = get_payments_contract(&env)?;
+            let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+            sync_payments_config(&env, &payments_client, &event);
+            sync_transfer_policy(&env, &event)?;
+        }
         Ok(())
     }
     pub fn update_event_status(
@@ -484,6 +552,9 @@ impl EventContract {
 
         update_event(&env, &event_id, &event)?;
         emit_status_changed(&env, &event_id, &old_status, &new_status);
+        if new_status == EventStatus::Completed {
+            close_ticket_event(&env, &event_id, false)?;
+        }
 
         Ok(())
     }
@@ -508,6 +579,7 @@ impl EventContract {
         emit_status_changed(&env, &event_id, &old_status, &EventStatus::Cancelled);
         let privacy = storage::get_event_privacy(&env, &event_id);
         emit_event_cancelled(&env, &event_id, &organizer, &privacy);
+        close_ticket_event(&env, &event_id, true)?;
         if has_linked_contracts(&env) {
             let payments_contract = get_payments_contract(&env)?;
             let payments_client = PaymentsContractClient::new(&env, &payments_contract);
@@ -635,23 +707,9 @@ impl EventContract {
         if has_linked_contracts(&env) {
             let payments_contract = get_payments_contract(&env)?;
             let payments_client = PaymentsContractClient::new(&env, &payments_contract);
-            payments_client.sync_event_config(
-                &env.current_contract_address(),
-                &event_id,
-                &event.organizer,
-                &event.payout_token,
-                &event.allow_anonymous,
-                &event.requires_verification,
-                &event.max_tickets_per_user,
-                &event.max_supply,
-                &event.event_start_ledger,
-                &event.event_end_ledger,
-                &event.withdrawal_delay_ledgers,
-                &event.resale_royalty_bps,
-                &event.max_resale_price,
-                &event.allow_free_ticket_transfer,
-            );
+            sync_payments_config(&env, &payments_client, &event);
             payments_client.resume_event(&event_id, &event.organizer);
+            sync_transfer_policy(&env, &event)?;
         }
 
         Ok(())
@@ -677,26 +735,376 @@ impl EventContract {
         }
         let ticket_contract = get_ticket_contract(&env)?;
         let ticket_client = TicketContractClient::new(&env, &ticket_contract);
-        let mut revocable: Option<u64> = None;
-        for tid in ticket_client.get_tickets_by_owner(&attendee).iter() {
-            let minted = ticket_client.get_ticket(&tid);
-            if minted.event_id == event_id
-                && !minted.is_used
-                && minted.status == ticket_contract::TicketStatus::Valid
-            {
-                revocable = Some(tid);
-                break;
-            }
-        }
-        let revocable = revocable.ok_or(EventError::NoRefundableTicket)?;
+        let revocable = first_valid_ticket(&ticket_client, &attendee, &event_id)
+            .ok_or(EventError::NoRefundableTicket)?;
         payments_client.request_postponement_refund(&attendee, &ticket_id);
         ticket_client.cancel_ticket(&revocable, &attendee);
-        if !has_valid_ticket_for_event(&ticket_client, &attendee, &event_id) {
+        if !ticket_client.has_valid_ticket(&attendee, &event_id, &None) {
             storage::remove_registration(&env, &event_id, &attendee);
         }
 
         Ok(())
     }
+    /// Void a ticket as the event organizer or the platform admin.
+    ///
+    /// The ticket is revoked in the ticket contract, its seat goes back to its
+    /// tier, and the ticket's share of the linked payment is refunded: directly
+    /// to Standard payers, as a claimable refund for Private/Anonymous ones.
+    /// The tier is the ticket's current one from `get_ticket_tier`, which
+    /// follows upgrades and downgrades; a tier since removed from the event
+    /// fails with `TierNotFound`.
+    /// Every ticket of a batch purchase is linked to the shared payment and
+    /// refunds its own tier price from it.
+    pub fn revoke_ticket(
+        env: Env,
+        caller: Address,
+        ticket_id: u64,
+        reason: RevocationReason,
+    ) -> Result<(), EventError> {
+        caller.require_auth();
+
+        let ticket_client = TicketContractClient::new(&env, &get_ticket_contract(&env)?);
+        let payments_client = PaymentsContractClient::new(&env, &get_payments_contract(&env)?);
+        let minted = ticket_client.get_ticket(&ticket_id);
+        let event_id = minted.event_id.clone();
+
+        let mut event = storage::get_event(&env, &event_id)?;
+        if caller != event.organizer && storage::get_admin(&env).ok() != Some(caller.clone()) {
+            return Err(EventError::Unauthorized);
+        }
+        if minted.status != ticket_contract::TicketStatus::Valid {
+            return Err(EventError::TicketNotRevocable);
+        }
+
+        let payment_id = payments_client.get_minted_ticket_payment(&ticket_id);
+        let index = tier_index(&event, ticket_client.get_ticket_tier(&ticket_id))
+            .ok_or(EventError::TierNotFound)?;
+        let mut tier = event.tiers.get(index).ok_or(EventError::TierNotFound)?;
+
+        ticket_client.revoke_ticket(&ticket_id, &reason);
+
+        let refund_amount = if payment_id.is_some() && tier.price > 0 {
+            payments_client.refund_minted_ticket(&ticket_id, &tier.price)
+        } else {
+            0
+        };
+
+        tier.sold = tier.sold.saturating_sub(1);
+        event.sold_count = event.sold_count.saturating_sub(1);
+        event.tiers.set(index, tier.clone());
+        update_event(&env, &event_id, &event)?;
+
+        if !ticket_client.has_valid_ticket(&minted.owner, &event_id, &None) {
+            storage::remove_registration(&env, &event_id, &minted.owner);
+        }
+
+        emit_ticket_revoked(
+            &env,
+            &event_id,
+            ticket_id,
+            &caller,
+            &reason,
+            tier.tier_id,
+            refund_amount,
+        );
+
+        Ok(())
+    }
+
+    /// Move a held ticket to another tier of the same event.
+    ///
+    /// A dearer tier charges the price difference from the holder; a cheaper
+    /// one refunds it against the ticket's original payment. A live
+    /// reservation the holder made for the target tier is consumed instead of
+    /// requiring free capacity.
+    pub fn upgrade_ticket(
+        env: Env,
+        nonce: u64,
+        owner: Address,
+        ticket_id: u64,
+        new_tier_id: u32,
+    ) -> Result<(), EventError> {
+        owner.require_auth();
+
+        let ticket_client = TicketContractClient::new(&env, &get_ticket_contract(&env)?);
+        let payments_client = PaymentsContractClient::new(&env, &get_payments_contract(&env)?);
+        let minted = ticket_client.get_ticket(&ticket_id);
+        if minted.owner != owner {
+            return Err(EventError::Unauthorized);
+        }
+        if minted.is_used || minted.status != ticket_contract::TicketStatus::Valid {
+            return Err(EventError::TicketNotUpgradable);
+        }
+        let from_tier_id = ticket_client.get_ticket_tier(&ticket_id);
+        if from_tier_id == new_tier_id {
+            return Err(EventError::InvalidInput);
+        }
+
+        let event_id = minted.event_id.clone();
+        let mut event = storage::get_event(&env, &event_id)?;
+        if event.status != EventStatus::Active {
+            return Err(EventError::EventNotActive);
+        }
+
+        let from_index = tier_index(&event, from_tier_id).ok_or(EventError::TierNotFound)?;
+        let to_index = tier_index(&event, new_tier_id).ok_or(EventError::TierNotFound)?;
+        let mut from_tier = event
+            .tiers
+            .get(from_index)
+            .ok_or(EventError::TierNotFound)?;
+        let mut to_tier = event.tiers.get(to_index).ok_or(EventError::TierNotFound)?;
+
+        let has_res = storage::has_reservation(&env, &event_id, &owner)
+            && storage::get_reservation(&env, &event_id, &owner).is_ok_and(|r| {
+                r.tier_id == new_tier_id && r.expires_at >= env.ledger().timestamp()
+            });
+        if !has_res && to_tier.sold + to_tier.reserved >= to_tier.capacity {
+            return Err(EventError::TierSoldOut);
+        }
+
+        let price_delta = to_tier.price - from_tier.price;
+        if price_delta > 0 {
+            require_settleable_privacy(&env, &event_id)?;
+            payments_client.pay_ticket_upgrade(&nonce, &owner, &event_id, &ticket_id, &price_delta);
+        } else if price_delta < 0 {
+            if payments_client
+                .get_minted_ticket_payment(&ticket_id)
+                .is_none()
+            {
+                return Err(EventError::DowngradeRefundUnavailable);
+            }
+            payments_client.refund_minted_ticket(&ticket_id, &-price_delta);
+        }
+
+        ticket_client.set_ticket_tier(&ticket_id, &new_tier_id);
+
+        if has_res {
+            to_tier.reserved = to_tier.reserved.saturating_sub(1);
+            storage::remove_reservation(&env, &event_id, &owner);
+        }
+        from_tier.sold = from_tier.sold.saturating_sub(1);
+        to_tier.sold += 1;
+        event.tiers.set(from_index, from_tier.clone());
+        event.tiers.set(to_index, to_tier.clone());
+        update_event(&env, &event_id, &event)?;
+
+        emit_ticket_upgraded(
+            &env,
+            &event_id,
+            ticket_id,
+            &owner,
+            from_tier.tier_id,
+            new_tier_id,
+            price_delta,
+        );
+
+        Ok(())
+    }
+
+    /// Let attendees buy tickets in installments. `max_installments` of 0
+    /// disables plans; the basis points apply to the tier price and the
+    /// deposit respectively.
+    pub fn set_installment_settings(
+        env: Env,
+        organizer: Address,
+        event_id: Symbol,
+        max_installments: u32,
+        min_deposit_bps: u32,
+        penalty_bps: u32,
+    ) -> Result<(), EventError> {
+        organizer.require_auth();
+        let event = storage::get_event(&env, &event_id)?;
+        if event.organizer != organizer {
+            return Err(EventError::Unauthorized);
+        }
+        if min_deposit_bps > 10_000 || penalty_bps > 10_000 {
+            return Err(EventError::InvalidInput);
+        }
+        storage::set_installment_settings(
+            &env,
+            &event_id,
+            &InstallmentSettings {
+                max_installments,
+                min_deposit_bps,
+                penalty_bps,
+            },
+        );
+        Ok(())
+    }
+    pub fn get_installment_settings(env: Env, event_id: Symbol) -> InstallmentSettings {
+        storage::get_installment_settings(&env, &event_id)
+    }
+
+    /// Buy a `tier_id` ticket in installments. The deposit is charged now
+    /// and the ticket's inventory stays reserved until the last top-up
+    /// mints it through `pay_installment`, or a missed deadline frees it
+    /// through `release_installment_plan`.
+    pub fn start_installment_plan(
+        env: Env,
+        nonce: u64,
+        holder: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        schedule: InstallmentSchedule,
+    ) -> Result<(), EventError> {
+        holder.require_auth();
+
+        let mut event = storage::get_event(&env, &event_id)?;
+        if event.status != EventStatus::Active {
+            return Err(EventError::EventNotActive);
+        }
+        require_settleable_privacy(&env, &event_id)?;
+        let settings = storage::get_installment_settings(&env, &event_id);
+        if settings.max_installments == 0 {
+            return Err(EventError::InstallmentsDisabled);
+        }
+
+        let index = tier_index(&event, tier_id).ok_or(EventError::TierNotFound)?;
+        let mut tier = event.tiers.get(index).ok_or(EventError::TierNotFound)?;
+        let min_deposit = tier.price * settings.min_deposit_bps as i128 / 10_000;
+        if tier.price == 0
+            || schedule.installments.len() > settings.max_installments
+            || schedule.deposit < min_deposit
+            || schedule
+                .installments
+                .iter()
+                .any(|installment| installment.due_at > event.event_date)
+        {
+            return Err(EventError::InvalidInstallmentSchedule);
+        }
+        if event.sold_count >= event.max_supply {
+            return Err(EventError::EventSoldOut);
+        }
+        if tier.sold + tier.reserved >= tier.capacity {
+            return Err(EventError::TierSoldOut);
+        }
+
+        let payments_client =
+            PaymentsContractClient::new(&env, &storage::get_payments_contract(&env)?);
+        // Identity-capped events register one ticket at a time through
+        // `register_with_identity`.
+        if payments_client.get_identity_limit(&event_id) > 0 {
+            return Err(EventError::IdentityRequired);
+        }
+        payments_client.open_installment_plan(
+            &nonce,
+            &holder,
+            &event_id,
+            &tier_id,
+            &schedule,
+            &settings.penalty_bps,
+        );
+
+        tier.reserved += 1;
+        event.tiers.set(index, tier);
+        update_event(&env, &event_id, &event)?;
+        emit_installment_plan_started(
+            &env,
+            &event_id,
+            &holder,
+            tier_id,
+            schedule.deposit,
+            schedule.installments.len(),
+        );
+        Ok(())
+    }
+
+    /// Pay the next installment of the holder's plan. The last one mints
+    /// the ticket against the inventory the plan reserved.
+    pub fn pay_installment(
+        env: Env,
+        nonce: u64,
+        holder: Address,
+        event_id: Symbol,
+    ) -> Result<(), EventError> {
+        holder.require_auth();
+
+        let mut event = storage::get_event(&env, &event_id)?;
+        if event.status != EventStatus::Active {
+            return Err(EventError::EventNotActive);
+        }
+        let payments_client =
+            PaymentsContractClient::new(&env, &storage::get_payments_contract(&env)?);
+        let plan = payments_client.pay_installment(&nonce, &holder, &event_id);
+        let amount = plan
+            .schedule
+            .installments
+            .get(plan.paid_count - 1)
+            .map(|installment| installment.amount)
+            .unwrap_or(0);
+
+        if plan.status == InstallmentStatus::Completed {
+            let index = tier_index(&event, plan.tier_id).ok_or(EventError::TierNotFound)?;
+            let mut tier = event.tiers.get(index).ok_or(EventError::TierNotFound)?;
+            let ticket_client =
+                TicketContractClient::new(&env, &storage::get_ticket_contract(&env)?);
+            let minted_ticket_id = ticket_client.mint_ticket(
+                &event.event_id,
+                &event.organizer,
+                &holder,
+                &tier.tier_id,
+            );
+            payments_client.link_installment_ticket(&event_id, &holder, &minted_ticket_id);
+            if !storage::is_registered(&env, &event_id, &holder) {
+                storage::save_registration(&env, &event_id, &holder);
+            }
+
+            tier.reserved = tier.reserved.saturating_sub(1);
+            tier.sold += 1;
+            event.sold_count += 1;
+            event.tiers.set(index, tier.clone());
+            update_event(&env, &event_id, &event)?;
+            let privacy = storage::get_event_privacy(&env, &event_id);
+            emit_registration(&env, &event_id, &holder, tier.tier_id, tier.sold, &privacy);
+        }
+
+        emit_installment_paid(
+            &env,
+            &event_id,
+            &holder,
+            amount,
+            plan.schedule.installments.len() - plan.paid_count,
+        );
+        Ok(())
+    }
+
+    /// Default a plan that missed an installment deadline: its inventory
+    /// returns to the tier and everything paid is refunded less the
+    /// event's penalty on the deposit. Once the event is cancelled, plans
+    /// are released at any time and refunded in full. Anyone may call this.
+    pub fn release_installment_plan(
+        env: Env,
+        event_id: Symbol,
+        holder: Address,
+    ) -> Result<(), EventError> {
+        let mut event = storage::get_event(&env, &event_id)?;
+        let payments_client =
+            PaymentsContractClient::new(&env, &storage::get_payments_contract(&env)?);
+        let plan = payments_client
+            .get_installment_plan(&event_id, &holder)
+            .filter(|plan| plan.status == InstallmentStatus::Active)
+            .ok_or(EventError::InstallmentPlanNotFound)?;
+        let cancelled = event.status == EventStatus::Cancelled;
+        let refunded = if cancelled {
+            payments_client.cancel_installment_plan(&event_id, &holder)
+        } else {
+            payments_client.default_installment_plan(&event_id, &holder)
+        };
+
+        if let Some(index) = tier_index(&event, plan.tier_id) {
+            let mut tier = event.tiers.get(index).ok_or(EventError::TierNotFound)?;
+            tier.reserved = tier.reserved.saturating_sub(1);
+            event.tiers.set(index, tier);
+            update_event(&env, &event_id, &event)?;
+        }
+        if cancelled {
+            emit_installment_plan_cancelled(&env, &event_id, &holder, plan.tier_id, refunded);
+        } else {
+            emit_installment_plan_defaulted(&env, &event_id, &holder, plan.tier_id, refunded);
+        }
+        Ok(())
+    }
+
     pub fn reserve_ticket(
         env: Env,
         attendee: Address,
@@ -809,161 +1217,73 @@ impl EventContract {
         event_id: Symbol,
         tier_id: u32,
         _is_verified: bool,
+        email_hash: Option<BytesN<32>>,
+    ) -> Result<(), EventError> {
+        register(&env, nonce, attendee, event_id, tier_id, email_hash, None)
+    }
+
+    /// Register under a ZkPassport identity so the event's per-identity cap
+    /// applies across wallets. The claim's proof must verify for this event's
+    /// scope (`get_zk_passport_scope`), which keeps purchases unlinkable
+    /// across events, and for the attendee (`get_zk_passport_holder`), so
+    /// another wallet cannot reuse it.
+    pub fn register_with_identity(
+        env: Env,
+        nonce: u64,
+        attendee: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        claim: ZkPassportClaim,
+        email_hash: Option<BytesN<32>>,
+    ) -> Result<(), EventError> {
+        if claim.expiry_ledger < env.ledger().sequence() {
+            return Err(EventError::ZkProofExpired);
+        }
+        let zk_config = storage::get_zk_verification_config(&env, &event_id);
+        if zk_config.enabled
+            && zk_config.required_claim_type != ZkClaimType::Any
+            && claim.claim_type != zk_config.required_claim_type
+        {
+            return Err(EventError::ZkClaimTypeMismatch);
+        }
+        verify_zk_passport_claim(&env, &event_id, &attendee, &claim)?;
+        register(
+            &env,
+            nonce,
+            attendee,
+            event_id,
+            tier_id,
+            email_hash,
+            Some(claim.nullifier),
+        )
+    }
+
+    /// Purchase `count` tickets for the same tier in a single atomic call.
+    /// Mirrors `register_for_event` but mints multiple tickets and charges the
+    /// combined price once. Flat argument list matches `register_for_event`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn batch_register_for_event(
+        env: Env,
+        nonce: u64,
+        attendee: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        count: u32,
+        _is_verified: bool,
         _email_hash: Option<BytesN<32>>,
     ) -> Result<(), EventError> {
         attendee.require_auth();
 
+        if count == 0 || count > 100 {
+            return Err(EventError::InvalidInput);
+        }
+
         let mut event = storage::get_event(&env, &event_id)?;
 
         if event.status != EventStatus::Active {
             return Err(EventError::EventNotActive);
         }
-        // Enforce the event's configured payment privacy before any state is
-        // mutated. This gates both free and paid registrations, so a
-        // Private/Anonymous event never stores a raw attendee via this path.
-        require_settleable_privacy(&env, &event_id)?;
-        {
-            let mut req_price: Option<i128> = None;
-            for t in event.tiers.iter() {
-                if t.tier_id == tier_id {
-                    req_price = Some(t.price);
-                    break;
-                }
-            }
-            if req_price == Some(0) {
-                let now = env.ledger().timestamp();
-                let settings = storage::get_claim_settings(&env, &event_id);
-                if settings.max_free_claims > 0 {
-                    let count = storage::get_free_claim_count(&env, &event_id, &attendee);
-                    if count >= settings.max_free_claims {
-                        return Err(EventError::ClaimLimitExceeded);
-                    }
-                }
-                if settings.cooldown_secs > 0 {
-                    let last = storage::get_last_free_claim(&env, &event_id, &attendee);
-                    if last > 0 && now < last + settings.cooldown_secs {
-                        return Err(EventError::ClaimCooldownActive);
-                    }
-                }
-            }
-        }
-
-        if storage::is_registered(&env, &event_id, &attendee) {
-            return Err(EventError::AlreadyRegistered);
-        }
-
-        let has_res = storage::has_reservation(&env, &event_id, &attendee);
-        let mut tier_index = None;
-
-        if has_res {
-            let reservation = storage::get_reservation(&env, &event_id, &attendee)?;
-            if reservation.expires_at < env.ledger().timestamp() {
-                return Err(EventError::ReservationExpired);
-            }
-            if reservation.tier_id != tier_id {
-                return Err(EventError::InvalidInput);
-            }
-
-            for i in 0..event.tiers.len() {
-                let tier = event.tiers.get(i).ok_or(EventError::TierNotFound)?;
-                if tier.tier_id == tier_id {
-                    tier_index = Some(i);
-                    break;
-                }
-            }
-        } else {
-            for i in 0..event.tiers.len() {
-                let tier = event.tiers.get(i).ok_or(EventError::TierNotFound)?;
-                if tier.tier_id == tier_id {
-                    tier_index = Some(i);
-                    break;
-                }
-            }
-        }
-
-        let index = tier_index.ok_or(EventError::TierNotFound)?;
-        let mut tier = event.tiers.get(index).ok_or(EventError::TierNotFound)?;
-
-        if event.sold_count >= event.max_supply {
-            return Err(EventError::EventSoldOut);
-        }
-
-        if !has_res && tier.sold + tier.reserved >= tier.capacity {
-            return Err(EventError::TierSoldOut);
-        }
-
-        let payments_contract = storage::get_payments_contract(&env)?;
-        let ticket_contract = storage::get_ticket_contract(&env)?;
-
-        if tier.price > 0 {
-            let payments_client = PaymentsContractClient::new(&env, &payments_contract);
-            let token = payments_client.get_accepted_token();
-
-            payments_client.pay_for_ticket(
-                &nonce,
-                &attendee,
-                &event_id,
-                &tier.price,
-                &_email_hash,
-                &token,
-                &PaymentPrivacy::Standard,
-                &None,
-                &None,
-            );
-        }
-
-        let ticket_client = TicketContractClient::new(&env, &ticket_contract);
-        ticket_client.mint_ticket(&event.event_id, &event.organizer, &attendee);
-
-        storage::save_registration(&env, &event_id, &attendee);
-
-        if has_res {
-            if tier.reserved > 0 {
-                tier.reserved -= 1;
-            }
-            storage::remove_reservation(&env, &event_id, &attendee);
-        }
-        if tier.price == 0 {
-            storage::increment_free_claim_count(&env, &event_id, &attendee);
-            storage::set_last_free_claim(&env, &event_id, &attendee, env.ledger().timestamp());
-        }
-
-        tier.sold += 1;
-        event.sold_count += 1;
-        event.tiers.set(index, tier.clone());
-        update_event(&env, &event_id, &event)?;
-        let privacy = storage::get_event_privacy(&env, &event_id);
-        emit_registration(&env, &event_id, &attendee, tier_id, tier.sold, &privacy);
-
-        Ok(())
-    }
-
-    /// Purchase `count` tickets for the same tier in a single atomic call.
-    /// Mirrors `register_for_event` but mints multiple tickets and charges the
-    /// combined price once. Flat argument list matches `register_for_event`.
-    #[allow(clippy::too_many_arguments)]
-    pub fn batch_register_for_event(
-        env: Env,
-        nonce: u64,
-        attendee: Address,
-        event_id: Symbol,
-        tier_id: u32,
-        count: u32,
-        _is_verified: bool,
-        _email_hash: Option<BytesN<32>>,
-    ) -> Result<(), EventError> {
-        attendee.require_auth();
-
-        if count == 0 || count > 100 {
-            return Err(EventError::InvalidInput);
-        }
-
-        let mut event = storage::get_event(&env, &event_id)?;
-
-        if event.status != EventStatus::Active {
-            return Err(EventError::EventNotActive);
-        }
-
+
         require_settleable_privacy(&env, &event_id)?;
 
         let mut tier_index = None;
@@ -1010,27 +1330,45 @@ impl EventContract {
 
         let payments_contract = storage::get_payments_contract(&env)?;
         let ticket_contract = storage::get_ticket_contract(&env)?;
+        let payments_client = PaymentsContractClient::new(&env, &payments_contract);
 
-        if tier.price > 0 {
-            let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+        // Batches carry no identity, so identity-capped events must register
+        // one ticket at a time through `register_with_identity`.
+        if payments_client.get_identity_limit(&event_id) > 0 {
+            return Err(EventError::IdentityRequired);
+        }
+
+        let payment_id = if tier.price > 0 {
             let token = payments_client.get_accepted_token();
 
-            payments_client.pay_for_ticket(
+            Some(payments_client.pay_for_tier(
                 &nonce,
                 &attendee,
                 &event_id,
+                &tier_id,
+                &count,
                 &(tier.price * count as i128),
                 &_email_hash,
                 &token,
-                &PaymentPrivacy::Standard,
-                &None,
                 &None,
-            );
-        }
+            ))
+        } else {
+            None
+        };
 
         let ticket_client = TicketContractClient::new(&env, &ticket_contract);
-        let _ticket_ids =
-            ticket_client.batch_mint_ticket(&event.event_id, &event.organizer, &attendee, &count);
+        let ticket_ids = ticket_client.batch_mint_ticket(
+            &event.event_id,
+            &event.organizer,
+            &attendee,
+            &tier_id,
+            &count,
+        );
+        // One payment covers the whole batch; every minted ticket points back
+        // to it so each can be refunded its share.
+        if let Some(payment_id) = payment_id {
+            payments_client.link_batch_minted_tickets(&payment_id, &ticket_ids);
+        }
 
         if !storage::is_registered(&env, &event_id, &attendee) {
             storage::save_registration(&env, &event_id, &attendee);
@@ -1177,6 +1515,130 @@ impl EventContract {
     pub fn get_claim_settings(env: Env, event_id: Symbol) -> ClaimSettings {
         storage::get_claim_settings(&env, &event_id)
     }
+    /// Cap tickets per verified identity across wallets; 0 lifts the cap.
+    /// Capped events only sell through `register_with_identity`, since a
+    /// wallet alone proves nothing. The payments contract keeps the cap.
+    pub fn set_identity_limit(
+        env: Env,
+        organizer: Address,
+        event_id: Symbol,
+        max_tickets_per_identity: u32,
+    ) -> Result<(), EventError> {
+        organizer.require_auth();
+        let event = storage::get_event(&env, &event_id)?;
+        if event.organizer != organizer {
+            return Err(EventError::Unauthorized);
+        }
+        let payments_contract = get_payments_contract(&env)?;
+        let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+        payments_client.set_identity_limit(&event_id, &max_tickets_per_identity);
+        Ok(())
+    }
+    /// Choose the allowlisted tokens, besides the payout token, that
+    /// attendees may pay in. The payments contract rejects tokens that are
+    /// not on its allowlist.
+    pub fn set_accepted_tokens(
+        env: Env,
+        organizer: Address,
+        event_id: Symbol,
+        tokens: soroban_sdk::Vec<Address>,
+    ) -> Result<(), EventError> {
+        organizer.require_auth();
+        let event = storage::get_event(&env, &event_id)?;
+        if event.organizer != organizer {
+            return Err(EventError::Unauthorized);
+        }
+        if event.status == EventStatus::Completed || event.status == EventStatus::Cancelled {
+            return Err(EventError::EventNotUpdatable);
+        }
+        storage::set_accepted_tokens(&env, &event_id, &tokens);
+        if has_linked_contracts(&env) {
+            let payments_contract = get_payments_contract(&env)?;
+            let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+            sync_payments_config(&env, &payments_client, &event);
+        }
+        Ok(())
+    }
+    /// Price `tier_id` in the payments oracle's reference unit (e.g. USD) so
+    /// attendees can buy it in any accepted token at the quoted rate through
+    /// the payments contract's `pay_for_ticket_quoted`. Registration here
+    /// still charges the tier's payout-token price, which must cover the
+    /// quote. `None` prices the tier in the payout token again.
+    pub fn set_tier_reference_price(
+        env: Env,
+        organizer: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        price: Option<i128>,
+    ) -> Result<(), EventError> {
+        organizer.require_auth();
+        let event = storage::get_event(&env, &event_id)?;
+        if event.organizer != organizer {
+            return Err(EventError::Unauthorized);
+        }
+        if event.status == EventStatus::Completed || event.status == EventStatus::Cancelled {
+            return Err(EventError::EventNotUpdatable);
+        }
+        if tier_index(&event, tier_id).is_none() {
+            return Err(EventError::TierNotFound);
+        }
+        let mut prices = storage::get_tier_reference_prices(&env, &event_id);
+        match price {
+            Some(price) if price <= 0 => return Err(EventError::InvalidPrice),
+            Some(price) => prices.set(tier_id, price),
+            None => {
+                prices.remove(tier_id);
+            }
+        }
+        storage::set_tier_reference_prices(&env, &event_id, &prices);
+        if has_linked_contracts(&env) {
+            let payments_contract = get_payments_contract(&env)?;
+            let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+            sync_payments_config(&env, &payments_client, &event);
+        }
+        Ok(())
+    }
+    /// Choose how tickets for this event may change hands. Tickets already
+    /// minted keep the policy they were issued with; the lock window applies
+    /// to all of them.
+    pub fn set_transfer_settings(
+        env: Env,
+        organizer: Address,
+        event_id: Symbol,
+        policy: TransferPolicy,
+        lock_ledgers: u32,
+    ) -> Result<(), EventError> {
+        organizer.require_auth();
+        let event = storage::get_event(&env, &event_id)?;
+        if event.organizer != organizer {
+            return Err(EventError::Unauthorized);
+        }
+        if event.status == EventStatus::Completed || event.status == EventStatus::Cancelled {
+            return Err(EventError::EventNotUpdatable);
+        }
+        let settings = TransferSettings {
+            policy,
+            lock_ledgers,
+        };
+        storage::set_transfer_settings(&env, &event_id, &settings);
+        if has_linked_contracts(&env) {
+            let ticket_client = TicketContractClient::new(&env, &get_ticket_contract(&env)?);
+            ticket_client.sync_transfer_policy(
+                &event_id,
+                &settings.policy,
+                &settings.lock_ledgers,
+                &event.event_start_ledger,
+            );
+        }
+        Ok(())
+    }
+    pub fn get_transfer_settings(
+        env: Env,
+        event_id: Symbol,
+    ) -> Result<TransferSettings, EventError> {
+        let event = storage::get_event(&env, &event_id)?;
+        Ok(transfer_settings_for(&env, &event))
+    }
     /// Configures the verifier once so an admin cannot later replace the proof
     /// system with one that accepts forged claims.
     pub fn set_anonymous_claim_verifier(
@@ -1197,6 +1659,34 @@ impl EventContract {
     pub fn get_anonymous_claim_verifier(env: Env) -> Result<Address, EventError> {
         storage::get_anonymous_claim_verifier(&env)
     }
+    /// Configures the ZkPassport verifier once, for the same reason as
+    /// `set_anonymous_claim_verifier`.
+    pub fn set_zk_passport_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+    ) -> Result<(), EventError> {
+        admin.require_auth();
+        if storage::get_admin(&env)? != admin {
+            return Err(EventError::Unauthorized);
+        }
+        if storage::get_zk_passport_verifier(&env).is_ok() {
+            return Err(EventError::ZkPassportVerifierAlreadyConfigured);
+        }
+        storage::set_zk_passport_verifier(&env, &verifier);
+        Ok(())
+    }
+    pub fn get_zk_passport_verifier(env: Env) -> Result<Address, EventError> {
+        storage::get_zk_passport_verifier(&env)
+    }
+    pub fn get_zk_passport_scope(env: Env, event_id: Symbol) -> Result<BytesN<32>, EventError> {
+        storage::get_event(&env, &event_id)?;
+        Ok(zk_passport_scope(&env, &event_id))
+    }
+    /// Public input binding a ZkPassport proof to the wallet it is made for.
+    pub fn get_zk_passport_holder(env: Env, holder: Address) -> BytesN<32> {
+        zk_passport_holder(&env, &holder)
+    }
     pub fn get_anonymous_claim_scope(env: Env, event_id: Symbol) -> Result<BytesN<32>, EventError> {
         storage::get_event(&env, &event_id)?;
         Ok(anonymous_claim_scope(&env, &event_id))
@@ -1298,6 +1788,45 @@ impl EventContract {
 
         Ok(())
     }
+    /// Check an anonymous ticket in at the door and mint its commitment badge.
+    ///
+    /// The organizer scans the attendee's nullifier, which must belong to a
+    /// ticket claimed through `claim_anonymous_ticket`. Each nullifier checks in
+    /// once. `badge_commitment` is `sha256(preimage)` for a secret the attendee
+    /// keeps, so the resulting badge is not linkable to any address.
+    pub fn check_in_anonymous_ticket(
+        env: Env,
+        organizer: Address,
+        event_id: Symbol,
+        nullifier: BytesN<32>,
+        badge_commitment: BytesN<32>,
+    ) -> Result<(), EventError> {
+        organizer.require_auth();
+
+        let event = storage::get_event(&env, &event_id)?;
+        if event.organizer != organizer {
+            return Err(EventError::Unauthorized);
+        }
+        if storage::get_anonymous_ticket_commitment(&env, &event_id, &nullifier).is_none() {
+            return Err(EventError::AnonymousTicketNotFound);
+        }
+        if storage::is_anonymous_checked_in(&env, &event_id, &nullifier) {
+            return Err(EventError::AnonymousTicketAlreadyCheckedIn);
+        }
+
+        storage::set_anonymous_checked_in(&env, &event_id, &nullifier);
+        if has_linked_contracts(&env) {
+            let ticket_contract = get_ticket_contract(&env)?;
+            let ticket_client = TicketContractClient::new(&env, &ticket_contract);
+            ticket_client.mint_anonymous_badge(&event_id, &badge_commitment);
+        }
+        emit_anon_check_in(&env, &event_id);
+
+        Ok(())
+    }
+    pub fn is_anonymous_checked_in(env: Env, event_id: Symbol, nullifier: BytesN<32>) -> bool {
+        storage::is_anonymous_checked_in(&env, &event_id, &nullifier)
+    }
     pub fn set_anon_claim_settings(
         env: Env,
         organizer: Address,
@@ -1372,30 +1901,44 @@ impl EventContract {
         if tier.sold + tier.reserved >= tier.capacity {
             return Err(EventError::TierSoldOut);
         }
-        if tier.price > 0 && has_linked_contracts(&env) {
+        if has_linked_contracts(&env) {
             let payments_contract = get_payments_contract(&env)?;
             let payments_client = PaymentsContractClient::new(&env, &payments_contract);
-            let token = payments_client.get_accepted_token();
-            payments_client.pay_for_ticket(
-                &0u64,
-                &env.current_contract_address(),
-                &event_id,
-                &tier.price,
-                &None::<BytesN<32>>,
-                &token,
-                &PaymentPrivacy::Standard,
-                &None,
-                &None,
-            );
-        }
-        if has_linked_contracts(&env) {
+            let identity =
+                capped_identity(&payments_client, &event_id, Some(claim.nullifier.clone()))?;
+            if identity.is_some() {
+                verify_zk_passport_claim(&env, &event_id, &env.current_contract_address(), &claim)?;
+            }
+            let payment_id = if tier.price > 0 {
+                let token = payments_client.get_accepted_token();
+                Some(payments_client.pay_for_tier(
+                    &0u64,
+                    &env.current_contract_address(),
+                    &event_id,
+                    &tier.tier_id,
+                    &1,
+                    &tier.price,
+                    &None::<BytesN<32>>,
+                    &token,
+                    &identity,
+                ))
+            } else {
+                if let Some(identity) = &identity {
+                    payments_client.record_identity_purchase(&event_id, identity);
+                }
+                None
+            };
             let ticket_contract = get_ticket_contract(&env)?;
             let ticket_client = TicketContractClient::new(&env, &ticket_contract);
-            ticket_client.mint_ticket(
+            let minted_ticket_id = ticket_client.mint_ticket(
                 &event.event_id,
                 &event.organizer,
                 &env.current_contract_address(),
+                &tier_id,
             );
+            if let Some(payment_id) = payment_id {
+                payments_client.link_minted_ticket(&payment_id, &minted_ticket_id);
+            }
         }
         storage::save_zk_nullifier(&env, &event_id, &claim.nullifier);
         tier.sold += 1;
@@ -1429,6 +1972,33 @@ impl EventContract {
     pub fn contract_version(env: Env) -> u32 {
         storage::get_contract_version(&env)
     }
+    /// Mirror existing events into the payments contract again, for events
+    /// created before it learned tier prices: until then it accepts any
+    /// positive amount for their tickets. Unknown events are skipped; returns
+    /// how many were synced.
+    pub fn resync_payments_config(
+        env: Env,
+        admin: Address,
+        event_ids: soroban_sdk::Vec<Symbol>,
+    ) -> Result<u32, EventError> {
+        admin.require_auth();
+        if storage::get_admin(&env)? != admin {
+            return Err(EventError::Unauthorized);
+        }
+        if event_ids.len() > MAX_RESYNC_BATCH {
+            return Err(EventError::BatchTooLarge);
+        }
+        let payments_contract = get_payments_contract(&env)?;
+        let payments_client = PaymentsContractClient::new(&env, &payments_contract);
+        let mut synced = 0;
+        for event_id in event_ids.iter() {
+            if let Ok(event) = storage::get_event(&env, &event_id) {
+                sync_payments_config(&env, &payments_client, &event);
+                synced += 1;
+            }
+        }
+        Ok(synced)
+    }
     pub fn migrate(env: Env, admin: Address) -> Result<u32, EventError> {
         admin.require_auth();
 
@@ -1538,21 +2108,290 @@ fn validate_revenue_splits(
         .map_err(|_| EventError::InvalidRevenueSplit)
 }
 
-fn has_valid_ticket_for_event(
+/// Stored transfer settings, or the default implied by the event's resale
+/// terms: a royalty or price cap makes the marketplace the only way to move a
+/// ticket, so neither can be bypassed with a direct transfer. When free-ticket
+/// transfer is disabled, free tickets must not move either: an all-free event
+/// is `Disallowed`, and a mixed one is `MarketplaceOnly` since the marketplace
+/// already refuses to resell free tickets.
+fn transfer_settings_for(env: &Env, event: &Event) -> TransferSettings {
+    let free_tiers = event.tiers.iter().filter(|tier| tier.price == 0).count() as u32;
+    let restrict_free = !event.allow_free_ticket_transfer && free_tiers > 0;
+    storage::get_transfer_settings(env, &event.event_id).unwrap_or(TransferSettings {
+        policy: if restrict_free && free_tiers == event.tiers.len() {
+            TransferPolicy::Disallowed
+        } else if restrict_free || event.resale_royalty_bps > 0 || event.max_resale_price.is_some()
+        {
+            TransferPolicy::MarketplaceOnly
+        } else {
+            TransferPolicy::Open
+        },
+        lock_ledgers: 0,
+    })
+}
+
+/// Shared registration path. `identity` is the verified, event-scoped
+/// identity charged against the per-identity cap, when the caller supplied
+/// one.
+fn register(
+    env: &Env,
+    nonce: u64,
+    attendee: Address,
+    event_id: Symbol,
+    tier_id: u32,
+    email_hash: Option<BytesN<32>>,
+    identity: Option<BytesN<32>>,
+) -> Result<(), EventError> {
+    attendee.require_auth();
+
+    let mut event = storage::get_event(env, &event_id)?;
+
+    if event.status != EventStatus::Active {
+        return Err(EventError::EventNotActive);
+    }
+    // Enforce the event's configured payment privacy before any state is
+    // mutated. This gates both free and paid registrations, so a
+    // Private/Anonymous event never stores a raw attendee via this path.
+    require_settleable_privacy(env, &event_id)?;
+    {
+        let mut req_price: Option<i128> = None;
+        for t in event.tiers.iter() {
+            if t.tier_id == tier_id {
+                req_price = Some(t.price);
+                break;
+            }
+        }
+        if req_price == Some(0) {
+            let now = env.ledger().timestamp();
+            let settings = storage::get_claim_settings(env, &event_id);
+            if settings.max_free_claims > 0 {
+                let count = storage::get_free_claim_count(env, &event_id, &attendee);
+                if count >= settings.max_free_claims {
+                    return Err(EventError::ClaimLimitExceeded);
+                }
+            }
+            if settings.cooldown_secs > 0 {
+                let last = storage::get_last_free_claim(env, &event_id, &attendee);
+                if last > 0 && now < last + settings.cooldown_secs {
+                    return Err(EventError::ClaimCooldownActive);
+                }
+            }
+        }
+    }
+
+    if storage::is_registered(env, &event_id, &attendee) {
+        return Err(EventError::AlreadyRegistered);
+    }
+
+    let has_res = storage::has_reservation(env, &event_id, &attendee);
+    let mut tier_index = None;
+
+    if has_res {
+        let reservation = storage::get_reservation(env, &event_id, &attendee)?;
+        if reservation.expires_at < env.ledger().timestamp() {
+            return Err(EventError::ReservationExpired);
+        }
+        if reservation.tier_id != tier_id {
+            return Err(EventError::InvalidInput);
+        }
+
+        for i in 0..event.tiers.len() {
+            let tier = event.tiers.get(i).ok_or(EventError::TierNotFound)?;
+            if tier.tier_id == tier_id {
+                tier_index = Some(i);
+                break;
+            }
+        }
+    } else {
+        for i in 0..event.tiers.len() {
+            let tier = event.tiers.get(i).ok_or(EventError::TierNotFound)?;
+            if tier.tier_id == tier_id {
+                tier_index = Some(i);
+                break;
+            }
+        }
+    }
+
+    let index = tier_index.ok_or(EventError::TierNotFound)?;
+    let mut tier = event.tiers.get(index).ok_or(EventError::TierNotFound)?;
+
+    if event.sold_count >= event.max_supply {
+        return Err(EventError::EventSoldOut);
+    }
+
+    if !has_res && tier.sold + tier.reserved >= tier.capacity {
+        return Err(EventError::TierSoldOut);
+    }
+
+    let payments_contract = storage::get_payments_contract(env)?;
+    let ticket_contract = storage::get_ticket_contract(env)?;
+    let payments_client = PaymentsContractClient::new(env, &payments_contract);
+    let identity = capped_identity(&payments_client, &event_id, identity)?;
+
+    let payment_id = if tier.price > 0 {
+        let token = payments_client.get_accepted_token();
+
+        Some(payments_client.pay_for_tier(
+            &nonce,
+            &attendee,
+            &event_id,
+            &tier.tier_id,
+            &1,
+            &tier.price,
+            &email_hash,
+            &token,
+            &identity,
+        ))
+    } else {
+        if let Some(identity) = &identity {
+            payments_client.record_identity_purchase(&event_id, identity);
+        }
+        None
+    };
+
+    let ticket_client = TicketContractClient::new(env, &ticket_contract);
+    let minted_ticket_id =
+        ticket_client.mint_ticket(&event.event_id, &event.organizer, &attendee, &tier_id);
+    if let Some(payment_id) = payment_id {
+        payments_client.link_minted_ticket(&payment_id, &minted_ticket_id);
+    }
+
+    storage::save_registration(env, &event_id, &attendee);
+
+    if has_res {
+        if tier.reserved > 0 {
+            tier.reserved -= 1;
+        }
+        storage::remove_reservation(env, &event_id, &attendee);
+    }
+    if tier.price == 0 {
+        storage::increment_free_claim_count(env, &event_id, &attendee);
+        storage::set_last_free_claim(env, &event_id, &attendee, env.ledger().timestamp());
+    }
+
+    tier.sold += 1;
+    event.sold_count += 1;
+    event.tiers.set(index, tier.clone());
+    update_event(env, &event_id, &event)?;
+    let privacy = storage::get_event_privacy(env, &event_id);
+    emit_registration(env, &event_id, &attendee, tier_id, tier.sold, &privacy);
+
+    Ok(())
+}
+
+/// Identity to charge against the event's per-identity cap, or `None` when the
+/// event has no cap. The payments contract keeps the cap and the count and
+/// enforces them again; checking here first surfaces an event-level error.
+fn capped_identity(
+    payments_client: &PaymentsContractClient,
+    event_id: &Symbol,
+    identity: Option<BytesN<32>>,
+) -> Result<Option<BytesN<32>>, EventError> {
+    let limit = payments_client.get_identity_limit(event_id);
+    if limit == 0 {
+        return Ok(None);
+    }
+    let identity = identity.ok_or(EventError::IdentityRequired)?;
+    if payments_client.get_identity_purchases(event_id, &identity) >= limit {
+        return Err(EventError::IdentityLimitReached);
+    }
+    Ok(Some(identity))
+}
+
+/// Mirror the event's sale, escrow and resale settings into the payments
+/// contract.
+fn sync_payments_config(env: &Env, payments_client: &PaymentsContractClient, event: &Event) {
+    payments_client.sync_event_config(
+        &env.current_contract_address(),
+        &event.event_id,
+        &event.organizer,
+        &event.payout_token,
+        &event.allow_anonymous,
+        &event.requires_verification,
+        &event.max_tickets_per_user,
+        &event.max_supply,
+        &event.event_start_ledger,
+        &event.event_end_ledger,
+        &event.withdrawal_delay_ledgers,
+        &event.resale_royalty_bps,
+        &event.max_resale_price,
+        &event.allow_free_ticket_transfer,
+        &storage::get_accepted_tokens(env, &event.event_id),
+    );
+    let reference_prices = storage::get_tier_reference_prices(env, &event.event_id);
+    let mut tier_prices = soroban_sdk::Vec::new(env);
+    for tier in event.tiers.iter() {
+        let reference_price = reference_prices.get(tier.tier_id);
+        tier_prices.push_back(TierPrice {
+            tier_id: tier.tier_id,
+            price: reference_price.unwrap_or(tier.price),
+            reference_priced: reference_price.is_some(),
+        });
+    }
+    payments_client.sync_tier_prices(&event.event_id, &tier_prices);
+}
+
+/// Push the event's transfer rules to the ticket contract. A rejected sync
+/// fails the whole call so the ticket contract never keeps enforcing a stale
+/// policy.
+fn sync_transfer_policy(env: &Env, event: &Event) -> Result<(), EventError> {
+    if let Ok(ticket_contract) = get_ticket_contract(env) {
+        let settings = transfer_settings_for(env, event);
+        let ticket_client = TicketContractClient::new(env, &ticket_contract);
+        ticket_client
+            .try_sync_transfer_policy(
+                &event.event_id,
+                &settings.policy,
+                &settings.lock_ledgers,
+                &event.event_start_ledger,
+            )
+            .map_err(|_| EventError::TicketSyncFailed)?
+            .map_err(|_| EventError::TicketSyncFailed)?;
+    }
+    Ok(())
+}
+
+/// Start the ticket contract's archival grace period for a finished event.
+fn close_ticket_event(env: &Env, event_id: &Symbol, cancelled: bool) -> Result<(), EventError> {
+    if let Ok(ticket_contract) = get_ticket_contract(env) {
+        let ticket_client = TicketContractClient::new(env, &ticket_contract);
+        ticket_client
+            .try_close_event(event_id, &cancelled)
+            .map_err(|_| EventError::TicketSyncFailed)?
+            .map_err(|_| EventError::TicketSyncFailed)?;
+    }
+    Ok(())
+}
+
+fn tier_index(event: &Event, tier_id: u32) -> Option<u32> {
+    event
+        .tiers
+        .iter()
+        .position(|tier| tier.tier_id == tier_id)
+        .map(|i| i as u32)
+}
+
+/// First valid ticket the attendee holds for the event, read page by page
+/// from the ticket contract's (owner, event) index.
+fn first_valid_ticket(
     ticket_client: &TicketContractClient,
     attendee: &Address,
     event_id: &Symbol,
-) -> bool {
-    for tid in ticket_client.get_tickets_by_owner(attendee).iter() {
-        let minted = ticket_client.get_ticket(&tid);
-        if minted.event_id == *event_id
-            && !minted.is_used
-            && minted.status == ticket_contract::TicketStatus::Valid
-        {
-            return true;
+) -> Option<u64> {
+    let mut cursor = 0u64;
+    loop {
+        let page = ticket_client.get_owner_tickets_paginated(
+            attendee,
+            &cursor,
+            &MAX_TICKET_PAGE,
+            &Some(event_id.clone()),
+            &Some(ticket_contract::TicketStatus::Valid),
+        );
+        if let Some(ticket_id) = page.ticket_ids.first() {
+            return Some(ticket_id);
         }
+        cursor = page.next_cursor?;
     }
-    false
 }
 
 #[cfg(test)]
@@ -1,9 +1,9 @@
 use crate::errors::EventError;
 use crate::types::{
-    AnonClaimSettings, AnonWindowState, ClaimSettings, Event, PostponementInfo, PrivacyLevel,
-    ZkClaimType, ZkVerificationConfig,
+    AnonClaimSettings, AnonWindowState, ClaimSettings, Event, InstallmentSettings,
+    PostponementInfo, PrivacyLevel, TransferSettings, ZkClaimType, ZkVerificationConfig,
 };
-use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, Map, Symbol, Vec};
 
 const CURRENT_VERSION: u32 = 1;
 /// TTL refresh threshold in ledgers (~30 days at 5s/ledger).
@@ -39,11 +39,22 @@ pub enum DataKey {
     EventAnonWindow(Symbol),
     EventAnonSettings(Symbol),
     AnonymousClaimVerifier,
+    /// Verifier for ZkPassport identity proofs on identity-capped events
+    ZkPassportVerifier,
     AnonymousNullifier(Symbol, BytesN<32>),
     ZkNullifier(Symbol, BytesN<32>),
     ZkVerificationConfig(Symbol),
     EventAttendeeIndex(Symbol, u64),
     EventAttendeesCount(Symbol),
+    /// Anonymous ticket (by nullifier) that has been checked in at the door.
+    AnonymousCheckIn(Symbol, BytesN<32>),
+    EventTransferSettings(Symbol),
+    EventInstallmentSettings(Symbol),
+    /// Allowlisted tokens taken besides the payout token; absent means none
+    EventAcceptedTokens(Symbol),
+    /// Tier prices in the payments oracle's reference unit, by tier id;
+    /// tiers without one are priced in the payout token
+    TierReferencePrices(Symbol),
 }
 pub fn event_exists(env: &Env, event_id: &Symbol) -> bool {
     env.storage()
@@ -402,6 +413,69 @@ pub fn set_claim_settings(env: &Env, event_id: &Symbol, settings: &ClaimSettings
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
 
+pub fn get_installment_settings(env: &Env, event_id: &Symbol) -> InstallmentSettings {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventInstallmentSettings(event_id.clone()))
+        .unwrap_or(InstallmentSettings {
+            max_installments: 0,
+            min_deposit_bps: 0,
+            penalty_bps: 0,
+        })
+}
+
+pub fn set_installment_settings(env: &Env, event_id: &Symbol, settings: &InstallmentSettings) {
+    let key = DataKey::EventInstallmentSettings(event_id.clone());
+    env.storage().persistent().set(&key, settings);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_accepted_tokens(env: &Env, event_id: &Symbol) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventAcceptedTokens(event_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn set_accepted_tokens(env: &Env, event_id: &Symbol, tokens: &Vec<Address>) {
+    let key = DataKey::EventAcceptedTokens(event_id.clone());
+    env.storage().persistent().set(&key, tokens);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_tier_reference_prices(env: &Env, event_id: &Symbol) -> Map<u32, i128> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TierReferencePrices(event_id.clone()))
+        .unwrap_or(Map::new(env))
+}
+
+pub fn set_tier_reference_prices(env: &Env, event_id: &Symbol, prices: &Map<u32, i128>) {
+    let key = DataKey::TierReferencePrices(event_id.clone());
+    env.storage().persistent().set(&key, prices);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_transfer_settings(env: &Env, event_id: &Symbol) -> Option<TransferSettings> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventTransferSettings(event_id.clone()))
+}
+
+pub fn set_transfer_settings(env: &Env, event_id: &Symbol, settings: &TransferSettings) {
+    let key = DataKey::EventTransferSettings(event_id.clone());
+    env.storage().persistent().set(&key, settings);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
 pub fn get_free_claim_count(env: &Env, event_id: &Symbol, attendee: &Address) -> u32 {
     env.storage()
         .persistent()
@@ -484,6 +558,28 @@ pub fn set_anonymous_claim_verifier(env: &Env, verifier: &Address) {
     );
 }
 
+pub fn set_zk_passport_verifier(env: &Env, verifier: &Address) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ZkPassportVerifier, verifier);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::ZkPassportVerifier, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_zk_passport_verifier(env: &Env) -> Result<Address, EventError> {
+    let key = DataKey::ZkPassportVerifier;
+    let verifier = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(EventError::ZkPassportVerifierNotConfigured)?;
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    Ok(verifier)
+}
+
 pub fn get_anonymous_claim_verifier(env: &Env) -> Result<Address, EventError> {
     let key = DataKey::AnonymousClaimVerifier;
     let verifier = env
@@ -525,6 +621,21 @@ pub fn save_anonymous_ticket_commitment(
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
 
+pub fn is_anonymous_checked_in(env: &Env, event_id: &Symbol, nullifier: &BytesN<32>) -> bool {
+    env.storage().persistent().has(&DataKey::AnonymousCheckIn(
+        event_id.clone(),
+        nullifier.clone(),
+    ))
+}
+
+pub fn set_anonymous_checked_in(env: &Env, event_id: &Symbol, nullifier: &BytesN<32>) {
+    let key = DataKey::AnonymousCheckIn(event_id.clone(), nullifier.clone());
+    env.storage().persistent().set(&key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
 pub fn get_anon_claim_settings(env: &Env, event_id: &Symbol) -> AnonClaimSettings {
     let key = DataKey::EventAnonSettings(event_id.clone());
     let settings: Option<AnonClaimSettings> = env.storage().persistent().get(&key);
@@ -1,5 +1,6 @@
 pub use privacy_utils::{mask_address, MaskedAddress, PrivacyLevel};
 use soroban_sdk::{contracttype, Address, Bytes, BytesN, String, Symbol, Vec};
+pub use ticket_contract::{RevocationReason, TransferPolicy};
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ZkClaimType {
@@ -153,6 +154,27 @@ pub struct ClaimSettings {
     pub max_free_claims: u32,
     pub cooldown_secs: u64,
 }
+/// Organizer terms for buying tickets in installments. Plans are disabled
+/// while `max_installments` is 0.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentSettings {
+    /// Most top-ups a schedule may have after its deposit
+    pub max_installments: u32,
+    /// Smallest deposit, as basis points of the tier price
+    pub min_deposit_bps: u32,
+    /// Share of the deposit kept when a plan misses a deadline
+    pub penalty_bps: u32,
+}
+/// Organizer-chosen transfer rules, synced to the ticket contract and recorded
+/// on every ticket minted afterwards.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferSettings {
+    pub policy: TransferPolicy,
+    /// Transfers are locked this many ledgers before `event_start_ledger`.
+    pub lock_ledgers: u32,
+}
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct AnonClaimSettings {
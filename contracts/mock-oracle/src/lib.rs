@@ -0,0 +1,61 @@
+#![no_std]
+//! Minimal SEP-40 price feed for tests. Prices are set directly and quoted
+//! against a USD base.
+use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Decimals,
+    Price(Asset),
+}
+
+#[contract]
+pub struct MockOracleContract;
+
+#[contractimpl]
+impl MockOracleContract {
+    pub fn initialize(env: Env, decimals: u32) {
+        env.storage().instance().set(&DataKey::Decimals, &decimals);
+    }
+
+    /// Record `price` for `asset`, observed at `timestamp`.
+    pub fn set_price(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&DataKey::Price(asset), &PriceData { price, timestamp });
+    }
+
+    pub fn base(_env: Env) -> Asset {
+        Asset::Other(symbol_short!("USD"))
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Decimals)
+            .unwrap_or(14)
+    }
+
+    pub fn resolution(_env: Env) -> u32 {
+        300
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage().instance().get(&DataKey::Price(asset))
+    }
+}
@@ -80,4 +80,79 @@ pub enum PaymentError {
     InvalidDisputeReason = 50,
     /// Organizer cannot withdraw while disputes are active.
     ActiveDisputes = 51,
+    /// The ticket is already linked to a minted ticket-contract ticket.
+    TicketAlreadyLinked = 52,
+    /// No refund is waiting to be claimed for this payment.
+    NoClaimableRefund = 53,
+    /// The event caps tickets per identity and no identity was supplied.
+    IdentityRequired = 54,
+    /// This identity already holds the maximum tickets allowed for the event.
+    IdentityLimitReached = 55,
+    /// The resale listing has passed its expiry ledger.
+    ListingExpired = 56,
+    /// The listing has not expired yet, so it cannot be settled.
+    ListingStillActive = 57,
+    /// The bid or offer does not beat the current price or leading bid.
+    BidTooLow = 58,
+    /// The operation does not apply to this kind of listing.
+    ListingKindMismatch = 59,
+    /// Listing terms are inconsistent (reserve above start, expiry in the past, ...).
+    InvalidListingTerms = 60, // CommonErrorCode::InvalidInput
+    /// An auction that already has a bid cannot be withdrawn or replaced.
+    AuctionHasBids = 61,
+    /// No offer from this buyer exists for the ticket.
+    OfferNotFound = 62, // CommonErrorCode::NotFound
+    /// The offer has passed its expiry ledger.
+    OfferExpired = 63,
+    /// The ticket already carries the maximum number of open offers.
+    TooManyOffers = 64,
+    /// A Private or Anonymous ticket is already listed; delist it first.
+    TicketAlreadyListed = 65,
+    /// The event does not take this token, or a resale price cap applies
+    /// and the token is not the event's payout token.
+    TokenNotAccepted = 66,
+    /// The token is not on the admin-managed payment token allowlist.
+    TokenNotAllowed = 67,
+    /// The allowlist or an event's accepted-token set is full.
+    TooManyTokens = 68,
+    /// Token metadata is out of range (e.g. more than 18 decimals).
+    InvalidTokenMetadata = 69, // CommonErrorCode::InvalidInput
+    /// No price oracle is configured for quoted payments.
+    OracleNotConfigured = 70, // CommonErrorCode::NotConfigured
+    /// The oracle has no usable price for the token.
+    PriceUnavailable = 71,
+    /// The oracle's latest price is older than the configured bound.
+    PriceStale = 72,
+    /// The quoted amount exceeds the payer's maximum.
+    SlippageExceeded = 73,
+    /// The event has synced tier prices and the purchase names no tier.
+    TierPriceRequired = 74,
+    /// The event has no synced price for the named tier.
+    TierNotPriced = 75, // CommonErrorCode::NotFound
+    /// The amount does not match the tier price times the quantity.
+    PriceMismatch = 76, // CommonErrorCode::InvalidAmount
+    /// The payer already has an open installment plan for the event.
+    InstallmentPlanExists = 77, // CommonErrorCode::AlreadyExists
+    /// No open installment plan exists for the payer and event.
+    InstallmentPlanNotFound = 78, // CommonErrorCode::NotFound
+    /// Installment amounts or deadlines are inconsistent.
+    InvalidInstallmentSchedule = 79, // CommonErrorCode::InvalidInput
+    /// The next installment's deadline has passed; the plan can only default.
+    InstallmentOverdue = 80,
+    /// The next installment is not yet overdue.
+    InstallmentNotOverdue = 81,
+    /// The event pays its organizer through `claim_vested`.
+    VestingActive = 82,
+    /// The event has no vesting schedule.
+    VestingNotConfigured = 83, // CommonErrorCode::NotConfigured
+    /// The event already carries a bond.
+    BondAlreadyLocked = 84, // CommonErrorCode::AlreadyExists
+    /// The bond is not held, or disputes may still draw on it.
+    BondNotReturnable = 85,
+    /// Fee caps or volume tiers are inconsistent.
+    InvalidFeeSchedule = 86, // CommonErrorCode::InvalidInput
+    /// The batch holds more entries than one call may process.
+    BatchTooLarge = 87, // CommonErrorCode::InvalidInput
+    /// The tier is priced in payout-token units, not the reference unit.
+    TierNotReferencePriced = 88,
 }
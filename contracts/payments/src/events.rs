@@ -1,4 +1,4 @@
-use crate::types::{PaymentPrivacy, PaymentRecord, Ticket};
+use crate::types::{PaymentPrivacy, PaymentRecord, ResaleListing, Ticket};
 use privacy_utils::{mask_address, MaskedAddress, PrivacyLevel};
 use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol};
 
@@ -201,6 +201,21 @@ pub fn emit_payment_refunded(env: &Env, payment: &PaymentRecord, amount: i128) {
     .publish(env);
 }
 
+/// Emit a refund set aside for a Private/Anonymous payer to claim. Uses the
+/// same masked identity as `emit_payment_refunded`.
+pub fn emit_refund_claimable(env: &Env, payment: &PaymentRecord, amount: i128) {
+    PaymentRefunded {
+        event_type: event_type(env, "refund_claimable"),
+        payment_id: payment.payment_id,
+        event_id: payment.event_id.clone(),
+        payer: masked_payment_identity(env, payment),
+        amount,
+        token: payment.token.clone(),
+        refunded_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 /// Emit a ticket-issued event using the ticket's own privacy level.
 pub fn emit_ticket_issued(env: &Env, ticket: &Ticket) {
     TicketIssued {
@@ -225,6 +240,53 @@ pub fn emit_escrow_auto_released(env: &Env, event_id: Symbol, organizer: Address
     .publish(env);
 }
 
+#[contractevent(data_format = "vec", topics = ["cancel_refunds"])]
+pub struct CancellationRefundsProcessed {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub from_cursor: u64,
+    pub to_cursor: u64,
+    pub payment_count: u64,
+    pub refunded_count: u32,
+}
+
+pub fn emit_cancellation_refunds_processed(
+    env: &Env,
+    event_id: Symbol,
+    from_cursor: u64,
+    to_cursor: u64,
+    payment_count: u64,
+    refunded_count: u32,
+) {
+    CancellationRefundsProcessed {
+        event_type: event_type(env, "cancellation_refunds_processed"),
+        event_id,
+        from_cursor,
+        to_cursor,
+        payment_count,
+        refunded_count,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["cancel_refund_skip"])]
+pub struct CancellationRefundSkipped {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub payment_id: u64,
+}
+
+/// A cancellation sweep could not pay `payment_id`; it stays Held for
+/// `claim_refund`.
+pub fn emit_cancellation_refund_skipped(env: &Env, event_id: &Symbol, payment_id: u64) {
+    CancellationRefundSkipped {
+        event_type: event_type(env, "cancellation_refund_skipped"),
+        event_id: event_id.clone(),
+        payment_id,
+    }
+    .publish(env);
+}
+
 #[contractevent(data_format = "vec", topics = ["platform_fee"])]
 pub struct PlatformFeeCollected {
     pub event_type: Symbol,
@@ -273,6 +335,60 @@ pub fn emit_platform_fee_updated(env: &Env, admin: Address, old_bps: u32, new_bp
     .publish(env);
 }
 
+#[contractevent(data_format = "vec", topics = ["token_allowlist"])]
+pub struct TokenAllowlistUpdated {
+    pub event_type: Symbol,
+    pub token: Address,
+    pub decimals: u32,
+    pub allowed: bool,
+}
+
+pub fn emit_token_allowlist_updated(env: &Env, token: Address, decimals: u32, allowed: bool) {
+    TokenAllowlistUpdated {
+        event_type: event_type(env, "token_allowlist"),
+        token,
+        decimals,
+        allowed,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["price_quoted"])]
+pub struct PriceQuoted {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub payment_id: u64,
+    pub token: Address,
+    pub reference_amount: i128,
+    pub amount: i128,
+    pub price: i128,
+    pub price_timestamp: u64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_price_quoted(
+    env: &Env,
+    event_id: Symbol,
+    payment_id: u64,
+    token: Address,
+    reference_amount: i128,
+    amount: i128,
+    price: i128,
+    price_timestamp: u64,
+) {
+    PriceQuoted {
+        event_type: event_type(env, "price_quoted"),
+        event_id,
+        payment_id,
+        token,
+        reference_amount,
+        amount,
+        price,
+        price_timestamp,
+    }
+    .publish(env);
+}
+
 #[contractevent(data_format = "vec", topics = ["platform_withdrawal"])]
 pub struct PlatformRevenueWithdrawn {
     pub event_type: Symbol,
@@ -301,6 +417,26 @@ pub fn emit_platform_revenue_withdrawn(
     .publish(env);
 }
 
+#[contractevent(data_format = "vec", topics = ["platform_sweep"])]
+pub struct PlatformRevenueSwept {
+    pub event_type: Symbol,
+    pub token: Address,
+    pub amount: i128,
+    pub to: Address,
+    pub swept_at: u64,
+}
+
+pub fn emit_platform_revenue_swept(env: &Env, token: Address, amount: i128, to: Address) {
+    PlatformRevenueSwept {
+        event_type: event_type(env, "platform_revenue_swept"),
+        token,
+        amount,
+        to,
+        swept_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 #[contractevent(data_format = "vec", topics = ["cohost_flagged"])]
 pub struct CohostFlagged {
     pub event_type: Symbol,
@@ -415,3 +551,214 @@ pub fn emit_dispute_timed_out(env: &Env, event_id: Symbol, ticket_id: u64) {
     }
     .publish(env);
 }
+
+#[contractevent(data_format = "vec", topics = ["bond_locked"])]
+pub struct BondLocked {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub organizer: Address,
+    pub amount: i128,
+}
+
+pub fn emit_bond_locked(env: &Env, event_id: Symbol, organizer: Address, amount: i128) {
+    BondLocked {
+        event_type: event_type(env, "bond_locked"),
+        event_id,
+        organizer,
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["bond_slashed"])]
+pub struct BondSlashed {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub approved_disputes: u32,
+    pub sold_count: u32,
+}
+
+pub fn emit_bond_slashed(env: &Env, event_id: Symbol, approved_disputes: u32, sold_count: u32) {
+    BondSlashed {
+        event_type: event_type(env, "bond_slashed"),
+        event_id,
+        approved_disputes,
+        sold_count,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["bond_returned"])]
+pub struct BondReturned {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub organizer: Address,
+    pub amount: i128,
+}
+
+pub fn emit_bond_returned(env: &Env, event_id: Symbol, organizer: Address, amount: i128) {
+    BondReturned {
+        event_type: event_type(env, "bond_returned"),
+        event_id,
+        organizer,
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["resale_listed"])]
+pub struct ResaleListed {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub seller: Address,
+    pub kind: crate::types::ListingKind,
+    pub price: i128,
+    pub reserve_price: i128,
+    pub token: Address,
+    pub expires_at_ledger: Option<u32>,
+}
+
+pub fn emit_resale_listed(env: &Env, listing: &ResaleListing) {
+    ResaleListed {
+        event_type: event_type(env, "resale_listed"),
+        event_id: listing.event_id.clone(),
+        ticket_id: listing.ticket_id,
+        seller: listing.seller.clone(),
+        kind: listing.kind.clone(),
+        price: listing.price,
+        reserve_price: listing.reserve_price,
+        token: listing.token.clone(),
+        expires_at_ledger: listing.expires_at_ledger,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["resale_bid"])]
+pub struct ResaleBidPlaced {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub bidder: Address,
+    pub amount: i128,
+}
+
+pub fn emit_resale_bid_placed(
+    env: &Env,
+    event_id: Symbol,
+    ticket_id: u64,
+    bidder: Address,
+    amount: i128,
+) {
+    ResaleBidPlaced {
+        event_type: event_type(env, "resale_bid"),
+        event_id,
+        ticket_id,
+        bidder,
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["resale_offer"])]
+pub struct ResaleOfferMade {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub buyer: Address,
+    pub amount: i128,
+}
+
+pub fn emit_resale_offer_made(
+    env: &Env,
+    event_id: Symbol,
+    ticket_id: u64,
+    buyer: Address,
+    amount: i128,
+) {
+    ResaleOfferMade {
+        event_type: event_type(env, "resale_offer"),
+        event_id,
+        ticket_id,
+        buyer,
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["resale_offer_evicted"])]
+pub struct ResaleOfferEvicted {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub buyer: Address,
+    pub amount: i128,
+}
+
+pub fn emit_resale_offer_evicted(
+    env: &Env,
+    event_id: Symbol,
+    ticket_id: u64,
+    buyer: Address,
+    amount: i128,
+) {
+    ResaleOfferEvicted {
+        event_type: event_type(env, "resale_offer_evicted"),
+        event_id,
+        ticket_id,
+        buyer,
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["resale_sold"])]
+pub struct ResaleSold {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub seller: Address,
+    pub buyer: Address,
+    pub price: i128,
+}
+
+pub fn emit_resale_sold(
+    env: &Env,
+    event_id: Symbol,
+    ticket_id: u64,
+    seller: &Address,
+    buyer: &Address,
+    price: i128,
+) {
+    ResaleSold {
+        event_type: event_type(env, "resale_sold"),
+        event_id,
+        ticket_id,
+        seller: seller.clone(),
+        buyer: buyer.clone(),
+        price,
+    }
+    .publish(env);
+}
+
+#[contractevent(data_format = "vec", topics = ["private_resale_sold"])]
+pub struct PrivateResaleSold {
+    pub event_type: Symbol,
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub new_owner: MaskedAddress,
+    pub price: i128,
+}
+
+/// Resale of a Private or Anonymous ticket; the buyer is only exposed in its
+/// masked form.
+pub fn emit_private_resale_sold(env: &Env, ticket: &Ticket, price: i128) {
+    PrivateResaleSold {
+        event_type: event_type(env, "private_resale_sold"),
+        event_id: ticket.event_id.clone(),
+        ticket_id: ticket.ticket_id,
+        new_owner: masked_ticket_identity(env, ticket),
+        price,
+    }
+    .publish(env);
+}
@@ -2,11 +2,12 @@
 #[cfg(test)]
 extern crate std;
 use soroban_sdk::{
-    contract, contractimpl, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol,
+    contract, contractimpl, token, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
 mod errors;
 mod events;
+mod oracle;
 mod storage;
 mod types;
 
@@ -20,10 +21,24 @@ pub use types::*;
 
 // Import common utilities
 use common_utils::validation;
+use oracle::{Asset, PriceData, PriceOracleClient};
 
 const MIN_DISPUTE_WINDOW_LEDGERS: u32 = 100;
 const ATTENDEE_DISPUTE_WINDOW_LEDGERS: u32 = 17_280 * 7;
 const DISPUTE_TIMEOUT_LEDGERS: u32 = 17_280 * 14;
+const REFUND_CLAIM_DOMAIN: &[u8] = b"zicket:refund-claim:v1";
+const IDENTITY_SCOPE_DOMAIN: &[u8] = b"zicket:identity-scope:v1";
+const PRIVATE_RESALE_DOMAIN: &[u8] = b"zicket:private-resale:v1";
+const MAX_OFFERS_PER_TICKET: u32 = 20;
+const MAX_RECONCILE_BATCH: u32 = 100;
+const MAX_MIGRATION_BATCH: u32 = 100;
+const MAX_LISTING_PAGE_SIZE: u32 = 50;
+/// Each pushed refund touches the payment and the payer's token balance, so
+/// pages stay small enough for one transaction's footprint.
+const MAX_REFUND_PAGE_SIZE: u32 = 10;
+const MAX_ALLOWED_TOKENS: u32 = 20;
+const MAX_EVENT_TOKENS: u32 = 5;
+const MAX_TOKEN_DECIMALS: u32 = 18;
 
 #[derive(Clone)]
 struct PaymentParams {
@@ -39,6 +54,19 @@ struct PaymentParams {
     zk_email_commitment: Option<BytesN<32>>,
     nullifier_commitment: Option<BytesN<32>>,
     stealth_delivery_key: Option<BytesN<32>>,
+    /// False for top-up charges (tier upgrades) that pay toward an existing
+    /// ticket rather than buying a new one.
+    issues_ticket: bool,
+    /// Event-scoped identity (a ZkPassport nullifier the event contract has
+    /// verified) charged against the event's per-identity cap
+    identity: Option<BytesN<32>>,
+    /// Tier and ticket count the amount pays for; checked against the
+    /// event's synced tier prices
+    tier_id: Option<u32>,
+    quantity: u32,
+    /// Paid toward the same ticket by earlier installments; counts toward
+    /// the tier price
+    prepaid: i128,
 }
 
 /// Build a privacy-level-aware payment record. Exactly one identity representation
@@ -88,13 +116,7 @@ fn build_payment_record(
                 .stealth_delivery_key
                 .clone()
                 .ok_or(PaymentError::MissingStealthDeliveryKey)?;
-            // Salt the wallet hash with the stealth key to prevent brute-force
-            // enumeration of the payer address from the stored hash.
-            let mut preimage = params.payer.clone().to_xdr(env);
-            let stealth_array = stealth_key.to_array();
-            let stealth_bytes = Bytes::from_slice(env, stealth_array.as_ref());
-            preimage.append(&stealth_bytes);
-            let hashed: BytesN<32> = env.crypto().sha256(&preimage).into();
+            let hashed = salted_wallet_hash(env, &params.payer, &stealth_key);
             Ok(PaymentRecord {
                 payment_id,
                 event_id: params.event_id.clone(),
@@ -148,6 +170,762 @@ fn build_ticket(payment: &PaymentRecord, ticket_id: u64) -> Ticket {
     }
 }
 
+/// Read the owner of a minted ticket from the ticket contract. Returns `None`
+/// when no ticket contract is configured or it does not know the ticket.
+fn minted_ticket_owner(env: &Env, minted_ticket_id: u64) -> Option<Address> {
+    let ticket_contract = storage::get_ticket_contract(env).ok()?;
+    match env.try_invoke_contract::<Address, soroban_sdk::Error>(
+        &ticket_contract,
+        &Symbol::new(env, "owner_of"),
+        soroban_sdk::vec![env, minted_ticket_id.into_val(env)],
+    ) {
+        Ok(Ok(owner)) => Some(owner),
+        _ => None,
+    }
+}
+
+/// Resolve the current owner of a Standard ticket. Once a ledger ticket is
+/// linked to its minted counterpart the ticket contract is the source of truth,
+/// and the local copy is repaired whenever it has drifted (for example after a
+/// direct `TicketContract::transfer_ticket`).
+fn resolve_ticket_owner(env: &Env, ticket: &mut Ticket) -> Option<Address> {
+    if ticket.privacy_level != PaymentPrivacy::Standard {
+        return ticket.owner.clone();
+    }
+    if let Some(owner) = storage::get_minted_ticket_id(env, ticket.payment_id)
+        .and_then(|minted_ticket_id| minted_ticket_owner(env, minted_ticket_id))
+    {
+        if ticket.owner.as_ref() != Some(&owner) {
+            set_ticket_owner(env, ticket, &owner);
+        }
+    }
+    ticket.owner.clone()
+}
+
+/// Move a Standard ledger ticket to `owner`, keeping the owner index in step.
+fn set_ticket_owner(env: &Env, ticket: &mut Ticket, owner: &Address) {
+    if let Some(previous) = &ticket.owner {
+        storage::remove_owner_ticket_map(env, previous, ticket.ticket_id);
+    }
+    storage::add_owner_ticket_map(env, owner, ticket.ticket_id);
+    ticket.owner = Some(owner.clone());
+    storage::update_ticket(env, ticket);
+}
+
+/// Ledger key for an identity's purchase count on one event. Hashing the
+/// event id in means the same identity never yields the same key twice, so
+/// counters cannot be joined across events even if a client reuses an
+/// identity; clients should still present event-scoped identities (a
+/// ZkPassport nullifier for the event's scope, or a zk-email commitment bound
+/// to the event) so the raw arguments do not link either.
+fn identity_scope_hash(env: &Env, event_id: &Symbol, identity: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, IDENTITY_SCOPE_DOMAIN);
+    preimage.append(&event_id.clone().to_xdr(env));
+    preimage.append(&Bytes::from_slice(env, identity.to_array().as_ref()));
+    env.crypto().sha256(&preimage).into()
+}
+
+/// Validate and store a new resale listing for a ticket the seller owns.
+fn create_listing(
+    env: &Env,
+    seller: Address,
+    ticket_id: u64,
+    kind: ListingKind,
+    price: i128,
+    reserve_price: i128,
+    expires_at_ledger: Option<u32>,
+    token: Option<Address>,
+) -> Result<(), PaymentError> {
+    require_not_paused(env)?;
+    seller.require_auth();
+
+    let mut ticket = storage::get_ticket(env, ticket_id)?;
+    // Only Standard tickets are address-owned; Private and Anonymous tickets
+    // list through `list_private_ticket_for_resale`.
+    if resolve_ticket_owner(env, &mut ticket).as_ref() != Some(&seller) {
+        return Err(PaymentError::Unauthorized);
+    }
+
+    open_listing(
+        env,
+        &ticket,
+        seller,
+        kind,
+        price,
+        reserve_price,
+        expires_at_ledger,
+        token,
+    )
+}
+
+/// Check listing terms against the event's resale rules and store the
+/// listing, replacing any earlier one without bids. Ownership has already
+/// been proven by the caller. `token` defaults to the event's payout token.
+#[allow(clippy::too_many_arguments)]
+fn open_listing(
+    env: &Env,
+    ticket: &Ticket,
+    seller: Address,
+    kind: ListingKind,
+    price: i128,
+    reserve_price: i128,
+    expires_at_ledger: Option<u32>,
+    token: Option<Address>,
+) -> Result<(), PaymentError> {
+    let ticket_id = ticket.ticket_id;
+    let config =
+        storage::get_event_config(env, &ticket.event_id).ok_or(PaymentError::InvalidOrganizer)?;
+    let token = resale_token(env, &ticket.event_id, &config, token)?;
+    let payment = storage::get_payment(env, ticket.payment_id)?;
+    check_resale_price(&config, &payment, &token, price)?;
+
+    let listed_at_ledger = env.ledger().sequence();
+    if expires_at_ledger.is_some_and(|expiry| expiry <= listed_at_ledger) {
+        return Err(PaymentError::InvalidListingTerms);
+    }
+    // An English reserve may sit above the opening bid, but a Dutch price
+    // only ever falls towards its reserve.
+    if reserve_price < 0 || (kind == ListingKind::DutchAuction && reserve_price > price) {
+        return Err(PaymentError::InvalidListingTerms);
+    }
+    check_resale_cap(&config, &token, reserve_price)?;
+
+    if let Some(existing) = storage::get_resale_listing(env, ticket_id) {
+        if existing.highest_bidder.is_some() {
+            return Err(PaymentError::AuctionHasBids);
+        }
+        storage::remove_resale_listing(env, ticket_id);
+    }
+
+    let listing = ResaleListing {
+        ticket_id,
+        event_id: ticket.event_id.clone(),
+        seller,
+        kind,
+        price,
+        reserve_price,
+        token,
+        listed_at_ledger,
+        expires_at_ledger,
+        highest_bidder: None,
+        highest_bid: 0,
+    };
+    storage::add_resale_listing(env, &listing);
+    events::emit_resale_listed(env, &listing);
+    Ok(())
+}
+
+/// Check that a payment token is both accepted by the event and still on
+/// the allowlist. The payout token is always the contract's accepted token,
+/// so it needs no allowlist lookup.
+fn check_event_token(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    token: &Address,
+) -> Result<(), PaymentError> {
+    if *token == config.payout_token {
+        return Ok(());
+    }
+    if !storage::get_event_accepted_tokens(env, event_id).contains(token) {
+        return Err(PaymentError::TokenNotAccepted);
+    }
+    if !storage::is_token_allowed(env, token) {
+        return Err(PaymentError::TokenNotAllowed);
+    }
+    Ok(())
+}
+
+/// Convert `reference_amount`, in the oracle's base asset and scale, into
+/// `token` units at the oracle's latest price. Rounds up so a quote never
+/// undercharges.
+fn quote_token_amount(
+    env: &Env,
+    token: &Address,
+    reference_amount: i128,
+) -> Result<(i128, PriceData), PaymentError> {
+    if reference_amount <= 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+    let (price, decimals) = oracle_price(env, token)?;
+    let scaled = reference_amount
+        .checked_mul(10i128.pow(decimals))
+        .ok_or(PaymentError::InvalidAmount)?;
+    let mut amount = scaled / price.price;
+    if scaled % price.price != 0 {
+        amount += 1;
+    }
+    Ok((amount, price))
+}
+
+/// Latest fresh oracle price for `token`, with the token's decimals.
+fn oracle_price(env: &Env, token: &Address) -> Result<(PriceData, u32), PaymentError> {
+    let config = storage::get_oracle_config(env).ok_or(PaymentError::OracleNotConfigured)?;
+    // Quotes need the token's decimals, so the token must carry allowlist
+    // metadata even when it is the accepted token.
+    let info = storage::get_token_info(env, token).ok_or(PaymentError::TokenNotAllowed)?;
+
+    let price = PriceOracleClient::new(env, &config.oracle)
+        .try_lastprice(&Asset::Stellar(token.clone()))
+        .ok()
+        .and_then(|result| result.ok())
+        .flatten()
+        .filter(|price| price.price > 0)
+        .ok_or(PaymentError::PriceUnavailable)?;
+    if env.ledger().timestamp().saturating_sub(price.timestamp) > config.max_price_age {
+        return Err(PaymentError::PriceStale);
+    }
+    Ok((price, info.decimals))
+}
+
+/// Check a ticket purchase against the event's synced tier prices and
+/// return the tier it pays for. A purchase that names no tier buys the
+/// event's only tier. Events whose prices were never synced accept any
+/// positive amount, as before tier pricing, until the event contract's
+/// `resync_payments_config` mirrors their prices. Tier prices are full
+/// prices: events have no discounts to apply.
+///
+/// A purchase in the payout token of a tier priced in payout-token units pays
+/// the price exactly. Any other purchase must cover the price quoted at the
+/// oracle's latest rates; quotes round up, so the payer may send slightly
+/// more.
+#[allow(clippy::too_many_arguments)]
+fn check_tier_amount(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    token: &Address,
+    tier_id: Option<u32>,
+    quantity: u32,
+    amount: i128,
+) -> Result<Option<u32>, PaymentError> {
+    if quantity == 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+    let Some(tier_prices) = storage::get_synced_tier_prices(env, event_id) else {
+        return Ok(tier_id);
+    };
+    let tier = match tier_id {
+        Some(tier_id) => tier_prices.iter().find(|tier| tier.tier_id == tier_id),
+        None if tier_prices.len() > 1 => return Err(PaymentError::TierPriceRequired),
+        None => tier_prices.first(),
+    }
+    .ok_or(PaymentError::TierNotPriced)?;
+    let price = tier.price;
+    let total = price
+        .checked_mul(quantity as i128)
+        .ok_or(PaymentError::InvalidAmount)?;
+
+    if tier.reference_priced {
+        let (expected, _) = quote_token_amount(env, token, total)?;
+        if amount < expected {
+            return Err(PaymentError::PriceMismatch);
+        }
+        return Ok(Some(tier.tier_id));
+    }
+    if *token == config.payout_token {
+        if amount != total {
+            return Err(PaymentError::PriceMismatch);
+        }
+        return Ok(Some(tier.tier_id));
+    }
+
+    let (payout_price, payout_decimals) = oracle_price(env, &config.payout_token)?;
+    let scaled = total
+        .checked_mul(payout_price.price)
+        .ok_or(PaymentError::InvalidAmount)?;
+    let unit = 10i128.pow(payout_decimals);
+    let mut reference = scaled / unit;
+    if scaled % unit != 0 {
+        reference += 1;
+    }
+    let (expected, _) = quote_token_amount(env, token, reference)?;
+    if amount < expected {
+        return Err(PaymentError::PriceMismatch);
+    }
+    Ok(Some(tier.tier_id))
+}
+
+/// Synced tier of `event_id` that is priced in the oracle's reference unit.
+fn reference_tier(env: &Env, event_id: &Symbol, tier_id: u32) -> Result<TierPrice, PaymentError> {
+    let tier = storage::get_event_tier_prices(env, event_id)
+        .iter()
+        .find(|tier| tier.tier_id == tier_id)
+        .ok_or(PaymentError::TierNotPriced)?;
+    if !tier.reference_priced {
+        return Err(PaymentError::TierNotReferencePriced);
+    }
+    Ok(tier)
+}
+
+/// Token a resale is priced in. Sellers and offering buyers may pick any
+/// token the event accepts; `None` means the event's payout token.
+fn resale_token(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    token: Option<Address>,
+) -> Result<Address, PaymentError> {
+    let token = token.unwrap_or_else(|| config.payout_token.clone());
+    check_event_token(env, event_id, config, &token)?;
+    Ok(token)
+}
+
+/// Resale pricing rules shared by listings, bids and offers: free tickets
+/// resell only for free and only when the organizer allows it, and no price
+/// may exceed the event's resale cap.
+fn check_resale_price(
+    config: &EventConfig,
+    payment: &PaymentRecord,
+    token: &Address,
+    price: i128,
+) -> Result<(), PaymentError> {
+    if !config.allow_free_ticket_transfer && payment.amount == 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+    if payment.amount == 0 && price > 0 {
+        return Err(PaymentError::InvalidAmount);
+    }
+    check_resale_cap(config, token, price)
+}
+
+/// Re-checked at sale time since the organizer may lower the cap while a
+/// listing or offer is open. The cap is denominated in the payout token, so
+/// capped events only resell in that token.
+fn check_resale_cap(
+    config: &EventConfig,
+    token: &Address,
+    price: i128,
+) -> Result<(), PaymentError> {
+    match config.max_resale_price {
+        Some(_) if *token != config.payout_token => Err(PaymentError::TokenNotAccepted),
+        Some(max_price) if price > max_price => Err(PaymentError::InvalidAmount),
+        _ => Ok(()),
+    }
+}
+
+fn listing_expired(env: &Env, listing: &ResaleListing) -> bool {
+    listing
+        .expires_at_ledger
+        .is_some_and(|expiry| env.ledger().sequence() > expiry)
+}
+
+/// Asking price at the current ledger. Dutch auctions fall linearly from
+/// `price` at listing to `reserve_price` at expiry.
+fn listing_price(env: &Env, listing: &ResaleListing) -> i128 {
+    match (&listing.kind, listing.expires_at_ledger) {
+        (ListingKind::DutchAuction, Some(expiry)) => {
+            let duration = expiry.saturating_sub(listing.listed_at_ledger).max(1) as i128;
+            let elapsed = env
+                .ledger()
+                .sequence()
+                .saturating_sub(listing.listed_at_ledger)
+                .min(expiry - listing.listed_at_ledger) as i128;
+            listing.price - (listing.price - listing.reserve_price) * elapsed / duration
+        }
+        _ => listing.price,
+    }
+}
+
+fn listing_page_limit(limit: u32) -> u64 {
+    u64::from(limit.min(MAX_LISTING_PAGE_SIZE))
+}
+
+/// Complete a resale whose `price` the contract already holds in `token`:
+/// pay the platform fee, royalty and seller, move the ticket in both
+/// contracts and close the listing.
+fn settle_resale(
+    env: &Env,
+    ticket: &mut Ticket,
+    config: &EventConfig,
+    seller: &Address,
+    buyer: &Address,
+    token: &Address,
+    price: i128,
+) -> Result<(), PaymentError> {
+    pay_resale_proceeds(env, &ticket.event_id, config, seller, token, price);
+
+    // Ledger and ticket-contract ids only coincide for unlinked legacy
+    // tickets, so prefer the recorded link.
+    let minted_ticket_id =
+        storage::get_minted_ticket_id(env, ticket.payment_id).unwrap_or(ticket.ticket_id);
+    let ticket_contract = storage::get_ticket_contract(env)?;
+    let _: () = env.invoke_contract(
+        &ticket_contract,
+        &soroban_sdk::Symbol::new(env, "admin_transfer_ticket"),
+        soroban_sdk::vec![
+            env,
+            env.current_contract_address().into_val(env),
+            seller.into_val(env),
+            buyer.clone().into_val(env),
+            minted_ticket_id.into_val(env)
+        ],
+    );
+
+    set_ticket_owner(env, ticket, buyer);
+    storage::remove_resale_listing(env, ticket.ticket_id);
+    events::emit_resale_sold(
+        env,
+        ticket.event_id.clone(),
+        ticket.ticket_id,
+        seller,
+        buyer,
+        price,
+    );
+
+    Ok(())
+}
+
+/// Split a resale `price` held by the contract in `token` into the platform
+/// fee, the organizer royalty and the seller's proceeds. Fees are booked
+/// under `token` and counted in the event's resale fee total so the revenue
+/// invariant still balances.
+fn pay_resale_proceeds(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    seller: &Address,
+    token: &Address,
+    price: i128,
+) {
+    if price > 0 {
+        let royalty = price * (config.resale_royalty_bps as i128) / 10000;
+        let platform_fee =
+            resale_platform_fee(env, event_id, config, token, price).min(price - royalty);
+        let seller_proceeds = price - platform_fee - royalty;
+
+        if seller_proceeds > 0 {
+            let token_client = token::Client::new(env, token);
+            token_client.transfer(&env.current_contract_address(), seller, &seller_proceeds);
+        }
+
+        if platform_fee > 0 {
+            book_platform_fee(env, event_id, token, platform_fee);
+        }
+
+        if platform_fee + royalty > 0 {
+            storage::add_event_token(env, event_id, token);
+            storage::add_total_resale_fees(env, event_id, platform_fee + royalty);
+        }
+
+        if royalty > 0 {
+            storage::add_event_token_revenue(env, event_id, token, royalty);
+        }
+    }
+}
+
+/// Free a slot on a ticket's full offer list so stale or lowball offers
+/// cannot lock out real buyers. Expired offers are refunded first; failing
+/// that, the lowest live offer in `token` is evicted if `amount` beats it.
+fn make_room_for_offer(
+    env: &Env,
+    event_id: &Symbol,
+    ticket_id: u64,
+    token: &Address,
+    amount: i128,
+) -> Result<(), PaymentError> {
+    let now = env.ledger().sequence();
+    let mut evicted = Vec::new(env);
+    let mut lowest: Option<ResaleOffer> = None;
+    for buyer in storage::get_ticket_offer_buyers(env, ticket_id).iter() {
+        let Some(offer) = storage::get_resale_offer(env, ticket_id, &buyer) else {
+            continue;
+        };
+        if offer.expires_at_ledger < now {
+            evicted.push_back(offer);
+        } else if offer.token == *token
+            && offer.amount < amount
+            && lowest.as_ref().is_none_or(|l| offer.amount < l.amount)
+        {
+            lowest = Some(offer);
+        }
+    }
+    if evicted.is_empty() {
+        evicted.push_back(lowest.ok_or(PaymentError::TooManyOffers)?);
+    }
+
+    for offer in evicted.iter() {
+        storage::remove_resale_offer(env, ticket_id, &offer.buyer);
+        token::Client::new(env, &offer.token).transfer(
+            &env.current_contract_address(),
+            &offer.buyer,
+            &offer.amount,
+        );
+        events::emit_resale_offer_evicted(
+            env,
+            event_id.clone(),
+            ticket_id,
+            offer.buyer,
+            offer.amount,
+        );
+    }
+    Ok(())
+}
+
+/// Book platform revenue held in `token`. The accepted token keeps its
+/// original bucket; other tokens get one each. The token's platform-wide
+/// total and sweep index are kept alongside.
+fn book_platform_fee(env: &Env, event_id: &Symbol, token: &Address, amount: i128) {
+    count_unindexed_platform_fees(env, event_id, token);
+    if storage::get_accepted_token(env).ok().as_ref() == Some(token) {
+        storage::add_platform_revenue(env, event_id, amount);
+    } else {
+        storage::add_platform_token_revenue(env, event_id, token, amount);
+    }
+    storage::index_platform_fee_event(env, event_id, token);
+    let mut total = storage::get_platform_total(env, token);
+    total.owed += amount;
+    storage::set_platform_total(env, &total);
+}
+
+/// Platform fees `event_id` holds in `token`.
+fn platform_fees_held(env: &Env, event_id: &Symbol, token: &Address) -> i128 {
+    if storage::get_accepted_token(env).ok().as_ref() == Some(token) {
+        storage::get_platform_revenue(env, event_id)
+    } else {
+        storage::get_platform_token_revenue(env, event_id, token)
+    }
+}
+
+/// Fees booked before the sweep index existed are in neither the index nor
+/// the token's owed total. Index the event and count what it holds, once.
+/// Returns whether anything was counted.
+fn count_unindexed_platform_fees(env: &Env, event_id: &Symbol, token: &Address) -> bool {
+    let held = platform_fees_held(env, event_id, token);
+    if held <= 0 || !storage::index_platform_fee_event(env, event_id, token) {
+        return false;
+    }
+    let mut total = storage::get_platform_total(env, token);
+    total.owed += held;
+    storage::set_platform_total(env, &total);
+    true
+}
+
+/// Zero `event_id`'s platform fees in `token` and return them. The payout
+/// counts as withdrawn so the event's revenue invariant still balances; the
+/// caller transfers the amount and records it with `record_platform_payout`.
+fn take_platform_fees(env: &Env, event_id: &Symbol, token: &Address) -> i128 {
+    let is_accepted = storage::get_accepted_token(env).ok().as_ref() == Some(token);
+    let amount = platform_fees_held(env, event_id, token);
+    if amount <= 0 {
+        return 0;
+    }
+    if is_accepted {
+        storage::reset_platform_revenue(env, event_id);
+    } else {
+        storage::reset_platform_token_revenue(env, event_id, token);
+    }
+    storage::add_total_withdrawn(env, event_id, amount);
+    amount
+}
+
+fn record_platform_payout(env: &Env, token: &Address, amount: i128) {
+    let mut total = storage::get_platform_total(env, token);
+    total.owed -= amount;
+    total.withdrawn += amount;
+    storage::set_platform_total(env, &total);
+}
+
+/// Fee terms for events `organizer` creates from now on: their override if
+/// the admin set one, else the platform schedule, else the flat legacy rate.
+fn current_fee_schedule(env: &Env, organizer: &Address) -> FeeSchedule {
+    storage::get_organizer_fee_schedule(env, organizer)
+        .or_else(|| storage::get_fee_schedule(env))
+        .unwrap_or_else(|| {
+            let bps = storage::get_platform_fee_bps(env);
+            FeeSchedule {
+                primary_bps: bps,
+                resale_bps: bps,
+                min_fee: 0,
+                max_fee: 0,
+                tiers: Vec::new(env),
+            }
+        })
+}
+
+/// The schedule an event snapshotted on its first sync. Events synced before
+/// schedules existed follow the current terms.
+fn event_fee_schedule(env: &Env, event_id: &Symbol, config: &EventConfig) -> FeeSchedule {
+    storage::get_event_fee_schedule(env, event_id)
+        .unwrap_or_else(|| current_fee_schedule(env, &config.organizer))
+}
+
+fn validate_fee_schedule(schedule: &FeeSchedule) -> Result<(), PaymentError> {
+    if schedule.primary_bps > 10_000 || schedule.resale_bps > 10_000 {
+        return Err(PaymentError::InvalidFeeBps);
+    }
+    if schedule.min_fee < 0
+        || schedule.max_fee < 0
+        || (schedule.max_fee > 0 && schedule.max_fee < schedule.min_fee)
+    {
+        return Err(PaymentError::InvalidFeeSchedule);
+    }
+    let mut last_volume = 0i128;
+    for (i, tier) in schedule.tiers.iter().enumerate() {
+        if tier.bps > 10_000 {
+            return Err(PaymentError::InvalidFeeBps);
+        }
+        if tier.min_volume <= 0 || (i > 0 && tier.min_volume <= last_volume) {
+            return Err(PaymentError::InvalidFeeSchedule);
+        }
+        last_volume = tier.min_volume;
+    }
+    Ok(())
+}
+
+/// Platform fee on `amount` of primary-sale revenue held in `token`. The
+/// rate steps down through the volume tiers as payout-token sales grow. The
+/// per-ticket caps are denominated in the payout token and scale with the
+/// share of its gross sales that `amount` represents.
+fn primary_platform_fee(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    token: &Address,
+    amount: i128,
+) -> i128 {
+    let schedule = event_fee_schedule(env, event_id, config);
+    let volume = storage::get_total_token_volume(env, event_id, &config.payout_token);
+    let mut bps = schedule.primary_bps;
+    for tier in schedule.tiers.iter() {
+        if volume >= tier.min_volume {
+            bps = tier.bps;
+        }
+    }
+
+    let mut fee = amount * bps as i128 / 10_000;
+    if *token == config.payout_token && config.sold_count > 0 && volume > 0 {
+        let tickets = config.sold_count as i128;
+        if schedule.min_fee > 0 {
+            fee = fee.max(schedule.min_fee * tickets * amount / volume);
+        }
+        if schedule.max_fee > 0 {
+            fee = fee.min(schedule.max_fee * tickets * amount / volume);
+        }
+    }
+    fee.clamp(0, amount)
+}
+
+/// Platform fee on a single resale at `price`, capped per ticket when the
+/// resale settles in the payout token.
+fn resale_platform_fee(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    token: &Address,
+    price: i128,
+) -> i128 {
+    let schedule = event_fee_schedule(env, event_id, config);
+    let mut fee = price * schedule.resale_bps as i128 / 10_000;
+    if *token == config.payout_token {
+        if schedule.min_fee > 0 {
+            fee = fee.max(schedule.min_fee);
+        }
+        if schedule.max_fee > 0 {
+            fee = fee.min(schedule.max_fee);
+        }
+    }
+    fee.clamp(0, price)
+}
+
+/// Stealth key that currently controls a Private ticket: the key it was last
+/// resold to, or the purchaser's key from its payment.
+fn ticket_stealth_key(env: &Env, ticket: &Ticket) -> Option<BytesN<32>> {
+    storage::get_ticket_stealth_key(env, ticket.ticket_id).or_else(|| {
+        storage::get_payment(env, ticket.payment_id)
+            .ok()?
+            .stealth_delivery_key
+    })
+}
+
+/// Check that `proof` comes from the holder of a Private or Anonymous ticket
+/// and authorizes the action described by `message`.
+///
+/// Private holders sign `message` with the ticket's stealth key. Anonymous
+/// holders send `next_commitment (32 bytes) ‖ preimage`, where the preimage
+/// opens the ticket's nullifier commitment, after posting
+/// `sha256(proof ‖ message)` with `commit_shielded_action` in an earlier
+/// ledger. The commit binds the action, so a front-runner who copies the
+/// revealed preimage cannot use it on other terms; the ticket then rotates
+/// to `next_commitment`, so the preimage proves nothing afterwards. The
+/// rotated ticket is saved here.
+fn verify_shielded_owner(
+    env: &Env,
+    ticket: &mut Ticket,
+    message: &Bytes,
+    proof: Bytes,
+) -> Result<(), PaymentError> {
+    match ticket.privacy_level {
+        PaymentPrivacy::Anonymous => {
+            if proof.len() <= 32 {
+                return Err(PaymentError::Unauthorized);
+            }
+            let next: BytesN<32> = proof
+                .slice(..32)
+                .try_into()
+                .map_err(|_| PaymentError::Unauthorized)?;
+            let hash: BytesN<32> = env.crypto().sha256(&proof.slice(32..)).into();
+            if Some(hash) != ticket.nullifier_commitment {
+                return Err(PaymentError::Unauthorized);
+            }
+            let mut committed = proof.clone();
+            committed.append(message);
+            let commit: BytesN<32> = env.crypto().sha256(&committed).into();
+            match storage::get_shielded_commit(env, &commit) {
+                Some(ledger) if ledger < env.ledger().sequence() => {}
+                _ => return Err(PaymentError::Unauthorized),
+            }
+            storage::remove_shielded_commit(env, &commit);
+            if storage::has_nullifier(env, &next) {
+                return Err(PaymentError::DuplicateRequest);
+            }
+            storage::mark_nullifier_spent(env, &next);
+            ticket.nullifier_commitment = Some(next);
+            storage::update_ticket(env, ticket);
+        }
+        PaymentPrivacy::Private => {
+            let pub_key = ticket_stealth_key(env, ticket).ok_or(PaymentError::Unauthorized)?;
+            if proof.len() != 64 {
+                return Err(PaymentError::Unauthorized);
+            }
+            let sig_bytes: BytesN<64> = proof.try_into().map_err(|_| PaymentError::Unauthorized)?;
+            env.crypto().ed25519_verify(&pub_key, message, &sig_bytes);
+        }
+        PaymentPrivacy::Standard => return Err(PaymentError::PrivacyLevelMismatch),
+    }
+    Ok(())
+}
+
+/// Message a Private seller signs to list a ticket. It binds the payout
+/// address, price and token so an observed signature cannot list the ticket
+/// on other terms, and the network, this contract and the ticket's resale
+/// nonce so it cannot be replayed on another deployment or after delisting.
+fn private_resale_message(
+    env: &Env,
+    ticket_id: u64,
+    payout: &Address,
+    price: i128,
+    token: &Option<Address>,
+) -> Bytes {
+    let mut message = Bytes::from_slice(env, PRIVATE_RESALE_DOMAIN);
+    message.extend_from_slice(&env.ledger().network_id().to_array());
+    message.append(&env.current_contract_address().to_xdr(env));
+    message.append(&ticket_id.to_xdr(env));
+    message.append(&storage::get_private_resale_nonce(env, ticket_id).to_xdr(env));
+    message.append(&payout.clone().to_xdr(env));
+    message.append(&price.to_xdr(env));
+    message.append(&token.clone().to_xdr(env));
+    message
+}
+
+/// Salted wallet hash stored for Private payments and tickets. Salting with
+/// the stealth key prevents brute-force enumeration of the wallet address.
+fn salted_wallet_hash(env: &Env, wallet: &Address, stealth_key: &BytesN<32>) -> BytesN<32> {
+    let mut preimage = wallet.clone().to_xdr(env);
+    preimage.append(&Bytes::from_slice(env, stealth_key.to_array().as_ref()));
+    env.crypto().sha256(&preimage).into()
+}
+
 /// SHA-256 of the payer address, used as the privacy-preserving ledger key for
 /// **Private** payments (nonce replay-protection and per-user ticket counters).
 /// Private payments accept wallet hashing by design; the raw address is never
@@ -157,6 +935,68 @@ fn private_wallet_hash(env: &Env, payer: &Address) -> BytesN<32> {
     env.crypto().sha256(&payer_xdr).into()
 }
 
+fn build_refund_claim_message(env: &Env, ticket_id: u64, recipient: &Address) -> Bytes {
+    let mut message = Bytes::from_slice(env, REFUND_CLAIM_DOMAIN);
+    message.extend_from_slice(&env.ledger().network_id().to_array());
+    message.append(&env.current_contract_address().to_xdr(env));
+    message.extend_from_slice(&ticket_id.to_be_bytes());
+    message.append(&recipient.clone().to_xdr(env));
+    message
+}
+
+/// Credit `amount` to the refund voucher of a Private/Anonymous payment,
+/// which stores no address to pay. The holder later claims it with
+/// `claim_refund_voucher`.
+fn credit_refund_voucher(env: &Env, payment: &PaymentRecord, amount: i128) {
+    let claimable = storage::get_claimable_refund(env, payment.payment_id);
+    storage::set_claimable_refund(env, payment.payment_id, claimable + amount);
+    events::emit_refund_claimable(env, payment, amount);
+}
+
+/// Refund the part of a Held payment on a cancelled event that the organizer
+/// does not keep: pushed to a Standard payer, credited to the voucher of a
+/// Private/Anonymous one. Returns the amount, zero if nothing is owed.
+fn settle_cancellation_share(
+    env: &Env,
+    payment: &mut PaymentRecord,
+    withdrawable_ratio_bps: u32,
+) -> Result<i128, PaymentError> {
+    let max_refund = payment.amount * ((10_000 - withdrawable_ratio_bps) as i128) / 10_000;
+    let share = max_refund - payment.refunded_amount;
+    if share <= 0 {
+        return Ok(0);
+    }
+
+    if let Some(refund_to) = payment.payer.clone() {
+        let token_client = token::Client::new(env, &payment.token);
+        token_client
+            .try_transfer(&env.current_contract_address(), &refund_to, &share)
+            .map_err(|_| PaymentError::TransferFailed)?
+            .map_err(|_| PaymentError::TransferFailed)?;
+    }
+    payment.refunded_amount += share;
+    payment.status = PaymentStatus::Refunded;
+    storage::update_payment(env, payment)?;
+
+    let token_revenue = storage::get_event_token_revenue(env, &payment.event_id, &payment.token);
+    storage::set_event_token_revenue(
+        env,
+        &payment.event_id,
+        &payment.token,
+        token_revenue - share,
+    );
+    storage::add_total_refunds(env, &payment.event_id, share);
+
+    // The refund event derives its masked identity from the stored payment,
+    // preserving the original privacy level.
+    if payment.payer.is_some() {
+        events::emit_payment_refunded(env, payment, share);
+    } else {
+        credit_refund_voucher(env, payment, share);
+    }
+    Ok(share)
+}
+
 #[contract]
 pub struct PaymentsContract;
 
@@ -198,8 +1038,6 @@ fn process_timed_out_disputes(env: &Env, event_id: &Symbol) -> Result<(), Paymen
                         if payment.status == PaymentStatus::Disputed {
                             payment.status = PaymentStatus::Held;
                             storage::update_payment(env, &payment)?;
-                            let rev = storage::get_event_revenue(env, event_id);
-                            storage::set_event_revenue(env, event_id, rev + payment.amount);
                             let token_rev =
                                 storage::get_event_token_revenue(env, event_id, &payment.token);
                             storage::set_event_token_revenue(
@@ -232,9 +1070,16 @@ fn validate_revenue_invariant(env: &Env, event_id: &Symbol) -> Result<(), Paymen
     process_timed_out_disputes(env, event_id)?;
 
     let total_payments = storage::get_total_payments(env, event_id);
+    let total_resale_fees = storage::get_total_resale_fees(env, event_id);
     let total_refunds = storage::get_total_refunds(env, event_id);
     let total_withdrawn = storage::get_total_withdrawn(env, event_id);
-    let platform_revenue = storage::get_platform_revenue(env, event_id);
+    let mut platform_revenue = storage::get_platform_revenue(env, event_id);
+    let accepted_token = storage::get_accepted_token(env).ok();
+    for token in storage::get_event_tokens(env, event_id).iter() {
+        if accepted_token.as_ref() != Some(&token) {
+            platform_revenue += storage::get_platform_token_revenue(env, event_id, &token);
+        }
+    }
 
     let mut total_disputed: i128 = 0;
     let disputes = storage::get_event_disputes(env, event_id);
@@ -249,8 +1094,9 @@ fn validate_revenue_invariant(env: &Env, event_id: &Symbol) -> Result<(), Paymen
     }
 
     let current_revenue = storage::get_event_revenue(env, event_id);
+    let bond_slashed = storage::get_event_bond(env, event_id).slashed;
 
-    if total_payments
+    if total_payments + total_resale_fees + bond_slashed
         != current_revenue + total_refunds + total_withdrawn + platform_revenue + total_disputed
     {
         return Err(PaymentError::AccountingMismatch);
@@ -313,7 +1159,31 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
         params.is_verified,
     )?;
 
-    if let Some(config) = storage::get_event_config(&env, &params.event_id) {
+    let config = storage::get_event_config(&env, &params.event_id);
+    match &config {
+        Some(config) => check_event_token(&env, &params.event_id, config, &params.token_address)?,
+        None => {
+            if params.token_address != storage::get_accepted_token(&env)?
+                && !storage::is_token_allowed(&env, &params.token_address)
+            {
+                return Err(PaymentError::TokenNotAllowed);
+            }
+        }
+    }
+
+    let mut identity_key = None;
+    let mut tier_id = params.tier_id;
+    if let Some(config) = config.filter(|_| params.issues_ticket) {
+        tier_id = check_tier_amount(
+            &env,
+            &params.event_id,
+            &config,
+            &params.token_address,
+            params.tier_id,
+            params.quantity,
+            params.amount + params.prepaid,
+        )?;
+
         if config.max_supply > 0 && config.sold_count >= config.max_supply {
             return Err(PaymentError::EventSoldOut);
         }
@@ -337,6 +1207,22 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
                 return Err(PaymentError::MaxTicketsReached);
             }
         }
+
+        // Wallet caps are trivially bypassed by rotating wallets, so events can
+        // also cap purchases per verified identity. Only identities the event
+        // contract has verified count; see `pay_for_ticket_with_identity`.
+        let identity_limit = storage::get_identity_limit(&env, &params.event_id);
+        if identity_limit > 0 {
+            let identity = params
+                .identity
+                .as_ref()
+                .ok_or(PaymentError::IdentityRequired)?;
+            let key = identity_scope_hash(&env, &params.event_id, identity);
+            if storage::get_identity_purchases(&env, &key) >= identity_limit {
+                return Err(PaymentError::IdentityLimitReached);
+            }
+            identity_key = Some(key);
+        }
     }
 
     if let Some(status) = storage::get_event_status(&env, &params.event_id) {
@@ -371,6 +1257,9 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
     }
 
     storage::save_payment(&env, &payment)?;
+    if let Some(tier_id) = tier_id {
+        storage::set_payment_tier(&env, payment_id, tier_id);
+    }
     storage::add_event_payment(&env, &params.event_id, payment_id);
     // Only Standard payments are indexed by raw address. Indexing Private or
     // Anonymous payments by their wallet would leak the payer identity.
@@ -393,9 +1282,8 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
             }
         }
     }
-    storage::add_event_revenue(&env, &params.event_id, params.amount);
-    storage::add_event_token_revenue(&env, &params.event_id, &params.token_address, params.amount);
     storage::add_event_token(&env, &params.event_id, &params.token_address);
+    storage::add_event_token_revenue(&env, &params.event_id, &params.token_address, params.amount);
     storage::add_total_payments(&env, &params.event_id, params.amount);
     storage::add_total_token_volume(&env, &params.event_id, &params.token_address, params.amount);
 
@@ -410,6 +1298,10 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
         );
     }
 
+    if !params.issues_ticket {
+        return Ok(payment_id);
+    }
+
     let ticket_id = storage::get_next_ticket_id(&env);
     let ticket = build_ticket(&payment, ticket_id);
     storage::save_ticket(&env, &ticket)?;
@@ -430,6 +1322,9 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
         // (see the read path); nothing wallet-derived is persisted.
         PaymentPrivacy::Anonymous => {}
     }
+    if let Some(key) = &identity_key {
+        storage::increment_identity_purchases(&env, key);
+    }
     if storage::get_event_config(&env, &params.event_id).is_some() {
         storage::increment_event_sold_count(&env, &params.event_id)?;
     }
@@ -438,16 +1333,67 @@ fn create_payment(env: Env, params: PaymentParams) -> Result<u64, PaymentError>
     Ok(payment_id)
 }
 
-fn collect_cancellation_organizer_pool(
-    env: &Env,
-    event_id: &Symbol,
-    token_address: &Address,
-    withdrawable_ratio_bps: u32,
-) -> Result<i128, PaymentError> {
-    let total_volume = storage::get_total_token_volume(env, event_id, token_address);
-
-    let mut disputed_volume = 0i128;
-    let disputes = storage::get_event_disputes(env, event_id);
+/// Return up to `amount` of a held payment to its payer, or set it aside as a
+/// claimable refund when the payment records no payer. Fully refunded payments
+/// are skipped; the amount actually refunded is returned.
+fn refund_payment_share(env: &Env, payment_id: u64, amount: i128) -> Result<i128, PaymentError> {
+    let mut payment = storage::get_payment(env, payment_id)?;
+    if payment.status == PaymentStatus::Refunded {
+        return Ok(0);
+    }
+    if payment.status != PaymentStatus::Held {
+        return Err(PaymentError::PaymentAlreadyProcessed);
+    }
+    if let Ok(meta) = storage::get_escrow_meta(env, &payment.event_id) {
+        if meta.auto_released {
+            return Err(PaymentError::PaymentAlreadyProcessed);
+        }
+    }
+
+    let refund_amt = amount.min(payment.amount - payment.refunded_amount);
+    if refund_amt <= 0 {
+        return Ok(0);
+    }
+
+    if let Some(refund_to) = payment.payer.clone() {
+        let token_client = token::Client::new(env, &payment.token);
+        token_client.transfer(&env.current_contract_address(), &refund_to, &refund_amt);
+    }
+
+    payment.refunded_amount += refund_amt;
+    if payment.refunded_amount == payment.amount {
+        payment.status = PaymentStatus::Refunded;
+    }
+    storage::update_payment(env, &payment)?;
+
+    let token_revenue = storage::get_event_token_revenue(env, &payment.event_id, &payment.token);
+    storage::set_event_token_revenue(
+        env,
+        &payment.event_id,
+        &payment.token,
+        token_revenue - refund_amt,
+    );
+    storage::add_total_refunds(env, &payment.event_id, refund_amt);
+
+    if payment.payer.is_some() {
+        events::emit_payment_refunded(env, &payment, refund_amt);
+    } else {
+        credit_refund_voucher(env, &payment, refund_amt);
+    }
+
+    Ok(refund_amt)
+}
+
+fn collect_cancellation_organizer_pool(
+    env: &Env,
+    event_id: &Symbol,
+    token_address: &Address,
+    withdrawable_ratio_bps: u32,
+) -> Result<i128, PaymentError> {
+    let total_volume = storage::get_total_token_volume(env, event_id, token_address);
+
+    let mut disputed_volume = 0i128;
+    let disputes = storage::get_event_disputes(env, event_id);
     for i in 0..disputes.len() {
         if let Some(ticket_id) = disputes.get(i) {
             if let Some(dispute) = storage::get_dispute(env, ticket_id) {
@@ -474,6 +1420,82 @@ fn ensure_no_splits(env: &Env, event_id: &Symbol) -> Result<(), PaymentError> {
     Ok(())
 }
 
+/// Lump-sum payout paths are closed while a vesting schedule governs the
+/// event; cancelled events settle through the cancellation pool instead.
+fn ensure_not_vesting(env: &Env, event_id: &Symbol) -> Result<(), PaymentError> {
+    if storage::get_vesting_schedule(env, event_id).is_some()
+        && storage::get_event_status(env, event_id) != Some(EventStatus::Cancelled)
+    {
+        return Err(PaymentError::VestingActive);
+    }
+    Ok(())
+}
+
+/// Gross `token` revenue the organizer may claim now. The vesting base is
+/// everything claimed so far plus what is still held, so disputes paid out
+/// of the unvested balance shrink later releases rather than failing.
+fn claimable_vested(
+    env: &Env,
+    event_id: &Symbol,
+    config: &EventConfig,
+    vesting: &VestingSchedule,
+    token: &Address,
+) -> i128 {
+    let current_ledger = env.ledger().sequence();
+    if current_ledger < config.event_end_ledger {
+        return 0;
+    }
+    let elapsed = current_ledger - config.event_end_ledger;
+    let linear_bps = 10_000 - vesting.release_bps;
+    let vested_bps = if elapsed >= vesting.vesting_ledgers {
+        10_000
+    } else {
+        vesting.release_bps as i128
+            + linear_bps as i128 * elapsed as i128 / vesting.vesting_ledgers as i128
+    };
+
+    let claimed = storage::get_vested_claimed(env, event_id, token);
+    let held = storage::get_event_token_revenue(env, event_id, token).max(0);
+    ((claimed + held) * vested_bps / 10_000 - claimed).clamp(0, held)
+}
+
+/// Count an approved dispute against the event's bond. Once approvals pass
+/// the slash threshold, the bond tops the payout-token escrow back up to zero
+/// whenever a refund overdraws it, e.g. after the organizer was already paid.
+fn apply_bond_to_dispute(env: &Env, event_id: &Symbol, token: &Address) {
+    let Some(config) = storage::get_event_config(env, event_id) else {
+        return;
+    };
+    let mut bond = storage::get_event_bond(env, event_id);
+    if bond.status != BondStatus::Locked && bond.status != BondStatus::Slashed {
+        return;
+    }
+
+    bond.approved_disputes += 1;
+    if bond.status == BondStatus::Locked
+        && bond.approved_disputes as u64 * 10_000
+            > config.sold_count as u64 * bond.slash_threshold_bps as u64
+    {
+        bond.status = BondStatus::Slashed;
+        events::emit_bond_slashed(
+            env,
+            event_id.clone(),
+            bond.approved_disputes,
+            config.sold_count,
+        );
+    }
+
+    if bond.status == BondStatus::Slashed && *token == config.payout_token {
+        let token_revenue = storage::get_event_token_revenue(env, event_id, token);
+        let cover = (-token_revenue).min(bond.amount - bond.slashed);
+        if cover > 0 {
+            bond.slashed += cover;
+            storage::set_event_token_revenue(env, event_id, token, token_revenue + cover);
+        }
+    }
+    storage::set_event_bond(env, event_id, &bond);
+}
+
 /// Look up a recipient's basis-point allocation within a split configuration.
 fn find_split_bps(splits: &soroban_sdk::Vec<RevenueSplit>, who: &Address) -> Option<u32> {
     // Convert RevenueSplit vec to (Address, u32) vec for common utility
@@ -576,8 +1598,7 @@ fn ensure_split_settled(env: &Env, event_id: &Symbol) -> Result<SplitSettlement,
         return Err(PaymentError::NoRevenue);
     }
 
-    let fee_bps = storage::get_platform_fee_bps(env) as i128;
-    let fee_amount = total_to_withdraw * fee_bps / 10_000;
+    let fee_amount = primary_platform_fee(env, event_id, &config, &payout_token, total_to_withdraw);
     let net = total_to_withdraw - fee_amount;
     if net <= 0 {
         return Err(PaymentError::NoRevenue);
@@ -589,8 +1610,6 @@ fn ensure_split_settled(env: &Env, event_id: &Symbol) -> Result<SplitSettlement,
     // remainder Held so attendees can still claim their pro-rata refunds.
     if withdrawable_ratio_bps == 10_000 {
         storage::set_event_token_revenue(env, event_id, &payout_token, 0);
-        let current_rev = storage::get_event_revenue(env, event_id);
-        storage::set_event_revenue(env, event_id, current_rev - total_to_withdraw);
     } else {
         let current_token_rev = storage::get_event_token_revenue(env, event_id, &payout_token);
         storage::set_event_token_revenue(
@@ -599,12 +1618,10 @@ fn ensure_split_settled(env: &Env, event_id: &Symbol) -> Result<SplitSettlement,
             &payout_token,
             current_token_rev - total_to_withdraw,
         );
-        let current_rev = storage::get_event_revenue(env, event_id);
-        storage::set_event_revenue(env, event_id, current_rev - total_to_withdraw);
     }
 
     if fee_amount > 0 {
-        storage::add_platform_revenue(env, event_id, fee_amount);
+        book_platform_fee(env, event_id, &payout_token, fee_amount);
         events::emit_platform_fee_collected(
             env,
             event_id.clone(),
@@ -660,9 +1677,104 @@ impl PaymentsContract {
         storage::get_accepted_token(&env)
     }
 
+    /// Add `token` to the payment token allowlist, or update its metadata.
+    /// The accepted token is always allowed; listing it only records its
+    /// decimals.
+    pub fn allow_token(env: Env, token: Address, decimals: u32) -> Result<(), PaymentError> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+
+        if decimals > MAX_TOKEN_DECIMALS {
+            return Err(PaymentError::InvalidTokenMetadata);
+        }
+        if !storage::is_token_allowed(&env, &token)
+            && storage::get_allowed_tokens(&env).len() >= MAX_ALLOWED_TOKENS
+        {
+            return Err(PaymentError::TooManyTokens);
+        }
+
+        storage::set_token_info(
+            &env,
+            &TokenInfo {
+                token: token.clone(),
+                decimals,
+            },
+        );
+        events::emit_token_allowlist_updated(&env, token, decimals, true);
+        Ok(())
+    }
+
+    /// Remove `token` from the allowlist. Events that accept it stop taking
+    /// new payments in it; balances already held stay withdrawable.
+    pub fn disallow_token(env: Env, token: Address) -> Result<(), PaymentError> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+
+        if token == storage::get_accepted_token(&env)? {
+            return Err(PaymentError::InvalidPayoutToken);
+        }
+        let info = storage::get_token_info(&env, &token).ok_or(PaymentError::TokenNotAllowed)?;
+        storage::remove_token_info(&env, &token);
+        events::emit_token_allowlist_updated(&env, token, info.decimals, false);
+        Ok(())
+    }
+
+    /// Use `oracle`, a SEP-40 price feed, for quoted payments. Quotes fail
+    /// once the feed's latest price is older than `max_price_age` seconds.
+    pub fn set_price_oracle(
+        env: Env,
+        oracle: Address,
+        max_price_age: u64,
+    ) -> Result<(), PaymentError> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        storage::set_oracle_config(
+            &env,
+            &OracleConfig {
+                oracle,
+                max_price_age,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<OracleConfig> {
+        storage::get_oracle_config(&env)
+    }
+
+    pub fn get_allowed_tokens(env: Env) -> Vec<TokenInfo> {
+        let mut tokens = Vec::new(&env);
+        for token in storage::get_allowed_tokens(&env).iter() {
+            if let Some(info) = storage::get_token_info(&env, &token) {
+                tokens.push_back(info);
+            }
+        }
+        tokens
+    }
+
+    pub fn get_token_info(env: Env, token: Address) -> Option<TokenInfo> {
+        storage::get_token_info(&env, &token)
+    }
+
+    /// Tokens an event takes payment in: its payout token first, then the
+    /// organizer's additional accepted tokens.
+    pub fn get_accepted_tokens(env: Env, event_id: Symbol) -> Result<Vec<Address>, PaymentError> {
+        let config =
+            storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        let mut tokens = Vec::new(&env);
+        tokens.push_back(config.payout_token);
+        tokens.append(&storage::get_event_accepted_tokens(&env, &event_id));
+        Ok(tokens)
+    }
+
     pub fn get_event_config(env: Env, event_id: Symbol) -> Result<EventConfig, PaymentError> {
         storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)
     }
+    /// Organizer bond held against the event; `BondStatus::None` when no
+    /// bond was taken.
+    pub fn get_event_bond(env: Env, event_id: Symbol) -> EventBond {
+        storage::get_event_bond(&env, &event_id)
+    }
     pub fn get_ticket(env: Env, ticket_id: u64) -> Result<Ticket, PaymentError> {
         storage::get_ticket(&env, ticket_id)
     }
@@ -670,6 +1782,103 @@ impl PaymentsContract {
         storage::get_owner_tickets(&env, &owner)
     }
 
+    /// Link the ledger ticket issued for `payment_id` to the ticket minted for
+    /// it in the ticket contract. Called by the event contract after minting.
+    pub fn link_minted_ticket(
+        env: Env,
+        payment_id: u64,
+        minted_ticket_id: u64,
+    ) -> Result<(), PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        storage::get_payment(&env, payment_id)?;
+        if storage::get_minted_ticket_id(&env, payment_id).is_some() {
+            return Err(PaymentError::TicketAlreadyLinked);
+        }
+        storage::link_minted_ticket(&env, payment_id, minted_ticket_id)
+    }
+
+    /// Link every ticket minted for one batch payment back to it, so each can
+    /// be refunded its share. The ledger ticket tracks the first of them.
+    pub fn link_batch_minted_tickets(
+        env: Env,
+        payment_id: u64,
+        minted_ticket_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<(), PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        storage::get_payment(&env, payment_id)?;
+        let first_ticket_id = minted_ticket_ids
+            .first()
+            .ok_or(PaymentError::TicketNotFound)?;
+        if storage::get_minted_ticket_id(&env, payment_id).is_some() {
+            return Err(PaymentError::TicketAlreadyLinked);
+        }
+        for minted_ticket_id in minted_ticket_ids.iter() {
+            if storage::get_minted_ticket_payment(&env, minted_ticket_id).is_some() {
+                return Err(PaymentError::TicketAlreadyLinked);
+            }
+        }
+        storage::link_minted_ticket(&env, payment_id, first_ticket_id)?;
+        for minted_ticket_id in minted_ticket_ids.iter().skip(1) {
+            storage::set_minted_ticket_payment(&env, minted_ticket_id, payment_id);
+        }
+        Ok(())
+    }
+
+    /// Link a ledger ticket issued before minted-ticket links were recorded.
+    pub fn admin_link_minted_ticket(
+        env: Env,
+        admin: Address,
+        ticket_id: u64,
+        minted_ticket_id: u64,
+    ) -> Result<(), PaymentError> {
+        let stored_admin = storage::get_admin(&env)?;
+        if admin != stored_admin {
+            return Err(PaymentError::Unauthorized);
+        }
+        admin.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if storage::get_minted_ticket_id(&env, ticket.payment_id).is_some()
+            || storage::get_minted_ticket_payment(&env, minted_ticket_id).is_some()
+        {
+            return Err(PaymentError::TicketAlreadyLinked);
+        }
+        storage::link_minted_ticket(&env, ticket.payment_id, minted_ticket_id)
+    }
+    pub fn get_minted_ticket_id(env: Env, ticket_id: u64) -> Option<u64> {
+        let ticket = storage::get_ticket(&env, ticket_id).ok()?;
+        storage::get_minted_ticket_id(&env, ticket.payment_id)
+    }
+    /// Payment behind a ticket-contract ticket, if it was linked at mint.
+    pub fn get_minted_ticket_payment(env: Env, minted_ticket_id: u64) -> Option<u64> {
+        storage::get_minted_ticket_payment(&env, minted_ticket_id)
+    }
+
+    /// Repair the owner recorded for each linked ledger ticket from the ticket
+    /// contract. Anyone may call this: it only copies ownership from the
+    /// source of truth. Returns how many tickets were out of date.
+    pub fn reconcile_ticket_owners(
+        env: Env,
+        ticket_ids: soroban_sdk::Vec<u64>,
+    ) -> Result<u32, PaymentError> {
+        if ticket_ids.len() > MAX_RECONCILE_BATCH {
+            return Err(PaymentError::BatchTooLarge);
+        }
+        let mut repaired = 0u32;
+        for ticket_id in ticket_ids.iter() {
+            let mut ticket = storage::get_ticket(&env, ticket_id)?;
+            let before = ticket.owner.clone();
+            if resolve_ticket_owner(&env, &mut ticket) != before {
+                repaired += 1;
+            }
+        }
+        Ok(repaired)
+    }
+
     pub fn is_paused(env: Env) -> bool {
         storage::is_paused(&env)
     }
@@ -727,6 +1936,11 @@ impl PaymentsContract {
                 zk_email_commitment: None,
                 nullifier_commitment,
                 stealth_delivery_key,
+                issues_ticket: true,
+                identity: None,
+                tier_id: None,
+                quantity: 1,
+                prepaid: 0,
             },
         )
     }
@@ -757,6 +1971,11 @@ impl PaymentsContract {
                 zk_email_commitment,
                 nullifier_commitment: None,
                 stealth_delivery_key: None,
+                issues_ticket: true,
+                identity: None,
+                tier_id: None,
+                quantity: 1,
+                prepaid: 0,
             },
         )
     }
@@ -787,10 +2006,595 @@ impl PaymentsContract {
                 zk_email_commitment: None,
                 nullifier_commitment: None,
                 stealth_delivery_key: None,
+                issues_ticket: true,
+                identity: None,
+                tier_id: None,
+                quantity: 1,
+                prepaid: 0,
+            },
+        )
+    }
+
+    /// Standard purchase charged against the event's per-identity cap.
+    /// `identity` must be scoped to this event, e.g. the nullifier of a
+    /// ZkPassport proof generated for the event. Only the event contract,
+    /// which verifies the proof, may charge an identity.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_for_ticket_with_identity(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+        amount: i128,
+        email_hash: Option<BytesN<32>>,
+        token_address: Address,
+        identity: BytesN<32>,
+    ) -> Result<u64, PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        create_payment(
+            env,
+            PaymentParams {
+                nonce,
+                payer,
+                event_id,
+                amount,
+                token_address,
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level: PaymentPrivacy::Standard,
+                email_hash,
+                zk_email_commitment: None,
+                nullifier_commitment: None,
+                stealth_delivery_key: None,
+                issues_ticket: true,
+                identity: Some(identity),
+                tier_id: None,
+                quantity: 1,
+                prepaid: 0,
+            },
+        )
+    }
+
+    /// Standard purchase of `quantity` tickets in `tier_id`. `amount` must
+    /// match the event's synced price for the tier; `identity`, when given,
+    /// is charged against the per-identity cap as in
+    /// `pay_for_ticket_with_identity`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_for_tier(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        quantity: u32,
+        amount: i128,
+        email_hash: Option<BytesN<32>>,
+        token_address: Address,
+        identity: Option<BytesN<32>>,
+    ) -> Result<u64, PaymentError> {
+        if identity.is_some() {
+            let event_contract = storage::get_event_contract(&env)?;
+            event_contract.require_auth();
+        }
+
+        create_payment(
+            env,
+            PaymentParams {
+                nonce,
+                payer,
+                event_id,
+                amount,
+                token_address,
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level: PaymentPrivacy::Standard,
+                email_hash,
+                zk_email_commitment: None,
+                nullifier_commitment: None,
+                stealth_delivery_key: None,
+                issues_ticket: true,
+                identity,
+                tier_id: Some(tier_id),
+                quantity,
+                prepaid: 0,
             },
         )
     }
 
+    /// Purchase of one ticket in `tier_id` at any privacy level. `amount`
+    /// must match the event's synced price for the tier; the privacy inputs
+    /// are those of `pay_for_ticket`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_for_tier_with_privacy(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        amount: i128,
+        token_address: Address,
+        privacy_level: PaymentPrivacy,
+        nullifier_commitment: Option<BytesN<32>>,
+        stealth_delivery_key: Option<BytesN<32>>,
+    ) -> Result<u64, PaymentError> {
+        create_payment(
+            env,
+            PaymentParams {
+                nonce,
+                payer,
+                event_id,
+                amount,
+                token_address,
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level,
+                email_hash: None,
+                zk_email_commitment: None,
+                nullifier_commitment,
+                stealth_delivery_key,
+                issues_ticket: true,
+                identity: None,
+                tier_id: Some(tier_id),
+                quantity: 1,
+                prepaid: 0,
+            },
+        )
+    }
+
+    /// Standard purchase of one ticket in `tier_id`, a tier priced in the
+    /// oracle's reference unit (e.g. USD), paid in `token_address` at the
+    /// current quote. The payer bounds slippage with `max_amount`, in token
+    /// units.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pay_for_ticket_quoted(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        email_hash: Option<BytesN<32>>,
+        token_address: Address,
+        max_amount: i128,
+    ) -> Result<u64, PaymentError> {
+        let tier = reference_tier(&env, &event_id, tier_id)?;
+        let (amount, price) = quote_token_amount(&env, &token_address, tier.price)?;
+        if amount > max_amount {
+            return Err(PaymentError::SlippageExceeded);
+        }
+
+        let payment_id = create_payment(
+            env.clone(),
+            PaymentParams {
+                nonce,
+                payer,
+                event_id: event_id.clone(),
+                amount,
+                token_address: token_address.clone(),
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level: PaymentPrivacy::Standard,
+                email_hash,
+                zk_email_commitment: None,
+                nullifier_commitment: None,
+                stealth_delivery_key: None,
+                issues_ticket: true,
+                identity: None,
+                tier_id: Some(tier_id),
+                quantity: 1,
+                prepaid: 0,
+            },
+        )?;
+        events::emit_price_quoted(
+            &env,
+            event_id,
+            payment_id,
+            token_address,
+            tier.price,
+            amount,
+            price.price,
+            price.timestamp,
+        );
+        Ok(payment_id)
+    }
+
+    /// Token amount `pay_for_ticket_quoted` would charge right now for a
+    /// ticket in `tier_id`.
+    pub fn quote_payment(
+        env: Env,
+        event_id: Symbol,
+        tier_id: u32,
+        token_address: Address,
+    ) -> Result<i128, PaymentError> {
+        let tier = reference_tier(&env, &event_id, tier_id)?;
+        quote_token_amount(&env, &token_address, tier.price).map(|(amount, _)| amount)
+    }
+
+    /// Count a free ticket against the event's per-identity cap. Free tickets
+    /// take no payment, so the event contract records them here to keep a
+    /// single counter per identity.
+    pub fn record_identity_purchase(
+        env: Env,
+        event_id: Symbol,
+        identity: BytesN<32>,
+    ) -> Result<(), PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        let identity_limit = storage::get_identity_limit(&env, &event_id);
+        if identity_limit == 0 {
+            return Ok(());
+        }
+        let key = identity_scope_hash(&env, &event_id, &identity);
+        if storage::get_identity_purchases(&env, &key) >= identity_limit {
+            return Err(PaymentError::IdentityLimitReached);
+        }
+        storage::increment_identity_purchases(&env, &key);
+        Ok(())
+    }
+
+    pub fn get_identity_purchases(env: Env, event_id: Symbol, identity: BytesN<32>) -> u32 {
+        storage::get_identity_purchases(&env, &identity_scope_hash(&env, &event_id, &identity))
+    }
+
+    /// Set the per-identity ticket cap mirrored from the event contract.
+    pub fn set_identity_limit(
+        env: Env,
+        event_id: Symbol,
+        max_tickets_per_identity: u32,
+    ) -> Result<(), PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        storage::set_identity_limit(&env, &event_id, max_tickets_per_identity);
+        Ok(())
+    }
+
+    pub fn get_identity_limit(env: Env, event_id: Symbol) -> u32 {
+        storage::get_identity_limit(&env, &event_id)
+    }
+
+    /// Replace the tier prices mirrored from the event contract. Ticket
+    /// purchases must pay a synced tier's price; an empty list leaves the
+    /// event with no tier to sell.
+    pub fn sync_tier_prices(
+        env: Env,
+        event_id: Symbol,
+        tier_prices: Vec<TierPrice>,
+    ) -> Result<(), PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        if tier_prices
+            .iter()
+            .any(|tier| tier.price < 0 || (tier.reference_priced && tier.price == 0))
+        {
+            return Err(PaymentError::InvalidAmount);
+        }
+        storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        storage::set_event_tier_prices(&env, &event_id, &tier_prices);
+        Ok(())
+    }
+
+    pub fn get_tier_prices(env: Env, event_id: Symbol) -> Vec<TierPrice> {
+        storage::get_event_tier_prices(&env, &event_id)
+    }
+
+    /// Tier a payment paid for; `None` for payments that named no tier
+    /// before tier prices were synced, and for top-ups.
+    pub fn get_payment_tier(env: Env, payment_id: u64) -> Option<u32> {
+        storage::get_payment_tier(&env, payment_id)
+    }
+
+    /// Charge the price difference when the event contract moves a ticket to
+    /// a dearer tier. The charge is held like any other payment for the event
+    /// and is refunded with the ticket, but issues no ledger ticket and does
+    /// not count toward supply or per-user limits.
+    pub fn pay_ticket_upgrade(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+        minted_ticket_id: u64,
+        amount: i128,
+    ) -> Result<u64, PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let token_address = storage::get_accepted_token(&env)?;
+        let payment_id = create_payment(
+            env.clone(),
+            PaymentParams {
+                nonce,
+                payer,
+                event_id,
+                amount,
+                token_address,
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level: PaymentPrivacy::Standard,
+                email_hash: None,
+                zk_email_commitment: None,
+                nullifier_commitment: None,
+                stealth_delivery_key: None,
+                issues_ticket: false,
+                identity: None,
+                tier_id: None,
+                quantity: 1,
+                prepaid: 0,
+            },
+        )?;
+        storage::add_ticket_upgrade_payment(&env, minted_ticket_id, payment_id);
+
+        Ok(payment_id)
+    }
+
+    /// Start an installment purchase of one `tier_id` ticket, charging the
+    /// schedule's deposit now. The event contract holds the tier inventory
+    /// while top-ups arrive through `pay_installment`; `penalty_bps` of the
+    /// deposit is kept if the plan defaults.
+    #[allow(clippy::too_many_arguments)]
+    pub fn open_installment_plan(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+        tier_id: u32,
+        schedule: InstallmentSchedule,
+        penalty_bps: u32,
+    ) -> Result<u64, PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        if storage::get_installment_plan(&env, &event_id, &payer)
+            .is_some_and(|plan| plan.status == InstallmentStatus::Active)
+        {
+            return Err(PaymentError::InstallmentPlanExists);
+        }
+        if penalty_bps > 10_000 || schedule.deposit <= 0 || schedule.installments.is_empty() {
+            return Err(PaymentError::InvalidInstallmentSchedule);
+        }
+        let mut total = schedule.deposit;
+        let mut last_due = env.ledger().timestamp();
+        for installment in schedule.installments.iter() {
+            if installment.amount <= 0 || installment.due_at <= last_due {
+                return Err(PaymentError::InvalidInstallmentSchedule);
+            }
+            total = total
+                .checked_add(installment.amount)
+                .ok_or(PaymentError::InvalidInstallmentSchedule)?;
+            last_due = installment.due_at;
+        }
+
+        let token_address = storage::get_accepted_token(&env)?;
+        if let Some(config) = storage::get_event_config(&env, &event_id) {
+            check_tier_amount(
+                &env,
+                &event_id,
+                &config,
+                &token_address,
+                Some(tier_id),
+                1,
+                total,
+            )?;
+        }
+
+        let payment_id = create_payment(
+            env.clone(),
+            PaymentParams {
+                nonce,
+                payer: payer.clone(),
+                event_id: event_id.clone(),
+                amount: schedule.deposit,
+                token_address,
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level: PaymentPrivacy::Standard,
+                email_hash: None,
+                zk_email_commitment: None,
+                nullifier_commitment: None,
+                stealth_delivery_key: None,
+                issues_ticket: false,
+                identity: None,
+                tier_id: Some(tier_id),
+                quantity: 1,
+                prepaid: 0,
+            },
+        )?;
+        storage::set_installment_plan(
+            &env,
+            &InstallmentPlan {
+                event_id,
+                payer,
+                tier_id,
+                schedule,
+                penalty_bps,
+                paid_count: 0,
+                payment_ids: Vec::from_array(&env, [payment_id]),
+                status: InstallmentStatus::Active,
+            },
+        );
+
+        Ok(payment_id)
+    }
+
+    /// Pay the next installment before its deadline. The last one issues
+    /// the ledger ticket and completes the plan; the updated plan is
+    /// returned.
+    pub fn pay_installment(
+        env: Env,
+        nonce: u64,
+        payer: Address,
+        event_id: Symbol,
+    ) -> Result<InstallmentPlan, PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let mut plan = storage::get_installment_plan(&env, &event_id, &payer)
+            .filter(|plan| plan.status == InstallmentStatus::Active)
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+        let installment = plan
+            .schedule
+            .installments
+            .get(plan.paid_count)
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+        if env.ledger().timestamp() > installment.due_at {
+            return Err(PaymentError::InstallmentOverdue);
+        }
+
+        let mut prepaid = plan.schedule.deposit;
+        for paid in plan
+            .schedule
+            .installments
+            .iter()
+            .take(plan.paid_count as usize)
+        {
+            prepaid += paid.amount;
+        }
+        let is_final = plan.paid_count + 1 == plan.schedule.installments.len();
+        let payment_id = create_payment(
+            env.clone(),
+            PaymentParams {
+                nonce,
+                payer,
+                event_id,
+                amount: installment.amount,
+                token_address: storage::get_accepted_token(&env)?,
+                is_anonymous: false,
+                is_verified: false,
+                privacy_level: PaymentPrivacy::Standard,
+                email_hash: None,
+                zk_email_commitment: None,
+                nullifier_commitment: None,
+                stealth_delivery_key: None,
+                issues_ticket: is_final,
+                identity: None,
+                tier_id: Some(plan.tier_id),
+                quantity: 1,
+                prepaid,
+            },
+        )?;
+
+        plan.payment_ids.push_back(payment_id);
+        plan.paid_count += 1;
+        if is_final {
+            plan.status = InstallmentStatus::Completed;
+        }
+        storage::set_installment_plan(&env, &plan);
+        Ok(plan)
+    }
+
+    /// Link the ticket-contract ticket minted for a completed plan. The
+    /// final payment carries the link; earlier installments are attached
+    /// like upgrade top-ups so refunds of the ticket cover them.
+    pub fn link_installment_ticket(
+        env: Env,
+        event_id: Symbol,
+        payer: Address,
+        minted_ticket_id: u64,
+    ) -> Result<(), PaymentError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let plan = storage::get_installment_plan(&env, &event_id, &payer)
+            .filter(|plan| plan.status == InstallmentStatus::Completed)
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+        let final_payment_id = plan
+            .payment_ids
+            .last()
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+        if storage::get_minted_ticket_id(&env, final_payment_id).is_some() {
+            return Err(PaymentError::TicketAlreadyLinked);
+        }
+        storage::link_minted_ticket(&env, final_payment_id, minted_ticket_id)?;
+        for payment_id in plan.payment_ids.iter() {
+            if payment_id != final_payment_id {
+                storage::add_ticket_upgrade_payment(&env, minted_ticket_id, payment_id);
+            }
+        }
+        Ok(())
+    }
+
+    /// Close a plan whose next installment is past due. Everything paid is
+    /// refunded except the plan's penalty share of the deposit, which stays
+    /// with the event as revenue. Returns the amount refunded.
+    pub fn default_installment_plan(
+        env: Env,
+        event_id: Symbol,
+        payer: Address,
+    ) -> Result<i128, PaymentError> {
+        require_not_paused(&env)?;
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let mut plan = storage::get_installment_plan(&env, &event_id, &payer)
+            .filter(|plan| plan.status == InstallmentStatus::Active)
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+        let installment = plan
+            .schedule
+            .installments
+            .get(plan.paid_count)
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+        if env.ledger().timestamp() <= installment.due_at {
+            return Err(PaymentError::InstallmentNotOverdue);
+        }
+
+        let penalty = plan.schedule.deposit * plan.penalty_bps as i128 / 10_000;
+        let mut refunded = 0;
+        for (i, payment_id) in plan.payment_ids.iter().enumerate() {
+            let share = if i == 0 {
+                plan.schedule.deposit - penalty
+            } else {
+                i128::MAX
+            };
+            refunded += refund_payment_share(&env, payment_id, share)?;
+        }
+
+        plan.status = InstallmentStatus::Defaulted;
+        storage::set_installment_plan(&env, &plan);
+        Ok(refunded)
+    }
+
+    /// Close a plan on a cancelled event. The holder missed nothing, so
+    /// everything paid is refunded with no penalty. Returns the amount
+    /// refunded.
+    pub fn cancel_installment_plan(
+        env: Env,
+        event_id: Symbol,
+        payer: Address,
+    ) -> Result<i128, PaymentError> {
+        require_not_paused(&env)?;
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        if storage::get_event_status(&env, &event_id) != Some(EventStatus::Cancelled) {
+            return Err(PaymentError::EventNotActive);
+        }
+        let mut plan = storage::get_installment_plan(&env, &event_id, &payer)
+            .filter(|plan| plan.status == InstallmentStatus::Active)
+            .ok_or(PaymentError::InstallmentPlanNotFound)?;
+
+        let mut refunded = 0;
+        for payment_id in plan.payment_ids.iter() {
+            refunded += refund_payment_share(&env, payment_id, i128::MAX)?;
+        }
+
+        plan.status = InstallmentStatus::Cancelled;
+        storage::set_installment_plan(&env, &plan);
+        Ok(refunded)
+    }
+
+    pub fn get_installment_plan(
+        env: Env,
+        event_id: Symbol,
+        payer: Address,
+    ) -> Option<InstallmentPlan> {
+        storage::get_installment_plan(&env, &event_id, &payer)
+    }
+
     pub fn sync_event_privacy(
         env: Env,
         event_contract: Address,
@@ -830,6 +2634,7 @@ impl PaymentsContract {
         resale_royalty_bps: u32,
         max_resale_price: Option<i128>,
         allow_free_ticket_transfer: bool,
+        accepted_tokens: Vec<Address>,
     ) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
         if event_contract != storage::get_event_contract(&env)? {
@@ -842,6 +2647,22 @@ impl PaymentsContract {
             return Err(PaymentError::InvalidPayoutToken);
         }
 
+        // Extra tokens must be allowlisted; the payout token and repeats are
+        // dropped so the stored set stays minimal.
+        let mut extra_tokens = Vec::new(&env);
+        for token in accepted_tokens.iter() {
+            if token == payout_token || extra_tokens.contains(&token) {
+                continue;
+            }
+            if !storage::is_token_allowed(&env, &token) {
+                return Err(PaymentError::TokenNotAllowed);
+            }
+            extra_tokens.push_back(token);
+        }
+        if extra_tokens.len() > MAX_EVENT_TOKENS {
+            return Err(PaymentError::TooManyTokens);
+        }
+
         let (
             existing_sold,
             existing_admin_delay,
@@ -866,6 +2687,19 @@ impl PaymentsContract {
             (0, 0, None, None, false)
         };
 
+        // Snapshot the fee terms on the first sync so later platform changes
+        // never reach an event that is already on sale.
+        if storage::get_event_fee_schedule(&env, &event_id).is_none() {
+            storage::set_event_fee_schedule(
+                &env,
+                &event_id,
+                &current_fee_schedule(&env, &organizer),
+            );
+        }
+        if storage::get_event_accepted_tokens(&env, &event_id) != extra_tokens {
+            storage::set_event_accepted_tokens(&env, &event_id, &extra_tokens);
+        }
+
         storage::set_event_config(
             &env,
             &event_id,
@@ -907,16 +2741,6 @@ impl PaymentsContract {
         admin.require_auth();
 
         let mut payment = storage::get_payment(&env, payment_id)?;
-
-        // Anonymous/Private payments cannot be refunded on-chain (no address stored).
-        // Off-chain settlement via stealth key or nullifier commitment is required.
-        match payment.privacy_level {
-            PaymentPrivacy::Anonymous | PaymentPrivacy::Private => {
-                return Err(PaymentError::RefundNotAllowed);
-            }
-            PaymentPrivacy::Standard => {}
-        }
-
         if payment.status == PaymentStatus::Refunded {
             return Err(PaymentError::PaymentAlreadyRefunded);
         }
@@ -956,9 +2780,8 @@ impl PaymentsContract {
         }
 
         // Only Standard payments carry an on-chain payer address to refund to.
-        // Private/Anonymous payments deliberately store no raw address; their
-        // refund settlement is handled off-chain via the stealth delivery key /
-        // nullifier, so no on-chain transfer target is dereferenced here.
+        // Private/Anonymous payments deliberately store no raw address, so
+        // their refund is credited to the payment's voucher instead.
         if let Some(refund_to) = payment.payer.clone() {
             let token_client = token::Client::new(&env, &payment.token);
             token_client.transfer(&env.current_contract_address(), &refund_to, &refund_amt);
@@ -970,8 +2793,6 @@ impl PaymentsContract {
         }
 
         storage::update_payment(&env, &payment)?;
-        let revenue = storage::get_event_revenue(&env, &payment.event_id);
-        storage::set_event_revenue(&env, &payment.event_id, revenue - refund_amt);
 
         let token_revenue =
             storage::get_event_token_revenue(&env, &payment.event_id, &payment.token);
@@ -986,7 +2807,11 @@ impl PaymentsContract {
         // Refund event preserves the original payment's privacy level: the
         // identity exposed is derived from the stored record, never re-derived
         // from event-level config.
-        events::emit_payment_refunded(&env, &payment, refund_amt);
+        if payment.payer.is_some() {
+            events::emit_payment_refunded(&env, &payment, refund_amt);
+        } else {
+            credit_refund_voucher(&env, &payment, refund_amt);
+        }
 
         Ok(())
     }
@@ -995,6 +2820,7 @@ impl PaymentsContract {
         require_not_paused(&env)?;
         organizer.require_auth();
         ensure_no_splits(&env, &event_id)?;
+        ensure_not_vesting(&env, &event_id)?;
 
         let stored_organizer = storage::get_event_organizer(&env, &event_id)?;
         if organizer != stored_organizer {
@@ -1085,8 +2911,8 @@ impl PaymentsContract {
 
         let token_client = token::Client::new(&env, &payout_token);
 
-        let fee_bps = storage::get_platform_fee_bps(&env) as i128;
-        let fee_amount = total_to_withdraw * fee_bps / 10_000;
+        let fee_amount =
+            primary_platform_fee(&env, &event_id, &config, &payout_token, total_to_withdraw);
         let organizer_amount = total_to_withdraw - fee_amount;
         // Transfer organizer share
         token_client.transfer(
@@ -1095,7 +2921,7 @@ impl PaymentsContract {
             &organizer_amount,
         );
         if fee_amount > 0 {
-            storage::add_platform_revenue(&env, &event_id, fee_amount);
+            book_platform_fee(&env, &event_id, &payout_token, fee_amount);
             events::emit_platform_fee_collected(
                 &env,
                 event_id.clone(),
@@ -1116,9 +2942,6 @@ impl PaymentsContract {
                 &payout_token,
                 current_token_rev - total_to_withdraw,
             );
-
-            let current_rev = storage::get_event_revenue(&env, &event_id);
-            storage::set_event_revenue(&env, &event_id, current_rev - total_to_withdraw);
         }
 
         storage::add_total_withdrawn(&env, &event_id, organizer_amount);
@@ -1205,8 +3028,7 @@ impl PaymentsContract {
         let mut payment = storage::get_payment(&env, payment_id)?;
         // Only Standard payments carry an on-chain payer address and are
         // refundable through this path. Anonymous/Private payments store no
-        // address, so an on-chain refund is not possible — settlement happens
-        // off-chain via the stealth key / nullifier commitment.
+        // address; their holders claim through `claim_refund_voucher`.
         let stored_payer = payment
             .payer
             .clone()
@@ -1233,38 +3055,77 @@ impl PaymentsContract {
             return Err(PaymentError::NoRevenue);
         }
 
-        let max_refund = payment.amount * (refund_ratio_bps as i128) / 10000;
-        let remaining = max_refund - payment.refunded_amount;
-
-        if remaining <= 0 {
+        if settle_cancellation_share(&env, &mut payment, withdrawable_ratio_bps)? <= 0 {
             return Err(PaymentError::InvalidAmount);
         }
+        Ok(())
+    }
+    /// Push cancellation refunds for up to `limit` payments of `event_id`,
+    /// starting at `cursor` or the persisted position, whichever comes first.
+    /// Anyone may call it, so a cursor can revisit payments, e.g. ones that
+    /// were disputed on the first pass, but never skip ahead of the persisted
+    /// position, which only moves forward. Standard payers are paid their pro-rata share
+    /// directly, Private/Anonymous refunds go to vouchers; refunded, disputed
+    /// and released payments are skipped. A payer who cannot receive the
+    /// transfer is skipped too and keeps a Held payment to `claim_refund`
+    /// later. `limit` is capped at `MAX_REFUND_PAGE_SIZE`.
+    pub fn process_cancellation_refunds(
+        env: Env,
+        event_id: Symbol,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<RefundProgress, PaymentError> {
+        require_not_paused(&env)?;
+        if storage::get_event_status(&env, &event_id) != Some(EventStatus::Cancelled) {
+            return Err(PaymentError::EventNotActive);
+        }
+        let withdrawable_ratio_bps = storage::get_event_config(&env, &event_id)
+            .and_then(|c| c.withdrawable_ratio_bps)
+            .unwrap_or(0);
+        if withdrawable_ratio_bps >= 10_000 {
+            return Err(PaymentError::NoRevenue);
+        }
 
-        let token_client = token::Client::new(&env, &payment.token);
-        token_client.transfer(&env.current_contract_address(), &stored_payer, &remaining);
-
-        payment.refunded_amount += remaining;
-        payment.status = PaymentStatus::Refunded;
-        storage::update_payment(&env, &payment)?;
-
-        let revenue = storage::get_event_revenue(&env, &payment.event_id);
-        storage::set_event_revenue(&env, &payment.event_id, revenue - remaining);
+        let count = storage::get_event_payments_count(&env, &event_id);
+        let persisted = storage::get_cancellation_refund_cursor(&env, &event_id);
+        let start = cursor.min(persisted);
+        let end = start
+            .saturating_add(u64::from(limit.min(MAX_REFUND_PAGE_SIZE)))
+            .min(count);
+        let mut refunded_count = 0u32;
+        for index in start..end {
+            let Some(payment_id) = storage::get_event_payment_at(&env, &event_id, index) else {
+                continue;
+            };
+            let mut payment = storage::get_payment(&env, payment_id)?;
+            if payment.status != PaymentStatus::Held {
+                continue;
+            }
+            match settle_cancellation_share(&env, &mut payment, withdrawable_ratio_bps) {
+                Ok(share) if share > 0 => refunded_count += 1,
+                Ok(_) => {}
+                Err(PaymentError::TransferFailed) => {
+                    events::emit_cancellation_refund_skipped(&env, &event_id, payment_id);
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        if end > persisted {
+            storage::set_cancellation_refund_cursor(&env, &event_id, end);
+        }
 
-        let token_revenue =
-            storage::get_event_token_revenue(&env, &payment.event_id, &payment.token);
-        storage::set_event_token_revenue(
+        events::emit_cancellation_refunds_processed(
             &env,
-            &payment.event_id,
-            &payment.token,
-            token_revenue - remaining,
+            event_id,
+            start,
+            end.max(start),
+            count,
+            refunded_count,
         );
-        storage::add_total_refunds(&env, &payment.event_id, remaining);
-
-        // The refund event derives its masked identity from the stored payment,
-        // preserving the original privacy level.
-        events::emit_payment_refunded(&env, &payment, remaining);
-
-        Ok(())
+        Ok(RefundProgress {
+            refunded_count,
+            next_cursor: if end < count { Some(end) } else { None },
+        })
     }
     pub fn postpone_event(
         env: Env,
@@ -1318,9 +3179,10 @@ impl PaymentsContract {
         let event_contract = storage::get_event_contract(&env)?;
         event_contract.require_auth();
 
-        let ticket = storage::get_ticket(&env, ticket_id)?;
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
         // Only Standard tickets are address-owned and refundable on-chain.
-        let ticket_owner = ticket.owner.clone().ok_or(PaymentError::RefundNotAllowed)?;
+        let ticket_owner =
+            resolve_ticket_owner(&env, &mut ticket).ok_or(PaymentError::RefundNotAllowed)?;
         if ticket_owner != caller {
             return Err(PaymentError::Unauthorized);
         }
@@ -1363,9 +3225,6 @@ impl PaymentsContract {
         payment.status = PaymentStatus::Refunded;
         storage::update_payment(&env, &payment)?;
 
-        let revenue = storage::get_event_revenue(&env, &payment.event_id);
-        storage::set_event_revenue(&env, &payment.event_id, revenue - refund_amt);
-
         let token_revenue =
             storage::get_event_token_revenue(&env, &payment.event_id, &payment.token);
         storage::set_event_token_revenue(
@@ -1382,6 +3241,100 @@ impl PaymentsContract {
 
         Ok(())
     }
+    /// Refund up to `amount` of what was paid for a ticket-contract ticket.
+    ///
+    /// Called by the event contract when an organizer or admin revokes a
+    /// ticket, or when its holder moves to a cheaper tier. Upgrade top-ups are
+    /// returned newest first, then the original purchase; the amount actually
+    /// refunded is returned. Standard payers are refunded directly; for
+    /// Private/Anonymous payments, which store no address, the amount is set
+    /// aside for `claim_refund_voucher`.
+    pub fn refund_minted_ticket(
+        env: Env,
+        minted_ticket_id: u64,
+        amount: i128,
+    ) -> Result<i128, PaymentError> {
+        require_not_paused(&env)?;
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        if amount <= 0 {
+            return Err(PaymentError::InvalidAmount);
+        }
+        let payment_id = storage::get_minted_ticket_payment(&env, minted_ticket_id)
+            .ok_or(PaymentError::TicketNotFound)?;
+        let mut payment_ids = storage::get_ticket_upgrade_payments(&env, minted_ticket_id);
+        payment_ids.push_front(payment_id);
+
+        let mut remaining = amount;
+        for payment_id in payment_ids.iter().rev() {
+            if remaining == 0 {
+                break;
+            }
+            remaining -= refund_payment_share(&env, payment_id, remaining)?;
+        }
+
+        Ok(amount - remaining)
+    }
+
+    pub fn get_claimable_refund(env: Env, payment_id: u64) -> i128 {
+        storage::get_claimable_refund(&env, payment_id)
+    }
+
+    /// Claim the refund voucher of the Private/Anonymous payment behind
+    /// ledger ticket `ticket_id` to any `recipient`. On a cancelled event the
+    /// payment's refundable share is credited to the voucher first, so one
+    /// call settles it.
+    ///
+    /// Only the ticket's current holder can claim, so after a private resale
+    /// the refund goes to the buyer. The proof authorizes
+    /// `refund_claim_message(ticket_id, recipient)` as described for
+    /// `verify_shielded_owner`: a stealth-key signature, or a commit-reveal of
+    /// the nullifier preimage. Either binds the payout to `recipient`.
+    pub fn claim_refund_voucher(
+        env: Env,
+        ticket_id: u64,
+        recipient: Address,
+        proof: Bytes,
+    ) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        recipient.require_auth();
+
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.privacy_level == PaymentPrivacy::Standard {
+            return Err(PaymentError::RefundNotAllowed);
+        }
+        let message = build_refund_claim_message(&env, ticket_id, &recipient);
+        verify_shielded_owner(&env, &mut ticket, &message, proof)?;
+        let payment_id = ticket.payment_id;
+        let mut payment = storage::get_payment(&env, payment_id)?;
+
+        if payment.status == PaymentStatus::Held
+            && storage::get_event_status(&env, &payment.event_id) == Some(EventStatus::Cancelled)
+        {
+            let withdrawable_ratio_bps = storage::get_event_config(&env, &payment.event_id)
+                .and_then(|c| c.withdrawable_ratio_bps)
+                .unwrap_or(0);
+            settle_cancellation_share(&env, &mut payment, withdrawable_ratio_bps)?;
+        }
+
+        let amount = storage::get_claimable_refund(&env, payment_id);
+        if amount <= 0 {
+            return Err(PaymentError::NoClaimableRefund);
+        }
+        storage::remove_claimable_refund(&env, payment_id);
+        let token_client = token::Client::new(&env, &payment.token);
+        token_client.transfer(&env.current_contract_address(), &recipient, &amount);
+
+        events::emit_payment_refunded(&env, &payment, amount);
+
+        Ok(())
+    }
+
+    /// Message a ticket holder authorizes to claim a refund voucher.
+    pub fn refund_claim_message(env: Env, ticket_id: u64, recipient: Address) -> Bytes {
+        build_refund_claim_message(&env, ticket_id, &recipient)
+    }
     pub fn set_event_end_time(
         env: Env,
         admin: Address,
@@ -1407,6 +3360,7 @@ impl PaymentsContract {
     pub fn release_if_expired(env: Env, event_id: Symbol) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
         ensure_no_splits(&env, &event_id)?;
+        ensure_not_vesting(&env, &event_id)?;
         let mut meta = storage::get_escrow_meta(&env, &event_id)?;
 
         if meta.auto_released {
@@ -1444,13 +3398,6 @@ impl PaymentsContract {
 
                     storage::set_event_token_revenue(&env, &event_id, &token_address, 0);
 
-                    let current_event_revenue = storage::get_event_revenue(&env, &event_id);
-                    storage::set_event_revenue(
-                        &env,
-                        &event_id,
-                        current_event_revenue - token_total,
-                    );
-
                     let record = WithdrawalRecord {
                         amount: token_total, // no fee in auto_release?
                         timestamp: env.ledger().timestamp(),
@@ -1485,6 +3432,7 @@ impl PaymentsContract {
         let admin = storage::get_admin(&env)?;
         admin.require_auth();
         ensure_no_splits(&env, &event_id)?;
+        ensure_not_vesting(&env, &event_id)?;
 
         // Escrow is frozen while the event is postponed (refund-choice window open).
         if storage::get_event_status(&env, &event_id) == Some(EventStatus::Postponed) {
@@ -1512,14 +3460,16 @@ impl PaymentsContract {
         if revenue <= 0 {
             return Err(PaymentError::InvalidAmount);
         }
-        let fee_bps = storage::get_platform_fee_bps(&env) as i128;
-        let fee_amount = revenue * fee_bps / 10_000;
+        let fee_amount = match &config {
+            Some(config) => primary_platform_fee(&env, &event_id, config, &token_address, revenue),
+            None => revenue * storage::get_platform_fee_bps(&env) as i128 / 10_000,
+        };
         let organizer_amount = revenue - fee_amount;
 
         let token_client = token::Client::new(&env, &token_address);
         token_client.transfer(&env.current_contract_address(), &to, &organizer_amount);
         if fee_amount > 0 {
-            storage::add_platform_revenue(&env, &event_id, fee_amount);
+            book_platform_fee(&env, &event_id, &token_address, fee_amount);
             events::emit_platform_fee_collected(
                 &env,
                 event_id.clone(),
@@ -1529,8 +3479,6 @@ impl PaymentsContract {
             );
         }
         storage::set_event_token_revenue(&env, &event_id, &token_address, 0);
-        let current_event_revenue = storage::get_event_revenue(&env, &event_id);
-        storage::set_event_revenue(&env, &event_id, current_event_revenue - revenue);
 
         storage::add_total_withdrawn(&env, &event_id, organizer_amount);
 
@@ -1565,6 +3513,242 @@ impl PaymentsContract {
     ) -> soroban_sdk::Vec<WithdrawalRecord> {
         storage::get_withdrawal_history(&env, &event_id)
     }
+
+    /// Replace lump-sum organizer withdrawal with a vesting schedule, or
+    /// clear it with `None`. Organizers may only choose a schedule before
+    /// the first sale; the admin may change it at any time.
+    pub fn set_vesting_schedule(
+        env: Env,
+        caller: Address,
+        event_id: Symbol,
+        vesting: Option<VestingSchedule>,
+    ) -> Result<(), PaymentError> {
+        caller.require_auth();
+        ensure_no_splits(&env, &event_id)?;
+
+        let config =
+            storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        let is_admin = caller == storage::get_admin(&env)?;
+        if !is_admin && (caller != config.organizer || config.sold_count > 0) {
+            return Err(PaymentError::Unauthorized);
+        }
+        if let Some(vesting) = &vesting {
+            if vesting.release_bps > 10_000
+                || (vesting.release_bps < 10_000 && vesting.vesting_ledgers == 0)
+            {
+                return Err(PaymentError::InvalidAmount);
+            }
+        }
+        storage::set_vesting_schedule(&env, &event_id, &vesting);
+        Ok(())
+    }
+
+    pub fn get_vesting_schedule(env: Env, event_id: Symbol) -> Option<VestingSchedule> {
+        storage::get_vesting_schedule(&env, &event_id)
+    }
+
+    /// Pay the organizer everything vested so far, in every token the event
+    /// took, less the platform fee. Vesting starts at `event_end_ledger`.
+    pub fn claim_vested(env: Env, event_id: Symbol) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        ensure_no_splits(&env, &event_id)?;
+
+        let config =
+            storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        config.organizer.require_auth();
+        let vesting = storage::get_vesting_schedule(&env, &event_id)
+            .ok_or(PaymentError::VestingNotConfigured)?;
+        if storage::get_event_status(&env, &event_id) != Some(EventStatus::Completed) {
+            return Err(PaymentError::EventNotCompleted);
+        }
+        if env.ledger().sequence() < config.event_end_ledger {
+            return Err(PaymentError::EscrowNotExpired);
+        }
+
+        validate_revenue_invariant(&env, &event_id)?;
+
+        let mut claimed_any = false;
+        for token_address in storage::get_event_tokens(&env, &event_id).iter() {
+            let amount = claimable_vested(&env, &event_id, &config, &vesting, &token_address);
+            if amount <= 0 {
+                continue;
+            }
+            claimed_any = true;
+
+            let fee_amount = primary_platform_fee(&env, &event_id, &config, &token_address, amount);
+            let organizer_amount = amount - fee_amount;
+            token::Client::new(&env, &token_address).transfer(
+                &env.current_contract_address(),
+                &config.organizer,
+                &organizer_amount,
+            );
+            if fee_amount > 0 {
+                book_platform_fee(&env, &event_id, &token_address, fee_amount);
+                events::emit_platform_fee_collected(
+                    &env,
+                    event_id.clone(),
+                    fee_amount,
+                    organizer_amount,
+                    token_address.clone(),
+                );
+            }
+
+            let token_revenue = storage::get_event_token_revenue(&env, &event_id, &token_address);
+            storage::set_event_token_revenue(
+                &env,
+                &event_id,
+                &token_address,
+                token_revenue - amount,
+            );
+            storage::add_vested_claimed(&env, &event_id, &token_address, amount);
+            storage::add_total_withdrawn(&env, &event_id, organizer_amount);
+
+            storage::add_withdrawal_record(
+                &env,
+                &event_id,
+                &WithdrawalRecord {
+                    amount: organizer_amount,
+                    timestamp: env.ledger().timestamp(),
+                    organizer: config.organizer.clone(),
+                },
+            );
+            events::emit_revenue_withdrawn(
+                &env,
+                event_id.clone(),
+                config.organizer.clone(),
+                organizer_amount,
+                token_address,
+                config.organizer.clone(),
+                &storage::get_emission_privacy(&env, &event_id),
+            );
+        }
+
+        if !claimed_any {
+            return Err(PaymentError::NoRevenue);
+        }
+        Ok(())
+    }
+
+    /// Gross `token_address` revenue `claim_vested` would release now.
+    pub fn get_claimable_vested(env: Env, event_id: Symbol, token_address: Address) -> i128 {
+        match (
+            storage::get_event_config(&env, &event_id),
+            storage::get_vesting_schedule(&env, &event_id),
+        ) {
+            (Some(config), Some(vesting)) => {
+                claimable_vested(&env, &event_id, &config, &vesting, &token_address)
+            }
+            _ => 0,
+        }
+    }
+    /// Set the bond organizers lock at event creation, in basis points of the
+    /// event's face value, and the approved-dispute share that slashes it.
+    pub fn set_bond_policy(
+        env: Env,
+        bond_bps: u32,
+        slash_threshold_bps: u32,
+    ) -> Result<(), PaymentError> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        if bond_bps > 10_000 || slash_threshold_bps > 10_000 {
+            return Err(PaymentError::InvalidAmount);
+        }
+        storage::set_bond_policy(
+            &env,
+            &BondPolicy {
+                bond_bps,
+                slash_threshold_bps,
+            },
+        );
+        Ok(())
+    }
+    pub fn get_bond_policy(env: Env) -> BondPolicy {
+        storage::get_bond_policy(&env)
+    }
+
+    /// Lock the organizer's bond for a newly created event. `face_value` is
+    /// the sum of capacity × price over the event's tiers; the bond is the
+    /// policy share of it, paid in the payout token. Returns the amount locked,
+    /// which is zero while bonds are disabled.
+    pub fn lock_event_bond(
+        env: Env,
+        event_id: Symbol,
+        face_value: i128,
+    ) -> Result<i128, PaymentError> {
+        require_not_paused(&env)?;
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+        if face_value < 0 {
+            return Err(PaymentError::InvalidAmount);
+        }
+
+        let config =
+            storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        if storage::get_event_bond(&env, &event_id).status != BondStatus::None {
+            return Err(PaymentError::BondAlreadyLocked);
+        }
+        let policy = storage::get_bond_policy(&env);
+        let amount = face_value
+            .checked_mul(policy.bond_bps as i128)
+            .ok_or(PaymentError::InvalidAmount)?
+            / 10_000;
+        if amount == 0 {
+            return Ok(0);
+        }
+
+        config.organizer.require_auth();
+        let token_client = token::Client::new(&env, &config.payout_token);
+        token_client.transfer(&config.organizer, env.current_contract_address(), &amount);
+        storage::set_event_bond(
+            &env,
+            &event_id,
+            &EventBond {
+                amount,
+                slashed: 0,
+                slash_threshold_bps: policy.slash_threshold_bps,
+                approved_disputes: 0,
+                status: BondStatus::Locked,
+            },
+        );
+
+        events::emit_bond_locked(&env, event_id, config.organizer, amount);
+        Ok(amount)
+    }
+
+    /// Return whatever is left of the bond to the organizer once the attendee
+    /// dispute window has closed and every open dispute has been resolved.
+    pub fn return_bond(env: Env, event_id: Symbol) -> Result<i128, PaymentError> {
+        require_not_paused(&env)?;
+        let config =
+            storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        let mut bond = storage::get_event_bond(&env, &event_id);
+        if bond.status != BondStatus::Locked && bond.status != BondStatus::Slashed {
+            return Err(PaymentError::BondNotReturnable);
+        }
+        if env.ledger().sequence()
+            < config
+                .event_end_ledger
+                .saturating_add(ATTENDEE_DISPUTE_WINDOW_LEDGERS)
+        {
+            return Err(PaymentError::BondNotReturnable);
+        }
+        process_timed_out_disputes(&env, &event_id)?;
+        if !storage::get_event_disputes(&env, &event_id).is_empty() {
+            return Err(PaymentError::ActiveDisputes);
+        }
+
+        let amount = bond.amount - bond.slashed;
+        if amount > 0 {
+            let token_client = token::Client::new(&env, &config.payout_token);
+            token_client.transfer(&env.current_contract_address(), &config.organizer, &amount);
+        }
+        bond.status = BondStatus::Returned;
+        storage::set_event_bond(&env, &event_id, &bond);
+
+        events::emit_bond_returned(&env, event_id, config.organizer, amount);
+        Ok(amount)
+    }
+
     pub fn set_platform_fee(env: Env, fee_bps: u32, wallet: Address) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
         let admin = storage::get_admin(&env)?;
@@ -1577,6 +3761,10 @@ impl PaymentsContract {
         let old_bps = storage::get_platform_fee_bps(&env);
         storage::set_platform_fee_bps(&env, fee_bps);
         storage::set_platform_wallet(&env, &wallet);
+        if let Some(mut schedule) = storage::get_fee_schedule(&env) {
+            schedule.primary_bps = fee_bps;
+            storage::set_fee_schedule(&env, &schedule);
+        }
 
         events::emit_platform_fee_updated(&env, admin, old_bps, fee_bps);
 
@@ -1585,40 +3773,169 @@ impl PaymentsContract {
     pub fn get_platform_fee_bps(env: Env) -> u32 {
         storage::get_platform_fee_bps(&env)
     }
+
+    /// Replace the platform fee schedule. Only events created afterwards use
+    /// it; `get_platform_fee_bps` follows its primary rate.
+    pub fn set_fee_schedule(env: Env, schedule: FeeSchedule) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        validate_fee_schedule(&schedule)?;
+
+        let old_bps = storage::get_platform_fee_bps(&env);
+        storage::set_platform_fee_bps(&env, schedule.primary_bps);
+        storage::set_fee_schedule(&env, &schedule);
+
+        events::emit_platform_fee_updated(&env, admin, old_bps, schedule.primary_bps);
+        Ok(())
+    }
+    pub fn get_fee_schedule(env: Env) -> FeeSchedule {
+        storage::get_fee_schedule(&env).unwrap_or_else(|| {
+            let bps = storage::get_platform_fee_bps(&env);
+            FeeSchedule {
+                primary_bps: bps,
+                resale_bps: bps,
+                min_fee: 0,
+                max_fee: 0,
+                tiers: Vec::new(&env),
+            }
+        })
+    }
+
+    /// Give `organizer` its own terms for the events it creates from now on;
+    /// `None` returns it to the platform schedule.
+    pub fn set_organizer_fee_schedule(
+        env: Env,
+        organizer: Address,
+        schedule: Option<FeeSchedule>,
+    ) -> Result<(), PaymentError> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        if let Some(schedule) = &schedule {
+            validate_fee_schedule(schedule)?;
+        }
+        storage::set_organizer_fee_schedule(&env, &organizer, &schedule);
+        Ok(())
+    }
+    pub fn get_organizer_fee_schedule(env: Env, organizer: Address) -> Option<FeeSchedule> {
+        storage::get_organizer_fee_schedule(&env, &organizer)
+    }
+
+    /// Override the terms an event snapshotted at creation.
+    pub fn set_event_fee_schedule(
+        env: Env,
+        event_id: Symbol,
+        schedule: FeeSchedule,
+    ) -> Result<(), PaymentError> {
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
+        if storage::get_event_config(&env, &event_id).is_none() {
+            return Err(PaymentError::InvalidOrganizer);
+        }
+        validate_fee_schedule(&schedule)?;
+        storage::set_event_fee_schedule(&env, &event_id, &schedule);
+        Ok(())
+    }
+    pub fn get_event_fee_schedule(env: Env, event_id: Symbol) -> Result<FeeSchedule, PaymentError> {
+        let config =
+            storage::get_event_config(&env, &event_id).ok_or(PaymentError::InvalidOrganizer)?;
+        Ok(event_fee_schedule(&env, &event_id, &config))
+    }
     pub fn get_platform_revenue(env: Env, event_id: Symbol) -> i128 {
         storage::get_platform_revenue(&env, &event_id)
     }
+    /// Platform fees an event holds in `token_address` other than the
+    /// accepted token, which `get_platform_revenue` reports.
+    pub fn get_platform_token_revenue(env: Env, event_id: Symbol, token_address: Address) -> i128 {
+        storage::get_platform_token_revenue(&env, &event_id, &token_address)
+    }
+    /// Pay the event's platform fees to the platform wallet, in the accepted
+    /// token and in every other token resales were settled in.
     pub fn withdraw_platform_revenue(env: Env, event_id: Symbol) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
         let admin = storage::get_admin(&env)?;
         admin.require_auth();
 
-        let platform_revenue = storage::get_platform_revenue(&env, &event_id);
-        if platform_revenue <= 0 {
+        let platform_wallet = storage::get_platform_wallet(&env)?;
+        let accepted_token = storage::get_accepted_token(&env)?;
+        let mut tokens = Vec::new(&env);
+        tokens.push_back(accepted_token.clone());
+        for token_address in storage::get_event_tokens(&env, &event_id).iter() {
+            if token_address != accepted_token {
+                tokens.push_back(token_address);
+            }
+        }
+
+        let mut withdrawn = false;
+        for token_address in tokens.iter() {
+            count_unindexed_platform_fees(&env, &event_id, &token_address);
+            let amount = take_platform_fees(&env, &event_id, &token_address);
+            if amount <= 0 {
+                continue;
+            }
+            let token_client = token::Client::new(&env, &token_address);
+            token_client.transfer(&env.current_contract_address(), &platform_wallet, &amount);
+            record_platform_payout(&env, &token_address, amount);
+            events::emit_platform_revenue_withdrawn(
+                &env,
+                event_id.clone(),
+                amount,
+                token_address,
+                platform_wallet.clone(),
+            );
+            withdrawn = true;
+        }
+
+        if !withdrawn {
             return Err(PaymentError::NoPlatformRevenue);
         }
+        Ok(())
+    }
 
+    /// Pay the platform wallet every event's fees in `token`, walking the
+    /// events that have booked fees in it from `cursor`. `limit` is capped at
+    /// `MAX_LISTING_PAGE_SIZE`; the fees of the whole page move in a single
+    /// transfer.
+    pub fn sweep_platform_revenue(
+        env: Env,
+        token: Address,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<PlatformSweep, PaymentError> {
+        require_not_paused(&env)?;
+        let admin = storage::get_admin(&env)?;
+        admin.require_auth();
         let platform_wallet = storage::get_platform_wallet(&env)?;
-        let token_address = storage::get_accepted_token(&env)?;
-        let token_client = token::Client::new(&env, &token_address);
-
-        token_client.transfer(
-            &env.current_contract_address(),
-            &platform_wallet,
-            &platform_revenue,
-        );
 
-        storage::reset_platform_revenue(&env, &event_id);
+        let count = storage::get_platform_fee_event_count(&env, &token);
+        let end = cursor.saturating_add(listing_page_limit(limit)).min(count);
+        let mut amount = 0i128;
+        for index in cursor..end {
+            if let Some(event_id) = storage::get_platform_fee_event(&env, &token, index) {
+                amount += take_platform_fees(&env, &event_id, &token);
+            }
+        }
 
-        events::emit_platform_revenue_withdrawn(
-            &env,
-            event_id,
-            platform_revenue,
-            token_address,
-            platform_wallet,
-        );
+        if amount > 0 {
+            let token_client = token::Client::new(&env, &token);
+            token_client.transfer(&env.current_contract_address(), &platform_wallet, &amount);
+            record_platform_payout(&env, &token, amount);
+            events::emit_platform_revenue_swept(&env, token.clone(), amount, platform_wallet);
+        }
+        Ok(PlatformSweep {
+            token,
+            amount,
+            next_cursor: if end < count { Some(end) } else { None },
+        })
+    }
 
-        Ok(())
+    /// Platform fees owed and withdrawn, per token, across all events.
+    pub fn get_platform_totals(env: Env) -> Vec<PlatformTotal> {
+        let mut totals = Vec::new(&env);
+        for token in storage::get_platform_tokens(&env).iter() {
+            totals.push_back(storage::get_platform_total(&env, &token));
+        }
+        totals
     }
     pub fn set_event_privacy(
         env: Env,
@@ -1670,6 +3987,38 @@ impl PaymentsContract {
         Ok(new_version)
     }
 
+    /// Count platform fees events booked before the sweep index existed, so
+    /// `sweep_platform_revenue` reaches them and `get_platform_totals`
+    /// includes them. Returns how many event and token pairs were counted.
+    pub fn migrate_platform_fees(
+        env: Env,
+        admin: Address,
+        event_ids: Vec<Symbol>,
+    ) -> Result<u32, PaymentError> {
+        admin.require_auth();
+        if storage::get_admin(&env)? != admin {
+            return Err(PaymentError::Unauthorized);
+        }
+        if event_ids.len() > MAX_MIGRATION_BATCH {
+            return Err(PaymentError::BatchTooLarge);
+        }
+
+        let accepted_token = storage::get_accepted_token(&env)?;
+        let mut counted = 0;
+        for event_id in event_ids.iter() {
+            if count_unindexed_platform_fees(&env, &event_id, &accepted_token) {
+                counted += 1;
+            }
+            for token in storage::get_event_tokens(&env, &event_id).iter() {
+                if token != accepted_token && count_unindexed_platform_fees(&env, &event_id, &token)
+                {
+                    counted += 1;
+                }
+            }
+        }
+        Ok(counted)
+    }
+
     pub fn migrate_event(env: Env, admin: Address, event_id: Symbol) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
         admin.require_auth();
@@ -1754,6 +4103,7 @@ impl PaymentsContract {
         require_not_paused(&env)?;
         organizer.require_auth();
         ensure_no_splits(&env, &event_id)?;
+        ensure_not_vesting(&env, &event_id)?;
 
         match storage::get_event_status(&env, &event_id) {
             Some(EventStatus::Completed) => {}
@@ -1774,9 +4124,6 @@ impl PaymentsContract {
 
         storage::set_event_token_revenue(&env, &event_id, &token_address, 0);
 
-        let current_event_revenue = storage::get_event_revenue(&env, &event_id);
-        storage::set_event_revenue(&env, &event_id, current_event_revenue - total);
-
         storage::add_total_withdrawn(&env, &event_id, total);
 
         let record = WithdrawalRecord {
@@ -1806,6 +4153,7 @@ impl PaymentsContract {
         require_not_paused(&env)?;
         organizer.require_auth();
         ensure_no_splits(&env, &event_id)?;
+        ensure_not_vesting(&env, &event_id)?;
 
         match storage::get_event_status(&env, &event_id) {
             Some(EventStatus::Completed) => {}
@@ -1832,9 +4180,6 @@ impl PaymentsContract {
                 storage::set_event_token_revenue(&env, &event_id, &token_address, 0);
                 storage::add_total_withdrawn(&env, &event_id, total);
 
-                let current_event_revenue = storage::get_event_revenue(&env, &event_id);
-                storage::set_event_revenue(&env, &event_id, current_event_revenue - total);
-
                 let record = WithdrawalRecord {
                     amount: total,
                     timestamp: env.ledger().timestamp(),
@@ -2192,140 +4537,492 @@ impl PaymentsContract {
         Ok(())
     }
 
+    /// List a ticket at a fixed price in `token`, which must be one the
+    /// event takes; `None` prices it in the event's payout token.
     pub fn list_ticket_for_resale(
         env: Env,
         seller: Address,
         ticket_id: u64,
         price: i128,
+        expires_at_ledger: Option<u32>,
+        token: Option<Address>,
+    ) -> Result<(), PaymentError> {
+        create_listing(
+            &env,
+            seller,
+            ticket_id,
+            ListingKind::FixedPrice,
+            price,
+            0,
+            expires_at_ledger,
+            token,
+        )
+    }
+
+    /// List a ticket for auction. English auctions open at `start_price` and
+    /// sell to the highest bid at or above `reserve_price` once the listing
+    /// expires; Dutch auctions fall from `start_price` to `reserve_price` by
+    /// `expires_at_ledger` and sell to the first buyer. Bids are made in
+    /// the listing's `token`, as for `list_ticket_for_resale`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn list_ticket_for_auction(
+        env: Env,
+        seller: Address,
+        ticket_id: u64,
+        kind: ListingKind,
+        start_price: i128,
+        reserve_price: i128,
+        expires_at_ledger: u32,
+        token: Option<Address>,
     ) -> Result<(), PaymentError> {
+        if kind == ListingKind::FixedPrice {
+            return Err(PaymentError::ListingKindMismatch);
+        }
+        create_listing(
+            &env,
+            seller,
+            ticket_id,
+            kind,
+            start_price,
+            reserve_price,
+            Some(expires_at_ledger),
+            token,
+        )
+    }
+
+    pub fn delist_ticket(env: Env, seller: Address, ticket_id: u64) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
         seller.require_auth();
 
-        let ticket = storage::get_ticket(&env, ticket_id)?;
-        // Only Standard tickets are address-owned; Anonymous/Private tickets are
-        // not resale-eligible through this address-based path.
-        if ticket.owner.as_ref() != Some(&seller) {
+        let listing =
+            storage::get_resale_listing(&env, ticket_id).ok_or(PaymentError::TicketNotFound)?;
+        if listing.seller != seller {
+            return Err(PaymentError::Unauthorized);
+        }
+        // Bidders' funds are committed until the auction settles.
+        if listing.highest_bidder.is_some() {
+            return Err(PaymentError::AuctionHasBids);
+        }
+        // Retire the signature that opened a shielded listing.
+        if storage::get_ticket(&env, ticket_id)?.privacy_level != PaymentPrivacy::Standard {
+            storage::increment_private_resale_nonce(&env, ticket_id);
+        }
+
+        storage::remove_resale_listing(&env, ticket_id);
+        Ok(())
+    }
+
+    /// Buy a fixed-price listing, or a Dutch auction at its current price.
+    pub fn buy_resale_ticket(env: Env, buyer: Address, ticket_id: u64) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        buyer.require_auth();
+
+        let listing =
+            storage::get_resale_listing(&env, ticket_id).ok_or(PaymentError::TicketNotFound)?;
+        if listing.kind == ListingKind::EnglishAuction {
+            return Err(PaymentError::ListingKindMismatch);
+        }
+        if listing_expired(&env, &listing) {
+            return Err(PaymentError::ListingExpired);
+        }
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.privacy_level != PaymentPrivacy::Standard {
+            return Err(PaymentError::PrivacyLevelMismatch);
+        }
+
+        if resolve_ticket_owner(&env, &mut ticket).as_ref() != Some(&listing.seller) {
+            storage::remove_resale_listing(&env, ticket_id);
             return Err(PaymentError::Unauthorized);
         }
 
         let config = storage::get_event_config(&env, &ticket.event_id)
             .ok_or(PaymentError::InvalidOrganizer)?;
-        let payment = storage::get_payment(&env, ticket.payment_id)?;
+        let price = listing_price(&env, &listing);
+        check_resale_cap(&config, &listing.token, price)?;
 
-        if payment.amount == 0 && price > 0 && !config.allow_free_ticket_transfer {
-            return Err(PaymentError::InvalidAmount);
+        if price > 0 {
+            let token_client = token::Client::new(&env, &listing.token);
+            token_client.transfer(&buyer, env.current_contract_address(), &price);
         }
+        settle_resale(
+            &env,
+            &mut ticket,
+            &config,
+            &listing.seller,
+            &buyer,
+            &listing.token,
+            price,
+        )
+    }
 
-        if !config.allow_free_ticket_transfer && payment.amount == 0 {
-            return Err(PaymentError::InvalidAmount);
+    /// List a Private or Anonymous ticket at a fixed price without revealing
+    /// its holder. Ownership is proven with `proof` over
+    /// `private_resale_message`: a Private holder signs it with the ticket's
+    /// stealth key, an Anonymous holder reveals the commitment preimage
+    /// against an earlier `commit_shielded_action` (see
+    /// `commit_shielded_action` for the format). Proceeds go to `payout`,
+    /// which also controls the listing from then on (e.g. `delist_ticket`).
+    pub fn list_private_ticket_for_resale(
+        env: Env,
+        ticket_id: u64,
+        payout: Address,
+        price: i128,
+        expires_at_ledger: Option<u32>,
+        token: Option<Address>,
+        proof: Bytes,
+    ) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        payout.require_auth();
+
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        let message = private_resale_message(&env, ticket_id, &payout, price, &token);
+        verify_shielded_owner(&env, &mut ticket, &message, proof)?;
+        storage::increment_private_resale_nonce(&env, ticket_id);
+        // An open listing is controlled by its payout address; the holder
+        // delists it before listing on other terms.
+        if storage::get_resale_listing(&env, ticket_id).is_some() {
+            return Err(PaymentError::TicketAlreadyListed);
         }
 
-        if payment.amount == 0 && price > 0 {
-            return Err(PaymentError::InvalidAmount);
+        open_listing(
+            &env,
+            &ticket,
+            payout,
+            ListingKind::FixedPrice,
+            price,
+            0,
+            expires_at_ledger,
+            token,
+        )
+    }
+
+    /// Post the commitment for an Anonymous holder's next shielded action.
+    /// `commit` is `sha256(proof ‖ message)`, where `proof` is
+    /// `next_commitment ‖ preimage` and `message` is the action's message
+    /// (e.g. `private_resale_message`). The action can be taken from the next
+    /// ledger on; each commitment authorizes one action.
+    pub fn commit_shielded_action(env: Env, commit: BytesN<32>) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        // Keep the earliest ledger so re-posting cannot delay a reveal.
+        if storage::get_shielded_commit(&env, &commit).is_none() {
+            storage::set_shielded_commit(&env, &commit, env.ledger().sequence());
         }
+        Ok(())
+    }
 
-        if let Some(max_price) = config.max_resale_price {
-            if price > max_price {
-                return Err(PaymentError::InvalidAmount);
+    /// Bytes a Private holder signs to list `ticket_id` for `price` in
+    /// `token` with proceeds paid to `payout`. The message changes after
+    /// every private listing or delisting of the ticket.
+    pub fn private_resale_message(
+        env: Env,
+        ticket_id: u64,
+        payout: Address,
+        price: i128,
+        token: Option<Address>,
+    ) -> Bytes {
+        private_resale_message(&env, ticket_id, &payout, price, &token)
+    }
+
+    /// Buy a Private or Anonymous ticket listing. `delivery_key` becomes the
+    /// ticket's new identity: a fresh stealth key for Private tickets, a fresh
+    /// nullifier commitment for Anonymous ones. The seller's key or preimage
+    /// stops proving ownership.
+    pub fn buy_private_resale_ticket(
+        env: Env,
+        buyer: Address,
+        ticket_id: u64,
+        delivery_key: BytesN<32>,
+    ) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        buyer.require_auth();
+
+        let listing =
+            storage::get_resale_listing(&env, ticket_id).ok_or(PaymentError::TicketNotFound)?;
+        if listing.kind != ListingKind::FixedPrice {
+            return Err(PaymentError::ListingKindMismatch);
+        }
+        if listing_expired(&env, &listing) {
+            return Err(PaymentError::ListingExpired);
+        }
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        match ticket.privacy_level {
+            PaymentPrivacy::Private => {
+                ticket.hashed_owner = Some(salted_wallet_hash(&env, &buyer, &delivery_key));
+                storage::set_ticket_stealth_key(&env, ticket_id, &delivery_key);
             }
+            PaymentPrivacy::Anonymous => {
+                if storage::has_nullifier(&env, &delivery_key) {
+                    return Err(PaymentError::DuplicateRequest);
+                }
+                storage::mark_nullifier_spent(&env, &delivery_key);
+                ticket.nullifier_commitment = Some(delivery_key);
+            }
+            PaymentPrivacy::Standard => return Err(PaymentError::PrivacyLevelMismatch),
         }
 
-        let listing = crate::types::ResaleListing {
-            price,
-            seller: seller.clone(),
-        };
-        storage::save_resale_listing(&env, ticket_id, &listing);
+        let config = storage::get_event_config(&env, &ticket.event_id)
+            .ok_or(PaymentError::InvalidOrganizer)?;
+        check_resale_cap(&config, &listing.token, listing.price)?;
+
+        if listing.price > 0 {
+            let token_client = token::Client::new(&env, &listing.token);
+            token_client.transfer(&buyer, env.current_contract_address(), &listing.price);
+        }
+        pay_resale_proceeds(
+            &env,
+            &ticket.event_id,
+            &config,
+            &listing.seller,
+            &listing.token,
+            listing.price,
+        );
+
+        storage::update_ticket(&env, &ticket);
+        storage::remove_resale_listing(&env, ticket_id);
+        events::emit_private_resale_sold(&env, &ticket, listing.price);
         Ok(())
     }
 
-    pub fn delist_ticket(env: Env, seller: Address, ticket_id: u64) -> Result<(), PaymentError> {
+    /// Bid on an English auction. The bid is escrowed and the previous
+    /// leader is refunded.
+    pub fn place_bid(
+        env: Env,
+        bidder: Address,
+        ticket_id: u64,
+        amount: i128,
+    ) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
-        seller.require_auth();
+        bidder.require_auth();
 
-        let listing =
+        let mut listing =
             storage::get_resale_listing(&env, ticket_id).ok_or(PaymentError::TicketNotFound)?;
-        if listing.seller != seller {
+        if listing.kind != ListingKind::EnglishAuction {
+            return Err(PaymentError::ListingKindMismatch);
+        }
+        if listing_expired(&env, &listing) {
+            return Err(PaymentError::ListingExpired);
+        }
+        if bidder == listing.seller {
             return Err(PaymentError::Unauthorized);
         }
+        if amount < listing.price
+            || (listing.highest_bidder.is_some() && amount <= listing.highest_bid)
+        {
+            return Err(PaymentError::BidTooLow);
+        }
+        let config = storage::get_event_config(&env, &listing.event_id)
+            .ok_or(PaymentError::InvalidOrganizer)?;
+        check_resale_cap(&config, &listing.token, amount)?;
+
+        let token_client = token::Client::new(&env, &listing.token);
+        token_client.transfer(&bidder, env.current_contract_address(), &amount);
+        if let Some(previous) = &listing.highest_bidder {
+            token_client.transfer(
+                &env.current_contract_address(),
+                previous,
+                &listing.highest_bid,
+            );
+        }
 
-        storage::remove_resale_listing(&env, ticket_id);
+        listing.highest_bidder = Some(bidder.clone());
+        listing.highest_bid = amount;
+        storage::set_resale_listing(&env, &listing);
+        events::emit_resale_bid_placed(&env, listing.event_id, ticket_id, bidder, amount);
         Ok(())
     }
 
-    pub fn buy_resale_ticket(env: Env, buyer: Address, ticket_id: u64) -> Result<(), PaymentError> {
+    /// Close an expired listing. An English auction whose leading bid meets
+    /// the reserve is sold to that bidder; otherwise any bid is refunded and
+    /// the listing is removed. Anyone may call this.
+    pub fn settle_listing(env: Env, ticket_id: u64) -> Result<(), PaymentError> {
         require_not_paused(&env)?;
-        buyer.require_auth();
 
         let listing =
             storage::get_resale_listing(&env, ticket_id).ok_or(PaymentError::TicketNotFound)?;
-        let ticket = storage::get_ticket(&env, ticket_id)?;
-
-        if ticket.owner.as_ref() != Some(&listing.seller) {
+        if !listing_expired(&env, &listing) {
+            return Err(PaymentError::ListingStillActive);
+        }
+        let Some(bidder) = listing.highest_bidder.clone() else {
             storage::remove_resale_listing(&env, ticket_id);
-            return Err(PaymentError::Unauthorized);
+            return Ok(());
+        };
+
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        let config = storage::get_event_config(&env, &listing.event_id)
+            .ok_or(PaymentError::InvalidOrganizer)?;
+        let sellable = listing.highest_bid >= listing.reserve_price
+            && check_resale_cap(&config, &listing.token, listing.highest_bid).is_ok()
+            && resolve_ticket_owner(&env, &mut ticket).as_ref() == Some(&listing.seller);
+        if sellable {
+            return settle_resale(
+                &env,
+                &mut ticket,
+                &config,
+                &listing.seller,
+                &bidder,
+                &listing.token,
+                listing.highest_bid,
+            );
         }
 
+        let token_client = token::Client::new(&env, &listing.token);
+        token_client.transfer(
+            &env.current_contract_address(),
+            &bidder,
+            &listing.highest_bid,
+        );
+        storage::remove_resale_listing(&env, ticket_id);
+        Ok(())
+    }
+
+    /// Offer to buy a ticket, listed or not. The amount is escrowed until the
+    /// offer is accepted or cancelled; a new offer replaces the buyer's
+    /// previous one. `token` works as in `list_ticket_for_resale`.
+    pub fn make_offer(
+        env: Env,
+        buyer: Address,
+        ticket_id: u64,
+        amount: i128,
+        expires_at_ledger: u32,
+        token: Option<Address>,
+    ) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        buyer.require_auth();
+
+        if amount <= 0 {
+            return Err(PaymentError::InvalidAmount);
+        }
+        if expires_at_ledger <= env.ledger().sequence() {
+            return Err(PaymentError::InvalidListingTerms);
+        }
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        let owner = resolve_ticket_owner(&env, &mut ticket).ok_or(PaymentError::Unauthorized)?;
+        if owner == buyer {
+            return Err(PaymentError::Unauthorized);
+        }
         let config = storage::get_event_config(&env, &ticket.event_id)
             .ok_or(PaymentError::InvalidOrganizer)?;
+        let token = resale_token(&env, &ticket.event_id, &config, token)?;
+        let payment = storage::get_payment(&env, ticket.payment_id)?;
+        check_resale_price(&config, &payment, &token, amount)?;
 
-        if listing.price > 0 {
-            let platform_fee_bps = storage::get_platform_fee_bps(&env) as i128;
-            let platform_fee = listing.price * platform_fee_bps / 10000;
-            let royalty = listing.price * (config.resale_royalty_bps as i128) / 10000;
-            let seller_proceeds = listing.price - platform_fee - royalty;
+        let previous = storage::get_resale_offer(&env, ticket_id, &buyer);
+        if previous.is_none()
+            && storage::get_ticket_offer_buyers(&env, ticket_id).len() >= MAX_OFFERS_PER_TICKET
+        {
+            make_room_for_offer(&env, &ticket.event_id, ticket_id, &token, amount)?;
+        }
 
-            let token_client = token::Client::new(&env, &config.payout_token);
-            token_client.transfer(&buyer, env.current_contract_address(), &listing.price);
+        token::Client::new(&env, &token).transfer(&buyer, env.current_contract_address(), &amount);
+        if let Some(previous) = previous {
+            token::Client::new(&env, &previous.token).transfer(
+                &env.current_contract_address(),
+                &buyer,
+                &previous.amount,
+            );
+        }
 
-            if seller_proceeds > 0 {
-                token_client.transfer(
-                    &env.current_contract_address(),
-                    &listing.seller,
-                    &seller_proceeds,
-                );
-            }
+        storage::set_resale_offer(
+            &env,
+            &ResaleOffer {
+                ticket_id,
+                buyer: buyer.clone(),
+                amount,
+                token,
+                expires_at_ledger,
+            },
+        );
+        events::emit_resale_offer_made(&env, ticket.event_id, ticket_id, buyer, amount);
+        Ok(())
+    }
 
-            if platform_fee > 0 {
-                storage::add_platform_revenue(&env, &ticket.event_id, platform_fee);
-            }
+    /// Withdraw an offer and recover the escrowed amount, expired or not.
+    pub fn cancel_offer(env: Env, buyer: Address, ticket_id: u64) -> Result<(), PaymentError> {
+        buyer.require_auth();
 
-            if royalty > 0 {
-                storage::add_event_revenue(&env, &ticket.event_id, royalty);
-                storage::add_event_token_revenue(
-                    &env,
-                    &ticket.event_id,
-                    &config.payout_token,
-                    royalty,
-                );
+        let offer = storage::get_resale_offer(&env, ticket_id, &buyer)
+            .ok_or(PaymentError::OfferNotFound)?;
+        storage::remove_resale_offer(&env, ticket_id, &buyer);
+        token::Client::new(&env, &offer.token).transfer(
+            &env.current_contract_address(),
+            &buyer,
+            &offer.amount,
+        );
+        Ok(())
+    }
+
+    /// Sell the ticket to `buyer` at their escrowed offer. Any open listing
+    /// is withdrawn, which an English auction with bids does not allow.
+    pub fn accept_offer(
+        env: Env,
+        seller: Address,
+        ticket_id: u64,
+        buyer: Address,
+    ) -> Result<(), PaymentError> {
+        require_not_paused(&env)?;
+        seller.require_auth();
+
+        let offer = storage::get_resale_offer(&env, ticket_id, &buyer)
+            .ok_or(PaymentError::OfferNotFound)?;
+        if offer.expires_at_ledger < env.ledger().sequence() {
+            return Err(PaymentError::OfferExpired);
+        }
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        if resolve_ticket_owner(&env, &mut ticket).as_ref() != Some(&seller) {
+            return Err(PaymentError::Unauthorized);
+        }
+        if let Some(listing) = storage::get_resale_listing(&env, ticket_id) {
+            if listing.highest_bidder.is_some() {
+                return Err(PaymentError::AuctionHasBids);
             }
         }
+        let config = storage::get_event_config(&env, &ticket.event_id)
+            .ok_or(PaymentError::InvalidOrganizer)?;
+        check_resale_cap(&config, &offer.token, offer.amount)?;
 
-        let ticket_contract = storage::get_ticket_contract(&env)?;
-        let _: () = env.invoke_contract(
-            &ticket_contract,
-            &soroban_sdk::Symbol::new(&env, "admin_transfer_ticket"),
-            soroban_sdk::vec![
-                &env,
-                env.current_contract_address().into_val(&env),
-                listing.seller.into_val(&env),
-                buyer.clone().into_val(&env),
-                ticket_id.into_val(&env)
-            ],
-        );
+        storage::remove_resale_offer(&env, ticket_id, &buyer);
+        settle_resale(
+            &env,
+            &mut ticket,
+            &config,
+            &seller,
+            &buyer,
+            &offer.token,
+            offer.amount,
+        )
+    }
 
-        let mut new_ticket = ticket.clone();
-        new_ticket.owner = Some(buyer.clone());
-        let key = crate::storage::DataKey::Ticket(ticket_id);
-        env.storage().persistent().set(&key, &new_ticket);
+    pub fn get_resale_listing(env: Env, ticket_id: u64) -> Option<ResaleListing> {
+        storage::get_resale_listing(&env, ticket_id)
+    }
+
+    /// Current asking price of a listing; for Dutch auctions this falls with
+    /// each ledger.
+    pub fn get_listing_price(env: Env, ticket_id: u64) -> Result<i128, PaymentError> {
+        let listing =
+            storage::get_resale_listing(&env, ticket_id).ok_or(PaymentError::TicketNotFound)?;
+        Ok(listing_price(&env, &listing))
+    }
 
-        // Remove from seller's ownership (map-based)
-        storage::remove_owner_ticket_map(&env, &listing.seller, ticket_id);
-        // Add to buyer's ownership (map-based)
-        storage::add_owner_ticket_map(&env, &buyer, ticket_id);
+    /// Page through an event's listings, expired ones included until they are
+    /// settled. `limit` is capped at `MAX_LISTING_PAGE_SIZE`.
+    pub fn get_event_listings(env: Env, event_id: Symbol, cursor: u64, limit: u32) -> ListingPage {
+        storage::get_event_listings_page(&env, &event_id, cursor, listing_page_limit(limit))
+    }
 
-        storage::remove_resale_listing(&env, ticket_id);
+    /// Page through a seller's listings; paging works as in `get_event_listings`.
+    pub fn get_seller_listings(env: Env, seller: Address, cursor: u64, limit: u32) -> ListingPage {
+        storage::get_seller_listings_page(&env, &seller, cursor, listing_page_limit(limit))
+    }
 
-        Ok(())
+    pub fn get_ticket_offers(env: Env, ticket_id: u64) -> Vec<ResaleOffer> {
+        let mut offers = Vec::new(&env);
+        for buyer in storage::get_ticket_offer_buyers(&env, ticket_id).iter() {
+            if let Some(offer) = storage::get_resale_offer(&env, ticket_id, &buyer) {
+                offers.push_back(offer);
+            }
+        }
+        offers
     }
 
     pub fn raise_dispute(
@@ -2339,34 +5036,16 @@ impl PaymentsContract {
             return Err(PaymentError::InvalidDisputeReason);
         }
 
-        let ticket = storage::get_ticket(&env, ticket_id)?;
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
         if ticket.privacy_level == PaymentPrivacy::Standard {
-            if let Some(ref owner) = ticket.owner {
+            if let Some(ref owner) = resolve_ticket_owner(&env, &mut ticket) {
                 owner.require_auth();
             } else {
                 return Err(PaymentError::Unauthorized);
             }
-        } else if ticket.privacy_level == PaymentPrivacy::Anonymous {
-            let preimage = proof.ok_or(PaymentError::Unauthorized)?;
-            let hash = env.crypto().sha256(&preimage);
-            if Some(hash.into()) != ticket.nullifier_commitment {
-                return Err(PaymentError::Unauthorized);
-            }
-        } else if ticket.privacy_level == PaymentPrivacy::Private {
-            let signature = proof.ok_or(PaymentError::Unauthorized)?;
-            let payment = storage::get_payment(&env, ticket.payment_id)?;
-            let pub_key = payment
-                .stealth_delivery_key
-                .ok_or(PaymentError::Unauthorized)?;
-            let mut msg = Bytes::new(&env);
-            msg.append(&ticket_id.to_xdr(&env));
-            if signature.len() != 64 {
-                return Err(PaymentError::Unauthorized);
-            }
-            let sig_bytes: BytesN<64> = signature
-                .try_into()
-                .map_err(|_| PaymentError::Unauthorized)?;
-            env.crypto().ed25519_verify(&pub_key, &msg, &sig_bytes);
+        } else {
+            let proof = proof.ok_or(PaymentError::Unauthorized)?;
+            verify_shielded_owner(&env, &mut ticket, &ticket_id.to_xdr(&env), proof)?;
         }
 
         let config = storage::get_event_config(&env, &ticket.event_id)
@@ -2396,8 +5075,6 @@ impl PaymentsContract {
         payment.status = PaymentStatus::Disputed;
         storage::update_payment(&env, &payment)?;
 
-        let rev = storage::get_event_revenue(&env, &ticket.event_id);
-        storage::set_event_revenue(&env, &ticket.event_id, rev - payment.amount);
         let token_rev = storage::get_event_token_revenue(&env, &ticket.event_id, &payment.token);
         storage::set_event_token_revenue(
             &env,
@@ -2450,16 +5127,19 @@ impl PaymentsContract {
         }
 
         let remaining = payment.amount - payment.refunded_amount;
-        if let Some(refund_to) = payment.payer.clone() {
-            let token_client = token::Client::new(&env, &payment.token);
-            token_client.transfer(&env.current_contract_address(), &refund_to, &remaining);
-        } else {
-            return Err(PaymentError::RefundNotAllowed);
+        match payment.payer.clone() {
+            Some(refund_to) => {
+                let token_client = token::Client::new(&env, &payment.token);
+                token_client.transfer(&env.current_contract_address(), &refund_to, &remaining);
+            }
+            None => credit_refund_voucher(&env, &payment, remaining),
         }
 
         payment.refunded_amount += remaining;
         payment.status = PaymentStatus::Refunded;
         storage::update_payment(&env, &payment)?;
+        storage::add_total_refunds(&env, &payment.event_id, remaining);
+        apply_bond_to_dispute(&env, &payment.event_id, &payment.token);
 
         storage::remove_dispute(&env, ticket_id);
         let disputes = storage::get_event_disputes(&env, &dispute.event_id);
@@ -2492,8 +5172,6 @@ impl PaymentsContract {
         payment.status = PaymentStatus::Held;
         storage::update_payment(&env, &payment)?;
 
-        let rev = storage::get_event_revenue(&env, &dispute.event_id);
-        storage::set_event_revenue(&env, &dispute.event_id, rev + payment.amount);
         let token_rev = storage::get_event_token_revenue(&env, &dispute.event_id, &payment.token);
         storage::set_event_token_revenue(
             &env,
@@ -2526,6 +5204,8 @@ impl PaymentsContract {
 #[cfg(test)]
 mod multi_token_test;
 #[cfg(test)]
+mod oracle_test;
+#[cfg(test)]
 mod receipt_commitment_test;
 #[cfg(test)]
 mod revenue_split_test;
@@ -2535,3 +5215,5 @@ mod test;
 mod test_disputes;
 #[cfg(test)]
 mod test_privacy_semantics;
+#[cfg(test)]
+mod test_private_resale;
@@ -1,8 +1,9 @@
 use super::*;
 use mock_event_contract::MockEventContract;
+use mock_oracle_contract::{Asset as OracleAsset, MockOracleContract, MockOracleContractClient};
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::testutils::Ledger;
-use soroban_sdk::{symbol_short, token, Address, Env};
+use soroban_sdk::{symbol_short, token, vec, Address, Env};
 
 fn setup_contract_with_two_tokens(
     env: &Env,
@@ -28,6 +29,7 @@ fn setup_contract_with_two_tokens(
     let event_contract_id = env.register(MockEventContract, ());
     let platform_wallet = Address::generate(env);
     client.initialize(&admin, &token1, &0, &platform_wallet, &event_contract_id);
+    client.allow_token(&token2, &7);
 
     let token1_client = token::StellarAssetClient::new(env, &token1);
     let token2_client = token::StellarAssetClient::new(env, &token2);
@@ -44,6 +46,38 @@ fn setup_contract_with_two_tokens(
     )
 }
 
+/// Price the event's only tier at `price`, in payout-token units.
+fn price_tier(client: &PaymentsContractClient, event_id: &soroban_sdk::Symbol, price: i128) {
+    client.sync_tier_prices(
+        event_id,
+        &vec![
+            &client.env,
+            TierPrice {
+                tier_id: 0,
+                price,
+                reference_priced: false,
+            },
+        ],
+    );
+}
+
+/// Price `tokens` at par on a fresh mock oracle, so a tier price converts
+/// one-to-one between them.
+fn price_at_par(env: &Env, client: &PaymentsContractClient, tokens: &[&Address]) {
+    let oracle_id = env.register(MockOracleContract, ());
+    let oracle = MockOracleContractClient::new(env, &oracle_id);
+    oracle.initialize(&14);
+    client.set_price_oracle(&oracle_id, &600);
+    for token in tokens {
+        client.allow_token(token, &7);
+        oracle.set_price(
+            &OracleAsset::Stellar((*token).clone()),
+            &100_000_000_000_000,
+            &env.ledger().timestamp(),
+        );
+    }
+}
+
 #[test]
 fn test_multi_token_payments() {
     let env = Env::default();
@@ -200,7 +234,10 @@ fn test_withdraw_uses_only_the_event_payout_token_revenue() {
         &0,
         &None,
         &false,
+        &vec![&env, token2.clone()],
     );
+    price_tier(&client, &event_id, amount1);
+    price_at_par(&env, &client, &[&token1, &token2]);
     client.pay_for_ticket(
         &1,
         &payer1,
@@ -238,3 +275,106 @@ fn test_withdraw_uses_only_the_event_payout_token_revenue() {
     assert_eq!(token1_transfer_client.balance(&organizer), amount1);
     assert_eq!(token2_transfer_client.balance(&contract_id), amount2);
 }
+
+#[test]
+fn test_payments_in_unlisted_tokens_are_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, token1, token2, client, _contract_id, event_contract, _, token2_client) =
+        setup_contract_with_two_tokens(&env);
+    let token3 = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let payer = Address::generate(&env);
+    token2_client.mint(&payer, &1_000);
+    token::StellarAssetClient::new(&env, &token3).mint(&payer, &1_000);
+
+    assert_eq!(
+        client.get_allowed_tokens(),
+        vec![
+            &env,
+            TokenInfo {
+                token: token2.clone(),
+                decimals: 7,
+            }
+        ]
+    );
+    assert_eq!(
+        client.try_allow_token(&token3, &19),
+        Err(Ok(PaymentError::InvalidTokenMetadata))
+    );
+
+    // Without an event config only the allowlist applies.
+    let loose_event = symbol_short!("LOOSE");
+    assert_eq!(
+        client.try_pay_for_ticket(
+            &1,
+            &payer,
+            &loose_event,
+            &100,
+            &None,
+            &token3,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ),
+        Err(Ok(PaymentError::TokenNotAllowed))
+    );
+
+    // A configured event takes its payout token plus the tokens it accepts.
+    let event_id = symbol_short!("EVENT1");
+    let sync = |accepted: soroban_sdk::Vec<Address>| {
+        client.try_sync_event_config(
+            &event_contract,
+            &event_id,
+            &admin,
+            &token1,
+            &true,
+            &false,
+            &0,
+            &0,
+            &0,
+            &1000,
+            &17280,
+            &0,
+            &None,
+            &false,
+            &accepted,
+        )
+    };
+    assert_eq!(
+        sync(vec![&env, token3.clone()]),
+        Err(Ok(PaymentError::TokenNotAllowed))
+    );
+    sync(vec![&env]).unwrap().unwrap();
+    price_tier(&client, &event_id, 100);
+    price_at_par(&env, &client, &[&token1, &token2]);
+    assert_eq!(
+        client.try_pay_for_ticket(
+            &1,
+            &payer,
+            &event_id,
+            &100,
+            &None,
+            &token2,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ),
+        Err(Ok(PaymentError::TokenNotAccepted))
+    );
+    sync(vec![&env, token2.clone()]).unwrap().unwrap();
+    client.pay_for_ticket(
+        &1,
+        &payer,
+        &event_id,
+        &100,
+        &None,
+        &token2,
+        &PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_event_token_revenue(&event_id, &token2), 100);
+}
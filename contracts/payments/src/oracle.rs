@@ -0,0 +1,29 @@
+//! Client interface for SEP-40 price feeds.
+//!
+//! Prices are read with `lastprice` and expressed in the feed's base asset
+//! (e.g. USD) at the feed's `decimals`. Reference amounts passed to quoted
+//! payments use the same base asset and scale.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn base(env: Env) -> Asset;
+    fn decimals(env: Env) -> u32;
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
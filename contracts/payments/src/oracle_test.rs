@@ -0,0 +1,263 @@
+//! Tests for payments priced in the oracle's reference unit.
+
+use super::*;
+use mock_event_contract::MockEventContract;
+use mock_oracle_contract::{Asset as OracleAsset, MockOracleContract, MockOracleContractClient};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, token, Address, Env};
+
+/// $1 at the mock oracle's 14 decimals.
+const USD: i128 = 100_000_000_000_000;
+
+#[test]
+fn test_quoted_payment_converts_reference_price() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+
+    let contract_id = env.register(PaymentsContract, ());
+    let client = PaymentsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let event_contract = env.register(MockEventContract, ());
+    client.initialize(
+        &admin,
+        &token,
+        &0,
+        &Address::generate(&env),
+        &event_contract,
+    );
+    client.allow_token(&usdc, &7);
+
+    let payer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc).mint(&payer, &1_000_000_000);
+    let event_id = symbol_short!("EV");
+    client.sync_event_config(
+        &event_contract,
+        &event_id,
+        &Address::generate(&env),
+        &token,
+        &false,
+        &false,
+        &0,
+        &0,
+        &0,
+        &1000,
+        &0,
+        &0,
+        &None,
+        &false,
+        &soroban_sdk::vec![&env, usdc.clone()],
+    );
+    // A $20 tier, a tier priced in payout tokens and a tier at the oracle's
+    // smallest unit.
+    client.sync_tier_prices(
+        &event_id,
+        &soroban_sdk::vec![
+            &env,
+            TierPrice {
+                tier_id: 0,
+                price: 20 * USD,
+                reference_priced: true,
+            },
+            TierPrice {
+                tier_id: 1,
+                price: 300_000_000,
+                reference_priced: false,
+            },
+            TierPrice {
+                tier_id: 2,
+                price: 1,
+                reference_priced: true,
+            },
+        ],
+    );
+
+    assert_eq!(
+        client.try_quote_payment(&event_id, &0, &usdc),
+        Err(Ok(PaymentError::OracleNotConfigured))
+    );
+
+    let oracle_id = env.register(MockOracleContract, ());
+    let oracle = MockOracleContractClient::new(&env, &oracle_id);
+    oracle.initialize(&14);
+    client.set_price_oracle(&oracle_id, &600);
+    assert_eq!(
+        client.try_quote_payment(&event_id, &0, &usdc),
+        Err(Ok(PaymentError::PriceUnavailable))
+    );
+
+    // USDC at $0.50: a $20 ticket costs 40 USDC at 7 decimals.
+    oracle.set_price(&OracleAsset::Stellar(usdc.clone()), &(USD / 2), &1_000_000);
+    assert_eq!(client.quote_payment(&event_id, &0, &usdc), 400_000_000);
+    // Quotes round up rather than undercharge.
+    assert_eq!(client.quote_payment(&event_id, &2, &usdc), 1);
+    // Only tiers priced in the reference unit are quoted.
+    assert_eq!(
+        client.try_quote_payment(&event_id, &1, &usdc),
+        Err(Ok(PaymentError::TierNotReferencePriced))
+    );
+    assert_eq!(
+        client.try_quote_payment(&event_id, &9, &usdc),
+        Err(Ok(PaymentError::TierNotPriced))
+    );
+    // The accepted token has no decimals metadata until it is allowlisted.
+    assert_eq!(
+        client.try_quote_payment(&event_id, &0, &token),
+        Err(Ok(PaymentError::TokenNotAllowed))
+    );
+
+    assert_eq!(
+        client.try_pay_for_ticket_quoted(&1, &payer, &event_id, &0, &None, &usdc, &399_999_999,),
+        Err(Ok(PaymentError::SlippageExceeded))
+    );
+    let payment_id =
+        client.pay_for_ticket_quoted(&1, &payer, &event_id, &0, &None, &usdc, &400_000_000);
+    let payment = client.get_payment(&payment_id);
+    assert_eq!(payment.amount, 400_000_000);
+    assert_eq!(payment.token, usdc);
+    assert_eq!(client.get_payment_tier(&payment_id), Some(0));
+    assert_eq!(
+        client.get_event_token_revenue(&event_id, &usdc),
+        400_000_000
+    );
+
+    // Naming the tier directly still has to cover the quote.
+    assert_eq!(
+        client.try_pay_for_tier(
+            &2,
+            &payer,
+            &event_id,
+            &0,
+            &1,
+            &399_999_999,
+            &None,
+            &usdc,
+            &None
+        ),
+        Err(Ok(PaymentError::PriceMismatch))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000_601);
+    assert_eq!(
+        client.try_pay_for_ticket_quoted(&2, &payer, &event_id, &0, &None, &usdc, &400_000_000,),
+        Err(Ok(PaymentError::PriceStale))
+    );
+}
+
+#[test]
+fn test_tier_price_converts_to_other_accepted_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+
+    let contract_id = env.register(PaymentsContract, ());
+    let client = PaymentsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let usdc = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let event_contract = env.register(MockEventContract, ());
+    client.initialize(
+        &admin,
+        &token,
+        &0,
+        &Address::generate(&env),
+        &event_contract,
+    );
+    client.allow_token(&token, &7);
+    client.allow_token(&usdc, &6);
+
+    let event_id = symbol_short!("EV");
+    client.sync_event_config(
+        &event_contract,
+        &event_id,
+        &Address::generate(&env),
+        &token,
+        &false,
+        &false,
+        &0,
+        &0,
+        &0,
+        &1000,
+        &0,
+        &0,
+        &None,
+        &false,
+        &soroban_sdk::vec![&env, usdc.clone()],
+    );
+    // 30 payout tokens at 7 decimals.
+    client.sync_tier_prices(
+        &event_id,
+        &soroban_sdk::vec![
+            &env,
+            TierPrice {
+                tier_id: 0,
+                price: 300_000_000,
+                reference_priced: false,
+            },
+        ],
+    );
+
+    let payer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc).mint(&payer, &100_000_000);
+    assert_eq!(
+        client.try_pay_for_tier(
+            &1,
+            &payer,
+            &event_id,
+            &0,
+            &1,
+            &15_000_000,
+            &None,
+            &usdc,
+            &None
+        ),
+        Err(Ok(PaymentError::OracleNotConfigured))
+    );
+
+    let oracle_id = env.register(MockOracleContract, ());
+    let oracle = MockOracleContractClient::new(&env, &oracle_id);
+    oracle.initialize(&14);
+    client.set_price_oracle(&oracle_id, &600);
+    // Payout token at $0.50 and USDC at $1: the tier costs 15 USDC.
+    oracle.set_price(&OracleAsset::Stellar(token.clone()), &(USD / 2), &1_000_000);
+    oracle.set_price(&OracleAsset::Stellar(usdc.clone()), &USD, &1_000_000);
+
+    assert_eq!(
+        client.try_pay_for_tier(
+            &1,
+            &payer,
+            &event_id,
+            &0,
+            &1,
+            &14_999_999,
+            &None,
+            &usdc,
+            &None
+        ),
+        Err(Ok(PaymentError::PriceMismatch))
+    );
+    let payment_id = client.pay_for_tier(
+        &1,
+        &payer,
+        &event_id,
+        &0,
+        &1,
+        &15_000_000,
+        &None,
+        &usdc,
+        &None,
+    );
+    let payment = client.get_payment(&payment_id);
+    assert_eq!(payment.token, usdc);
+    assert_eq!(client.get_payment_tier(&payment_id), Some(0));
+}
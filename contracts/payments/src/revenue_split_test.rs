@@ -5,6 +5,7 @@
 
 use super::*;
 use mock_event_contract::MockEventContract;
+use mock_oracle_contract::{Asset as OracleAsset, MockOracleContract, MockOracleContractClient};
 use soroban_sdk::testutils::{Address as _, Ledger};
 use soroban_sdk::{symbol_short, token, vec, Address, Env, Symbol, Vec};
 
@@ -66,11 +67,45 @@ fn bind(
         &0,
         &None,
         &false,
+        &Vec::new(&client.env),
     );
 }
 
-/// Pay `amount` into the event escrow from a freshly funded payer.
+/// Price the event's only tier at `price`.
+fn price_tier(client: &PaymentsContractClient, event_id: &Symbol, price: i128) {
+    client.sync_tier_prices(
+        event_id,
+        &vec![
+            &client.env,
+            TierPrice {
+                tier_id: 0,
+                price,
+                reference_priced: false,
+            },
+        ],
+    );
+}
+
+/// Price `tokens` at par on a fresh mock oracle, so a tier price converts
+/// one-to-one between them.
+fn price_at_par(env: &Env, client: &PaymentsContractClient, tokens: &[&Address]) {
+    let oracle_id = env.register(MockOracleContract, ());
+    let oracle = MockOracleContractClient::new(env, &oracle_id);
+    oracle.initialize(&14);
+    client.set_price_oracle(&oracle_id, &600);
+    for token in tokens {
+        client.allow_token(token, &7);
+        oracle.set_price(
+            &OracleAsset::Stellar((*token).clone()),
+            &100_000_000_000_000,
+            &env.ledger().timestamp(),
+        );
+    }
+}
+
+/// Pay `amount` for the event's only ticket tier from a freshly funded payer.
 fn pay(fx: &SplitFixture, nonce: u64, amount: i128) {
+    price_tier(&fx.client, &fx.event_id, amount);
     let payer = Address::generate(fx.env);
     let asset = token::StellarAssetClient::new(fx.env, &fx.token);
     asset.mint(&payer, &amount);
@@ -317,6 +352,7 @@ fn test_withdraw_split_respects_withdrawal_delay() {
         (cohost.clone(), 4000u32),
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
+    price_tier(&client, &event_id, 100_000_000);
 
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &100_000_000);
@@ -367,6 +403,7 @@ fn test_rounding_dust_accrues_to_primary_organizer() {
         (r3.clone(), 3333u32),
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
+    price_tier(&client, &event_id, 100);
 
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &100);
@@ -544,6 +581,7 @@ fn test_cancelled_split_event_distributes_only_withdrawable_ratio() {
         (cohost.clone(), 4000u32),
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
+    price_tier(&client, &event_id, 200_000_000);
 
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &200_000_000);
@@ -592,6 +630,7 @@ fn test_cancelled_split_settlement_survives_attendee_refund() {
         (cohost.clone(), 4000u32),
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
+    price_tier(&client, &event_id, 200_000_000);
 
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &200_000_000);
@@ -642,6 +681,7 @@ fn test_cohost_flagged_mid_event_before_completion_blocks_withdrawal_after_settl
         (cohost.clone(), 4000u32),
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
+    price_tier(&client, &event_id, 100_000_000);
 
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &100_000_000);
@@ -694,6 +734,8 @@ fn test_revenue_split_settlement_ignores_non_payout_token_revenue() {
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
 
+    price_tier(&client, &event_id, 100_000_000);
+
     // Payout-token revenue that participates in the split.
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &100_000_000);
@@ -714,6 +756,25 @@ fn test_revenue_split_settlement_ignores_non_payout_token_revenue() {
     let admin2 = Address::generate(&env);
     let other_token_contract = env.register_stellar_asset_contract_v2(admin2.clone());
     let other_token = other_token_contract.address();
+    client.allow_token(&other_token, &7);
+    client.sync_event_config(
+        &event_contract,
+        &event_id,
+        &organizer,
+        &token,
+        &true,
+        &false,
+        &0,
+        &0,
+        &0,
+        &1000,
+        &17280,
+        &0,
+        &None,
+        &false,
+        &vec![&env, other_token.clone()],
+    );
+    price_at_par(&env, &client, &[&token, &other_token]);
     let payer2 = Address::generate(&env);
     token::StellarAssetClient::new(&env, &other_token).mint(&payer2, &500_000_000);
     client.pay_for_ticket(
@@ -768,6 +829,7 @@ fn test_withdraw_split_respects_admin_delay_extension_after_multiple_extensions(
         (cohost.clone(), 4000u32),
     ];
     client.sync_revenue_splits(&event_contract, &event_id, &splits);
+    price_tier(&client, &event_id, 100_000_000);
 
     let payer = Address::generate(&env);
     token::StellarAssetClient::new(&env, &token).mint(&payer, &100_000_000);
@@ -1,6 +1,8 @@
 use crate::errors::PaymentError;
 use crate::types::{
-    EscrowMetadata, EventStatus, PaymentRecord, PrivacyLevel, RevenueSplit, SplitSettlement, Ticket,
+    BondPolicy, BondStatus, EscrowMetadata, EventBond, EventStatus, FeeSchedule, InstallmentPlan,
+    ListingRecord, ListingTerms, OracleConfig, PaymentRecord, PlatformTotal, PrivacyLevel,
+    RevenueSplit, SplitSettlement, Ticket, TierPrice, TokenInfo, VestingSchedule,
 };
 use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Vec};
 
@@ -51,25 +53,27 @@ pub struct EventConfig {
 pub enum DataKey {
     Admin,
     AcceptedToken,
+    /// Allowlisted payment token -> its metadata
+    AllowedToken(Address),
+    /// Enumeration of the allowlist
+    AllowedTokens,
+    PriceOracle,
     EventContract,
     EventPrivacy(Symbol),
     EventConfig(Symbol),
     Payment(u64),
     Ticket(u64),
+    /// Legacy aggregate revenue; totals are summed from `EventTokenRevenue`
     EventRevenue(Symbol),
     EventTokenRevenue(Symbol, Address),
     EventStatus(Symbol),
-    /// Map-based: Individual event-payment relationship
-    EventPayment(Symbol, u64),
     /// Map-based: Individual owner-ticket relationship
     OwnerTicket(Address, u64),
-    /// Map-based: Individual payer-payment relationship
-    PayerPayment(Address, u64),
     /// Map-based: Individual withdrawal record by event and index
     WithdrawalRecord(Symbol, u32),
     /// Map-based: Count of withdrawals for an event
     WithdrawalCount(Symbol),
-    /// Map-based: Individual event-token relationship
+    /// Legacy event-token membership flag; the revenue entry now serves
     EventToken(Symbol, Address),
     /// Indexed storage for event tokens
     EventTokenIndex(Symbol, u32),
@@ -79,6 +83,18 @@ pub enum DataKey {
     PlatformFeeBps,
     PlatformWallet,
     PlatformRevenue(Symbol),
+    /// Platform fees held in a token other than the accepted token
+    PlatformTokenRevenue(Symbol, Address),
+    /// Tokens the platform has ever booked fees in
+    PlatformTokens,
+    /// Token -> platform fees owed and withdrawn across all events
+    PlatformTotal(Address),
+    /// Token -> number of events that have booked platform fees in it
+    PlatformFeeEventCount(Address),
+    /// (token, index) -> event id, for paginated sweeps
+    PlatformFeeEventIndex(Address, u64),
+    /// Marks an (event, token) pair as already indexed
+    PlatformFeeEventListed(Symbol, Address),
     EmissionPrivacy(Symbol),
     EscrowMeta(Symbol),
     ProcessedNonce(Address, u64),
@@ -96,14 +112,39 @@ pub enum DataKey {
     /// Whether a split recipient's share is frozen pending dispute resolution.
     SplitFlagged(Symbol, Address),
     ResaleListing(u64),
+    /// Auction, expiry and token terms of a listing; absent for listings
+    /// created before auctions existed
+    ResaleListingTerms(u64),
+    /// Positions of a listed ticket in the event and seller listing indexes
+    ListingPosition(u64),
+    EventListingIndex(Symbol, u64),
+    EventListingsCount(Symbol),
+    SellerListingIndex(Address, u64),
+    SellerListingsCount(Address),
+    /// Escrowed offer: (ticket id, buyer) -> offer
+    ResaleOffer(u64, Address),
+    /// Buyers with an open offer on a ticket
+    TicketOffers(u64),
     TicketContract,
     /// Nonce replay-protection keyed by a privacy-preserving wallet hash
     /// (used for Private payments instead of the raw payer address).
     ProcessedNonceHash(BytesN<32>, u64),
     /// Per-user ticket counter keyed by a privacy-preserving wallet hash.
     UserEventTicketsHash(Symbol, BytesN<32>),
+    /// Tickets bought under one identity for one event, keyed by the
+    /// event-scoped identity hash so entries cannot be joined across events
+    IdentityPurchases(BytesN<32>),
+    /// Cap on tickets per verified identity across wallets; absent means no cap
+    EventIdentityLimit(Symbol),
     /// Records a spent nullifier commitment to guarantee Anonymous-payment uniqueness.
     SpentNullifier(BytesN<32>),
+    /// Ledger a shielded-action commitment was first posted at, awaiting its reveal
+    ShieldedCommit(BytesN<32>),
+    /// Stealth key a Private ticket was resold to; absent means the payment's key
+    TicketStealthKey(u64),
+    /// Nonce bound into a shielded ticket's listing message; each private
+    /// listing or delisting consumes one
+    PrivateResaleNonce(u64),
     /// Dispute record keyed by ticket id.
     Dispute(u64),
     /// List of disputed ticket ids for an event.
@@ -111,16 +152,50 @@ pub enum DataKey {
     TotalPayments(Symbol),
     TotalRefunds(Symbol),
     TotalWithdrawn(Symbol),
+    /// Resale royalties and platform fees retained by the contract
+    TotalResaleFees(Symbol),
     EventPaymentIndex(Symbol, u64),
     EventPaymentsCount(Symbol),
+    /// Next `EventPaymentIndex` position for bulk cancellation refunds
+    CancellationRefundCursor(Symbol),
     PayerPaymentIndex(Address, u64),
     PayerPaymentsCount(Address),
     EventTokenVolume(Symbol, Address),
     /// Indexed storage for owner tickets
     OwnerTicketIndex(Address, u64),
     OwnerTicketsCount(Address),
+    /// Minted ticket-contract id -> payment id
+    MintedTicketPayment(u64),
+    /// Payment id -> ticket-contract ticket minted for it, once linked
+    PaymentMintedTicket(u64),
+    /// Refund voucher owed on a Private/Anonymous payment, awaiting its claim
+    ClaimableRefund(u64),
+    /// Top-up payments made when a minted ticket moved to a dearer tier
+    TicketUpgradePayments(u64),
+    /// (event, payer) -> installment plan
+    InstallmentPlan(Symbol, Address),
+    /// Payout schedule replacing lump-sum withdrawal for an event
+    Vesting(Symbol),
+    /// Gross revenue an event's organizer has claimed under vesting, per token
+    VestedClaimed(Symbol, Address),
+    BondPolicy,
+    /// Platform-wide fee schedule for new events
+    FeeSchedule,
+    /// Organizer -> fee schedule overriding the platform one for new events
+    OrganizerFeeSchedule(Address),
+    /// Fee schedule an event snapshotted when it was first synced
+    EventFeeSchedule(Symbol),
+    /// Allowlisted tokens an event takes besides its payout token; absent
+    /// means none
+    EventAcceptedTokens(Symbol),
+    /// Tier prices mirrored from the event contract; absent until synced
+    EventTierPrices(Symbol),
+    /// Tier a payment paid for, when it named or resolved one
+    PaymentTier(u64),
+    /// Organizer performance bond; absent when no bond was taken
+    EventBond(Symbol),
     /// Legacy: Vector storage pattern (kept for migration compatibility only)
-    /// @deprecated Only for migration - use EventPayment instead
+    /// @deprecated Only for migration - use EventPaymentIndex instead
     EventPayments(Symbol),
 }
 
@@ -216,6 +291,65 @@ pub fn set_accepted_token(env: &Env, token: &soroban_sdk::Address) {
         .extend_ttl(&DataKey::AcceptedToken, TTL_THRESHOLD, TTL_BUMP);
 }
 
+pub fn get_token_info(env: &Env, token: &Address) -> Option<TokenInfo> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AllowedToken(token.clone()))
+}
+pub fn is_token_allowed(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::AllowedToken(token.clone()))
+}
+pub fn get_allowed_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AllowedTokens)
+        .unwrap_or_else(|| Vec::new(env))
+}
+/// Add or refresh an allowlist entry.
+pub fn set_token_info(env: &Env, info: &TokenInfo) {
+    let key = DataKey::AllowedToken(info.token.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut tokens = get_allowed_tokens(env);
+        tokens.push_back(info.token.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::AllowedTokens, &tokens);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::AllowedTokens, TTL_THRESHOLD, TTL_BUMP);
+    }
+    env.storage().persistent().set(&key, info);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn remove_token_info(env: &Env, token: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::AllowedToken(token.clone()));
+    let mut tokens = get_allowed_tokens(env);
+    if let Some(index) = tokens.first_index_of(token) {
+        tokens.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AllowedTokens, &tokens);
+    }
+}
+
+pub fn get_oracle_config(env: &Env) -> Option<OracleConfig> {
+    env.storage().persistent().get(&DataKey::PriceOracle)
+}
+pub fn set_oracle_config(env: &Env, config: &OracleConfig) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::PriceOracle, config);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::PriceOracle, TTL_THRESHOLD, TTL_BUMP);
+}
+
 pub fn get_event_contract(env: &Env) -> Result<soroban_sdk::Address, PaymentError> {
     let key = DataKey::EventContract;
     let contract = env
@@ -377,6 +511,13 @@ pub fn save_ticket(env: &Env, ticket: &Ticket) -> Result<(), PaymentError> {
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
     Ok(())
 }
+pub fn update_ticket(env: &Env, ticket: &Ticket) {
+    let key = DataKey::Ticket(ticket.ticket_id);
+    env.storage().persistent().set(&key, ticket);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
 pub fn get_ticket(env: &Env, ticket_id: u64) -> Result<Ticket, PaymentError> {
     let key = DataKey::Ticket(ticket_id);
     let ticket = env
@@ -491,15 +632,8 @@ pub fn get_owner_tickets(env: &Env, owner: &Address) -> Vec<u64> {
     tickets
 }
 
-/// Map-based: Add an event-payment relationship
+/// Append a payment to an event's payment index
 pub fn add_event_payment_map(env: &Env, event_id: &Symbol, payment_id: u64) {
-    let key = DataKey::EventPayment(event_id.clone(), payment_id);
-    env.storage().persistent().set(&key, &true);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
-
-    // Also add to indexed list for retrieval
     let count = get_event_payments_count(env, event_id);
     let idx_key = DataKey::EventPaymentIndex(event_id.clone(), count);
     env.storage().persistent().set(&idx_key, &payment_id);
@@ -520,11 +654,33 @@ pub fn get_event_payments_count(env: &Env, event_id: &Symbol) -> u64 {
     env.storage().persistent().get(&key).unwrap_or(0)
 }
 
+pub fn get_cancellation_refund_cursor(env: &Env, event_id: &Symbol) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CancellationRefundCursor(event_id.clone()))
+        .unwrap_or(0)
+}
+
+pub fn set_cancellation_refund_cursor(env: &Env, event_id: &Symbol, cursor: u64) {
+    let key = DataKey::CancellationRefundCursor(event_id.clone());
+    env.storage().persistent().set(&key, &cursor);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
 /// Add event payment using map-based approach
 pub fn add_event_payment(env: &Env, event_id: &Symbol, payment_id: u64) {
     add_event_payment_map(env, event_id, payment_id);
 }
 
+/// Payment id at position `index` of an event's payment index
+pub fn get_event_payment_at(env: &Env, event_id: &Symbol, index: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventPaymentIndex(event_id.clone(), index))
+}
+
 /// Get event payments using indexed storage
 pub fn get_event_payments(env: &Env, event_id: &Symbol) -> Vec<u64> {
     let count = get_event_payments_count(env, event_id);
@@ -540,15 +696,8 @@ pub fn get_event_payments(env: &Env, event_id: &Symbol) -> Vec<u64> {
     payments
 }
 
-/// Map-based: Add a payer-payment relationship
+/// Append a payment to a payer's payment index
 pub fn add_payer_payment_map(env: &Env, payer: &Address, payment_id: u64) {
-    let key = DataKey::PayerPayment(payer.clone(), payment_id);
-    env.storage().persistent().set(&key, &true);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
-
-    // Also add to indexed list for retrieval
     let count = get_payer_payments_count(env, payer);
     let idx_key = DataKey::PayerPaymentIndex(payer.clone(), count);
     env.storage().persistent().set(&idx_key, &payment_id);
@@ -600,23 +749,6 @@ pub fn get_event_revenue(env: &Env, event_id: &Symbol) -> i128 {
 
     total
 }
-pub fn add_event_revenue(env: &Env, event_id: &Symbol, amount: i128) {
-    let current_revenue = get_event_revenue(env, event_id);
-    let new_revenue = current_revenue + amount;
-    let key = DataKey::EventRevenue(event_id.clone());
-    env.storage().persistent().set(&key, &new_revenue);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
-}
-
-pub fn set_event_revenue(env: &Env, event_id: &Symbol, amount: i128) {
-    let key = DataKey::EventRevenue(event_id.clone());
-    env.storage().persistent().set(&key, &amount);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
-}
 pub fn update_payment(env: &Env, payment: &PaymentRecord) -> Result<(), PaymentError> {
     let key = DataKey::Payment(payment.payment_id);
     if !env.storage().persistent().has(&key) {
@@ -693,9 +825,6 @@ pub fn get_withdrawal_history(env: &Env, event_id: &Symbol) -> Vec<crate::types:
 }
 
 pub fn reset_event_revenue(env: &Env, event_id: &Symbol) {
-    let key = DataKey::EventRevenue(event_id.clone());
-    env.storage().persistent().set(&key, &0i128);
-
     let tokens = get_event_tokens(env, event_id);
     for index in 0..tokens.len() {
         if let Some(token_address) = tokens.get(index) {
@@ -717,6 +846,131 @@ pub fn set_platform_fee_bps(env: &Env, bps: u32) {
         .persistent()
         .extend_ttl(&DataKey::PlatformFeeBps, TTL_THRESHOLD, TTL_BUMP);
 }
+pub fn get_bond_policy(env: &Env) -> BondPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BondPolicy)
+        .unwrap_or(BondPolicy {
+            bond_bps: 0,
+            slash_threshold_bps: 0,
+        })
+}
+pub fn set_bond_policy(env: &Env, policy: &BondPolicy) {
+    env.storage().persistent().set(&DataKey::BondPolicy, policy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::BondPolicy, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn get_fee_schedule(env: &Env) -> Option<FeeSchedule> {
+    env.storage().persistent().get(&DataKey::FeeSchedule)
+}
+pub fn set_fee_schedule(env: &Env, schedule: &FeeSchedule) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::FeeSchedule, schedule);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::FeeSchedule, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn get_organizer_fee_schedule(env: &Env, organizer: &Address) -> Option<FeeSchedule> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrganizerFeeSchedule(organizer.clone()))
+}
+pub fn set_organizer_fee_schedule(env: &Env, organizer: &Address, schedule: &Option<FeeSchedule>) {
+    let key = DataKey::OrganizerFeeSchedule(organizer.clone());
+    match schedule {
+        Some(schedule) => {
+            env.storage().persistent().set(&key, schedule);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+pub fn get_event_fee_schedule(env: &Env, event_id: &Symbol) -> Option<FeeSchedule> {
+    let key = DataKey::EventFeeSchedule(event_id.clone());
+    let schedule = env.storage().persistent().get(&key);
+    if schedule.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    schedule
+}
+pub fn set_event_fee_schedule(env: &Env, event_id: &Symbol, schedule: &FeeSchedule) {
+    let key = DataKey::EventFeeSchedule(event_id.clone());
+    env.storage().persistent().set(&key, schedule);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn get_event_accepted_tokens(env: &Env, event_id: &Symbol) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventAcceptedTokens(event_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+pub fn set_event_accepted_tokens(env: &Env, event_id: &Symbol, tokens: &Vec<Address>) {
+    let key = DataKey::EventAcceptedTokens(event_id.clone());
+    if tokens.is_empty() {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, tokens);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn get_event_tier_prices(env: &Env, event_id: &Symbol) -> Vec<TierPrice> {
+    get_synced_tier_prices(env, event_id).unwrap_or(Vec::new(env))
+}
+/// Tier prices of an event, or `None` if they were never synced (events
+/// synced before payments learned tier prices).
+pub fn get_synced_tier_prices(env: &Env, event_id: &Symbol) -> Option<Vec<TierPrice>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventTierPrices(event_id.clone()))
+}
+pub fn set_event_tier_prices(env: &Env, event_id: &Symbol, tier_prices: &Vec<TierPrice>) {
+    let key = DataKey::EventTierPrices(event_id.clone());
+    env.storage().persistent().set(&key, tier_prices);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn get_payment_tier(env: &Env, payment_id: u64) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PaymentTier(payment_id))
+}
+pub fn set_payment_tier(env: &Env, payment_id: u64, tier_id: u32) {
+    let key = DataKey::PaymentTier(payment_id);
+    env.storage().persistent().set(&key, &tier_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn get_event_bond(env: &Env, event_id: &Symbol) -> EventBond {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventBond(event_id.clone()))
+        .unwrap_or(EventBond {
+            amount: 0,
+            slashed: 0,
+            slash_threshold_bps: 0,
+            approved_disputes: 0,
+            status: BondStatus::None,
+        })
+}
+pub fn set_event_bond(env: &Env, event_id: &Symbol, bond: &EventBond) {
+    let key = DataKey::EventBond(event_id.clone());
+    env.storage().persistent().set(&key, bond);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
 pub fn get_platform_wallet(env: &Env) -> Result<Address, PaymentError> {
     let key = DataKey::PlatformWallet;
     let wallet = env
@@ -763,6 +1017,107 @@ pub fn reset_platform_revenue(env: &Env, event_id: &Symbol) {
     let key = DataKey::PlatformRevenue(event_id.clone());
     env.storage().persistent().set(&key, &0i128);
 }
+pub fn get_platform_token_revenue(env: &Env, event_id: &Symbol, token_address: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformTokenRevenue(
+            event_id.clone(),
+            token_address.clone(),
+        ))
+        .unwrap_or(0)
+}
+pub fn add_platform_token_revenue(
+    env: &Env,
+    event_id: &Symbol,
+    token_address: &Address,
+    amount: i128,
+) {
+    let current = get_platform_token_revenue(env, event_id, token_address);
+    let key = DataKey::PlatformTokenRevenue(event_id.clone(), token_address.clone());
+    env.storage().persistent().set(&key, &(current + amount));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+pub fn reset_platform_token_revenue(env: &Env, event_id: &Symbol, token_address: &Address) {
+    let key = DataKey::PlatformTokenRevenue(event_id.clone(), token_address.clone());
+    env.storage().persistent().set(&key, &0i128);
+}
+
+pub fn get_platform_tokens(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformTokens)
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn get_platform_total(env: &Env, token: &Address) -> PlatformTotal {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformTotal(token.clone()))
+        .unwrap_or(PlatformTotal {
+            token: token.clone(),
+            owed: 0,
+            withdrawn: 0,
+        })
+}
+
+pub fn set_platform_total(env: &Env, total: &PlatformTotal) {
+    let key = DataKey::PlatformTotal(total.token.clone());
+    env.storage().persistent().set(&key, total);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+/// Add `event_id` to `token`'s sweep index the first time it books a
+/// platform fee in that token. Returns whether it was newly added.
+pub fn index_platform_fee_event(env: &Env, event_id: &Symbol, token: &Address) -> bool {
+    let listed_key = DataKey::PlatformFeeEventListed(event_id.clone(), token.clone());
+    if env.storage().persistent().has(&listed_key) {
+        return false;
+    }
+    env.storage().persistent().set(&listed_key, &true);
+    env.storage()
+        .persistent()
+        .extend_ttl(&listed_key, TTL_THRESHOLD, TTL_BUMP);
+
+    let count = get_platform_fee_event_count(env, token);
+    if count == 0 {
+        let mut tokens = get_platform_tokens(env);
+        tokens.push_back(token.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::PlatformTokens, &tokens);
+        env.storage()
+            .persistent()
+            .extend_ttl(&DataKey::PlatformTokens, TTL_THRESHOLD, TTL_BUMP);
+    }
+    let index_key = DataKey::PlatformFeeEventIndex(token.clone(), count);
+    env.storage().persistent().set(&index_key, event_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&index_key, TTL_THRESHOLD, TTL_BUMP);
+    let count_key = DataKey::PlatformFeeEventCount(token.clone());
+    env.storage().persistent().set(&count_key, &(count + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, TTL_THRESHOLD, TTL_BUMP);
+    true
+}
+
+pub fn get_platform_fee_event_count(env: &Env, token: &Address) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformFeeEventCount(token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn get_platform_fee_event(env: &Env, token: &Address, index: u64) -> Option<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PlatformFeeEventIndex(token.clone(), index))
+}
 
 pub fn get_emission_privacy(env: &Env, event_id: &Symbol) -> PrivacyLevel {
     env.storage()
@@ -831,6 +1186,60 @@ pub fn mark_nullifier_spent(env: &Env, commitment: &BytesN<32>) {
         .extend_ttl(&key, NULLIFIER_TTL_THRESHOLD, NULLIFIER_TTL_BUMP);
 }
 
+pub fn get_shielded_commit(env: &Env, commit: &BytesN<32>) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ShieldedCommit(commit.clone()))
+}
+
+pub fn set_shielded_commit(env: &Env, commit: &BytesN<32>, ledger: u32) {
+    let key = DataKey::ShieldedCommit(commit.clone());
+    env.storage().persistent().set(&key, &ledger);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, NONCE_TTL_THRESHOLD, NONCE_TTL_BUMP);
+}
+
+pub fn remove_shielded_commit(env: &Env, commit: &BytesN<32>) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ShieldedCommit(commit.clone()));
+}
+
+pub fn get_ticket_stealth_key(env: &Env, ticket_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TicketStealthKey(ticket_id))
+}
+
+pub fn set_ticket_stealth_key(env: &Env, ticket_id: u64, key: &BytesN<32>) {
+    let data_key = DataKey::TicketStealthKey(ticket_id);
+    env.storage().persistent().set(&data_key, key);
+    env.storage()
+        .persistent()
+        .extend_ttl(&data_key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_private_resale_nonce(env: &Env, ticket_id: u64) -> u64 {
+    let key = DataKey::PrivateResaleNonce(ticket_id);
+    let nonce: Option<u64> = env.storage().persistent().get(&key);
+    if nonce.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    nonce.unwrap_or(0)
+}
+
+pub fn increment_private_resale_nonce(env: &Env, ticket_id: u64) {
+    let key = DataKey::PrivateResaleNonce(ticket_id);
+    let nonce = get_private_resale_nonce(env, ticket_id);
+    env.storage().persistent().set(&key, &(nonce + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
 /// Get the current contract version from storage.
 pub fn get_contract_version(env: &Env) -> u32 {
     env.storage()
@@ -888,23 +1297,19 @@ pub fn set_event_token_revenue(
         .persistent()
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
-/// Map-based: Check if an event has a specific token
+/// Check if an event has a specific token. Every indexed token has a
+/// revenue entry, so that entry doubles as the membership record.
 pub fn has_event_token(env: &Env, event_id: &Symbol, token_address: &Address) -> bool {
-    env.storage().persistent().has(&DataKey::EventToken(
+    env.storage().persistent().has(&DataKey::EventTokenRevenue(
         event_id.clone(),
         token_address.clone(),
     ))
 }
 
-/// Map-based: Add a token to an event
+/// Add a token to an event's token index and open its revenue entry
 pub fn add_event_token_map(env: &Env, event_id: &Symbol, token_address: &Address) {
-    let key = DataKey::EventToken(event_id.clone(), token_address.clone());
-    env.storage().persistent().set(&key, &true);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    set_event_token_revenue(env, event_id, token_address, 0);
 
-    // Also add to indexed list for retrieval
     let count = get_event_token_count(env, event_id);
     let idx_key = DataKey::EventTokenIndex(event_id.clone(), count);
     env.storage().persistent().set(&idx_key, token_address);
@@ -975,6 +1380,46 @@ pub fn increment_user_event_tickets_hash(env: &Env, event_id: &Symbol, user_hash
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
 
+pub fn get_identity_purchases(env: &Env, identity_key: &BytesN<32>) -> u32 {
+    let key = DataKey::IdentityPurchases(identity_key.clone());
+    let count: Option<u32> = env.storage().persistent().get(&key);
+    if count.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    count.unwrap_or(0)
+}
+
+pub fn increment_identity_purchases(env: &Env, identity_key: &BytesN<32>) {
+    let current = get_identity_purchases(env, identity_key);
+    let key = DataKey::IdentityPurchases(identity_key.clone());
+    env.storage().persistent().set(&key, &(current + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_identity_limit(env: &Env, event_id: &Symbol) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::EventIdentityLimit(event_id.clone()))
+        .unwrap_or(0)
+}
+
+/// A limit of 0 lifts the cap and drops the entry.
+pub fn set_identity_limit(env: &Env, event_id: &Symbol, limit: u32) {
+    let key = DataKey::EventIdentityLimit(event_id.clone());
+    if limit == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &limit);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
 pub fn increment_event_sold_count(env: &Env, event_id: &Symbol) -> Result<(), PaymentError> {
     let mut config = get_event_config(env, event_id).ok_or(PaymentError::InvalidOrganizer)?;
     config.sold_count = config
@@ -1083,23 +1528,489 @@ pub fn set_split_flagged(env: &Env, event_id: &Symbol, recipient: &Address, flag
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
 
-pub fn save_resale_listing(env: &Env, ticket_id: u64, listing: &crate::types::ResaleListing) {
-    let key = DataKey::ResaleListing(ticket_id);
-    env.storage().persistent().set(&key, listing);
+/// Where a listing sits in the event and seller listing indexes.
+#[contracttype]
+#[derive(Clone)]
+pub struct ListingPosition {
+    pub event_index: u64,
+    pub seller_index: u64,
+}
+
+/// Store a new listing and append it to the event and seller indexes.
+pub fn add_resale_listing(env: &Env, listing: &crate::types::ResaleListing) {
+    set_resale_listing(env, listing);
+
+    let event_count_key = DataKey::EventListingsCount(listing.event_id.clone());
+    let event_index = push_listing_index(env, &event_count_key, listing.ticket_id, |i| {
+        DataKey::EventListingIndex(listing.event_id.clone(), i)
+    });
+    let seller_count_key = DataKey::SellerListingsCount(listing.seller.clone());
+    let seller_index = push_listing_index(env, &seller_count_key, listing.ticket_id, |i| {
+        DataKey::SellerListingIndex(listing.seller.clone(), i)
+    });
+    set_listing_position(
+        env,
+        listing.ticket_id,
+        &ListingPosition {
+            event_index,
+            seller_index,
+        },
+    );
+}
+
+/// Overwrite an indexed listing in place (e.g. a new leading bid).
+pub fn set_resale_listing(env: &Env, listing: &crate::types::ResaleListing) {
+    let key = DataKey::ResaleListing(listing.ticket_id);
+    env.storage().persistent().set(
+        &key,
+        &ListingRecord {
+            price: listing.price,
+            seller: listing.seller.clone(),
+        },
+    );
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    let terms_key = DataKey::ResaleListingTerms(listing.ticket_id);
+    env.storage().persistent().set(
+        &terms_key,
+        &ListingTerms {
+            kind: listing.kind.clone(),
+            reserve_price: listing.reserve_price,
+            token: listing.token.clone(),
+            listed_at_ledger: listing.listed_at_ledger,
+            expires_at_ledger: listing.expires_at_ledger,
+            highest_bidder: listing.highest_bidder.clone(),
+            highest_bid: listing.highest_bid,
+        },
+    );
+    env.storage()
+        .persistent()
+        .extend_ttl(&terms_key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+fn set_listing_position(env: &Env, ticket_id: u64, position: &ListingPosition) {
+    let key = DataKey::ListingPosition(ticket_id);
+    env.storage().persistent().set(&key, position);
     env.storage()
         .persistent()
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
 
-pub fn get_resale_listing(env: &Env, ticket_id: u64) -> Option<crate::types::ResaleListing> {
+fn get_listing_position(env: &Env, ticket_id: u64) -> Option<ListingPosition> {
     env.storage()
         .persistent()
-        .get(&DataKey::ResaleListing(ticket_id))
+        .get(&DataKey::ListingPosition(ticket_id))
+}
+
+fn push_listing_index(
+    env: &Env,
+    count_key: &DataKey,
+    ticket_id: u64,
+    index_key: impl Fn(u64) -> DataKey,
+) -> u64 {
+    let count: u64 = env.storage().persistent().get(count_key).unwrap_or(0);
+    let key = index_key(count);
+    env.storage().persistent().set(&key, &ticket_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    env.storage().persistent().set(count_key, &(count + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(count_key, TTL_THRESHOLD, TTL_BUMP);
+    count
+}
+
+/// Swap-remove `index` from a listing index. Returns the ticket moved into
+/// the freed slot, if any, so its position can be updated.
+fn swap_remove_listing_index(
+    env: &Env,
+    count_key: &DataKey,
+    index: u64,
+    index_key: impl Fn(u64) -> DataKey,
+) -> Option<u64> {
+    let count: u64 = env.storage().persistent().get(count_key).unwrap_or(0);
+    if count == 0 {
+        return None;
+    }
+    let last = count - 1;
+    let mut moved = None;
+    if index < last {
+        if let Some(last_ticket_id) = env.storage().persistent().get::<_, u64>(&index_key(last)) {
+            let key = index_key(index);
+            env.storage().persistent().set(&key, &last_ticket_id);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+            moved = Some(last_ticket_id);
+        }
+    }
+    env.storage().persistent().remove(&index_key(last));
+    env.storage().persistent().set(count_key, &last);
+    env.storage()
+        .persistent()
+        .extend_ttl(count_key, TTL_THRESHOLD, TTL_BUMP);
+    moved
+}
+
+/// Link a payment's ledger ticket to its ticket-contract counterpart in both
+/// directions.
+pub fn link_minted_ticket(
+    env: &Env,
+    payment_id: u64,
+    minted_ticket_id: u64,
+) -> Result<(), PaymentError> {
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::Payment(payment_id))
+    {
+        return Err(PaymentError::PaymentNotFound);
+    }
+    let forward_key = DataKey::PaymentMintedTicket(payment_id);
+    env.storage()
+        .persistent()
+        .set(&forward_key, &minted_ticket_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&forward_key, TTL_THRESHOLD, TTL_BUMP);
+
+    set_minted_ticket_payment(env, minted_ticket_id, payment_id);
+    Ok(())
+}
+
+/// Point a ticket-contract ticket at the payment that bought it.
+pub fn set_minted_ticket_payment(env: &Env, minted_ticket_id: u64, payment_id: u64) {
+    let key = DataKey::MintedTicketPayment(minted_ticket_id);
+    env.storage().persistent().set(&key, &payment_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_minted_ticket_payment(env: &Env, minted_ticket_id: u64) -> Option<u64> {
+    let key = DataKey::MintedTicketPayment(minted_ticket_id);
+    let payment_id = env.storage().persistent().get(&key);
+    if payment_id.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    payment_id
+}
+
+pub fn get_claimable_refund(env: &Env, payment_id: u64) -> i128 {
+    let key = DataKey::ClaimableRefund(payment_id);
+    let amount: Option<i128> = env.storage().persistent().get(&key);
+    if amount.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    amount.unwrap_or(0)
+}
+
+pub fn set_claimable_refund(env: &Env, payment_id: u64, amount: i128) {
+    let key = DataKey::ClaimableRefund(payment_id);
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_claimable_refund(env: &Env, payment_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ClaimableRefund(payment_id));
+}
+
+pub fn get_ticket_upgrade_payments(env: &Env, minted_ticket_id: u64) -> Vec<u64> {
+    let key = DataKey::TicketUpgradePayments(minted_ticket_id);
+    let payment_ids: Option<Vec<u64>> = env.storage().persistent().get(&key);
+    if payment_ids.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    payment_ids.unwrap_or_else(|| Vec::new(env))
+}
+
+pub fn add_ticket_upgrade_payment(env: &Env, minted_ticket_id: u64, payment_id: u64) {
+    let key = DataKey::TicketUpgradePayments(minted_ticket_id);
+    let mut payment_ids = get_ticket_upgrade_payments(env, minted_ticket_id);
+    payment_ids.push_back(payment_id);
+    env.storage().persistent().set(&key, &payment_ids);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
 
+pub fn get_installment_plan(
+    env: &Env,
+    event_id: &Symbol,
+    payer: &Address,
+) -> Option<InstallmentPlan> {
+    let key = DataKey::InstallmentPlan(event_id.clone(), payer.clone());
+    let plan = env.storage().persistent().get(&key);
+    if plan.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    plan
+}
+
+pub fn set_installment_plan(env: &Env, plan: &InstallmentPlan) {
+    let key = DataKey::InstallmentPlan(plan.event_id.clone(), plan.payer.clone());
+    env.storage().persistent().set(&key, plan);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_vesting_schedule(env: &Env, event_id: &Symbol) -> Option<VestingSchedule> {
+    let key = DataKey::Vesting(event_id.clone());
+    let vesting = env.storage().persistent().get(&key);
+    if vesting.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    vesting
+}
+
+pub fn set_vesting_schedule(env: &Env, event_id: &Symbol, vesting: &Option<VestingSchedule>) {
+    let key = DataKey::Vesting(event_id.clone());
+    match vesting {
+        Some(vesting) => {
+            env.storage().persistent().set(&key, vesting);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_vested_claimed(env: &Env, event_id: &Symbol, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VestedClaimed(event_id.clone(), token.clone()))
+        .unwrap_or(0)
+}
+
+pub fn add_vested_claimed(env: &Env, event_id: &Symbol, token: &Address, amount: i128) {
+    let key = DataKey::VestedClaimed(event_id.clone(), token.clone());
+    let current = get_vested_claimed(env, event_id, token);
+    env.storage().persistent().set(&key, &(current + amount));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_minted_ticket_id(env: &Env, payment_id: u64) -> Option<u64> {
+    let key = DataKey::PaymentMintedTicket(payment_id);
+    let minted_ticket_id = env.storage().persistent().get(&key);
+    if minted_ticket_id.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    minted_ticket_id
+}
+
+/// Assemble a ticket's listing from its record and terms. A listing with no
+/// terms entry predates auctions and reads as a fixed-price listing in the
+/// event's payout token that never expires.
+pub fn get_resale_listing(env: &Env, ticket_id: u64) -> Option<crate::types::ResaleListing> {
+    let record: ListingRecord = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ResaleListing(ticket_id))?;
+    let event_id = get_ticket(env, ticket_id).ok()?.event_id;
+    let terms = match env
+        .storage()
+        .persistent()
+        .get::<_, ListingTerms>(&DataKey::ResaleListingTerms(ticket_id))
+    {
+        Some(terms) => terms,
+        None => ListingTerms {
+            kind: crate::types::ListingKind::FixedPrice,
+            reserve_price: 0,
+            token: get_event_config(env, &event_id)?.payout_token,
+            listed_at_ledger: 0,
+            expires_at_ledger: None,
+            highest_bidder: None,
+            highest_bid: 0,
+        },
+    };
+    Some(crate::types::ResaleListing {
+        ticket_id,
+        event_id,
+        seller: record.seller,
+        kind: terms.kind,
+        price: record.price,
+        reserve_price: terms.reserve_price,
+        token: terms.token,
+        listed_at_ledger: terms.listed_at_ledger,
+        expires_at_ledger: terms.expires_at_ledger,
+        highest_bidder: terms.highest_bidder,
+        highest_bid: terms.highest_bid,
+    })
+}
+
+/// Remove a listing and drop it from the event and seller indexes.
 pub fn remove_resale_listing(env: &Env, ticket_id: u64) {
-    let key = DataKey::ResaleListing(ticket_id);
-    env.storage().persistent().remove(&key);
+    let Some(listing) = get_resale_listing(env, ticket_id) else {
+        return;
+    };
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ResaleListing(ticket_id));
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ResaleListingTerms(ticket_id));
+
+    // Listings created before the indexes existed have no position.
+    let Some(position) = get_listing_position(env, ticket_id) else {
+        return;
+    };
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ListingPosition(ticket_id));
+
+    let event_count_key = DataKey::EventListingsCount(listing.event_id.clone());
+    if let Some(moved) =
+        swap_remove_listing_index(env, &event_count_key, position.event_index, |i| {
+            DataKey::EventListingIndex(listing.event_id.clone(), i)
+        })
+    {
+        if let Some(mut moved_position) = get_listing_position(env, moved) {
+            moved_position.event_index = position.event_index;
+            set_listing_position(env, moved, &moved_position);
+        }
+    }
+    let seller_count_key = DataKey::SellerListingsCount(listing.seller.clone());
+    if let Some(moved) =
+        swap_remove_listing_index(env, &seller_count_key, position.seller_index, |i| {
+            DataKey::SellerListingIndex(listing.seller.clone(), i)
+        })
+    {
+        if let Some(mut moved_position) = get_listing_position(env, moved) {
+            moved_position.seller_index = position.seller_index;
+            set_listing_position(env, moved, &moved_position);
+        }
+    }
+}
+
+/// Page through an event's listings starting at index `cursor`.
+pub fn get_event_listings_page(
+    env: &Env,
+    event_id: &Symbol,
+    cursor: u64,
+    limit: u64,
+) -> crate::types::ListingPage {
+    let count_key = DataKey::EventListingsCount(event_id.clone());
+    get_listings_page(env, &count_key, cursor, limit, |i| {
+        DataKey::EventListingIndex(event_id.clone(), i)
+    })
+}
+
+/// Page through a seller's listings starting at index `cursor`.
+pub fn get_seller_listings_page(
+    env: &Env,
+    seller: &Address,
+    cursor: u64,
+    limit: u64,
+) -> crate::types::ListingPage {
+    let count_key = DataKey::SellerListingsCount(seller.clone());
+    get_listings_page(env, &count_key, cursor, limit, |i| {
+        DataKey::SellerListingIndex(seller.clone(), i)
+    })
+}
+
+fn get_listings_page(
+    env: &Env,
+    count_key: &DataKey,
+    cursor: u64,
+    limit: u64,
+    index_key: impl Fn(u64) -> DataKey,
+) -> crate::types::ListingPage {
+    let count: u64 = env.storage().persistent().get(count_key).unwrap_or(0);
+    let end = cursor.saturating_add(limit).min(count);
+    let mut listings = Vec::new(env);
+    for i in cursor..end {
+        if let Some(listing) = env
+            .storage()
+            .persistent()
+            .get::<_, u64>(&index_key(i))
+            .and_then(|ticket_id| get_resale_listing(env, ticket_id))
+        {
+            listings.push_back(listing);
+        }
+    }
+    crate::types::ListingPage {
+        listings,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+pub fn get_resale_offer(
+    env: &Env,
+    ticket_id: u64,
+    buyer: &Address,
+) -> Option<crate::types::ResaleOffer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ResaleOffer(ticket_id, buyer.clone()))
+}
+
+/// Store an offer, adding the buyer to the ticket's offer list if new.
+pub fn set_resale_offer(env: &Env, offer: &crate::types::ResaleOffer) {
+    let key = DataKey::ResaleOffer(offer.ticket_id, offer.buyer.clone());
+    env.storage().persistent().set(&key, offer);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    let mut buyers = get_ticket_offer_buyers(env, offer.ticket_id);
+    if !buyers.contains(&offer.buyer) {
+        buyers.push_back(offer.buyer.clone());
+        let list_key = DataKey::TicketOffers(offer.ticket_id);
+        env.storage().persistent().set(&list_key, &buyers);
+        env.storage()
+            .persistent()
+            .extend_ttl(&list_key, TTL_THRESHOLD, TTL_BUMP);
+    }
+}
+
+pub fn remove_resale_offer(env: &Env, ticket_id: u64, buyer: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ResaleOffer(ticket_id, buyer.clone()));
+
+    let mut buyers = get_ticket_offer_buyers(env, ticket_id);
+    if let Some(i) = buyers.first_index_of(buyer) {
+        buyers.remove(i);
+        let list_key = DataKey::TicketOffers(ticket_id);
+        if buyers.is_empty() {
+            env.storage().persistent().remove(&list_key);
+        } else {
+            env.storage().persistent().set(&list_key, &buyers);
+            env.storage()
+                .persistent()
+                .extend_ttl(&list_key, TTL_THRESHOLD, TTL_BUMP);
+        }
+    }
+}
+
+pub fn get_ticket_offer_buyers(env: &Env, ticket_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TicketOffers(ticket_id))
+        .unwrap_or_else(|| Vec::new(env))
 }
 
 pub fn get_dispute(env: &Env, ticket_id: u64) -> Option<crate::types::DisputeRecord> {
@@ -1142,6 +2053,20 @@ pub fn add_total_refunds(env: &Env, event_id: &Symbol, amount: i128) {
         .persistent()
         .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
 }
+pub fn get_total_resale_fees(env: &Env, event_id: &Symbol) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TotalResaleFees(event_id.clone()))
+        .unwrap_or(0)
+}
+pub fn add_total_resale_fees(env: &Env, event_id: &Symbol, amount: i128) {
+    let current = get_total_resale_fees(env, event_id);
+    let key = DataKey::TotalResaleFees(event_id.clone());
+    env.storage().persistent().set(&key, &(current + amount));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
 pub fn get_total_withdrawn(env: &Env, event_id: &Symbol) -> i128 {
     env.storage()
         .persistent()
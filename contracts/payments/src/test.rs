@@ -268,9 +268,23 @@ fn bind_event(
         &0,
         &None,
         &false,
+        &Vec::new(&client.env),
     );
 }
 
+/// Sync one tier per price, numbered from 0.
+fn price_event(client: &PaymentsContractClient, event_id: &soroban_sdk::Symbol, prices: &[i128]) {
+    let mut tiers = Vec::new(&client.env);
+    for (tier_id, price) in prices.iter().enumerate() {
+        tiers.push_back(TierPrice {
+            tier_id: tier_id as u32,
+            price: *price,
+            reference_priced: false,
+        });
+    }
+    client.sync_tier_prices(event_id, &tiers);
+}
+
 fn set_event_status_for_test(
     client: &PaymentsContractClient<'_>,
     admin: &Address,
@@ -468,6 +482,135 @@ fn test_pay_for_ticket_invalid_amount_negative() {
     assert_eq!(result.err(), Some(Ok(PaymentError::InvalidAmount)));
 }
 
+#[test]
+fn test_tier_priced_event_binds_amount_to_tier() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (_, token, client, _, token_contract, event_contract) = setup_contract_with_token(&env);
+    let organizer = Address::generate(&env);
+    let payer = Address::generate(&env);
+    let event_id = symbol_short!("TIERED");
+    token_contract.mint(&payer, &1_000);
+    bind_event(&client, &event_contract, &event_id, &organizer, &token);
+
+    // Until prices are synced the event takes any amount, as before tier
+    // pricing.
+    let payment_id = client.pay_for_ticket(
+        &1,
+        &payer,
+        &event_id,
+        &1,
+        &None,
+        &token,
+        &PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_payment_tier(&payment_id), None);
+
+    client.sync_tier_prices(
+        &event_id,
+        &soroban_sdk::vec![
+            &env,
+            TierPrice {
+                tier_id: 0,
+                price: 100,
+                reference_priced: false,
+            },
+            TierPrice {
+                tier_id: 1,
+                price: 250,
+                reference_priced: false,
+            },
+        ],
+    );
+    // Re-syncing the event config keeps the prices.
+    bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    assert_eq!(client.get_tier_prices(&event_id).len(), 2);
+
+    assert_eq!(
+        client.try_pay_for_ticket(
+            &2,
+            &payer,
+            &event_id,
+            &1,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ),
+        Err(Ok(PaymentError::TierPriceRequired))
+    );
+    assert_eq!(
+        client.try_pay_for_tier(&2, &payer, &event_id, &1, &2, &250, &None, &token, &None),
+        Err(Ok(PaymentError::PriceMismatch))
+    );
+    assert_eq!(
+        client.try_pay_for_tier(&2, &payer, &event_id, &7, &1, &100, &None, &token, &None),
+        Err(Ok(PaymentError::TierNotPriced))
+    );
+
+    let payment_id = client.pay_for_tier(&2, &payer, &event_id, &1, &2, &500, &None, &token, &None);
+    assert_eq!(client.get_payment(&payment_id).amount, 500);
+    assert_eq!(client.get_payment_tier(&payment_id), Some(1));
+
+    // Private and Anonymous buyers name their tier the same way.
+    let stealth_key = BytesN::from_array(&env, &[9u8; 32]);
+    assert_eq!(
+        client.try_pay_for_tier_with_privacy(
+            &3,
+            &payer,
+            &event_id,
+            &1,
+            &100,
+            &token,
+            &PaymentPrivacy::Private,
+            &None,
+            &Some(stealth_key.clone()),
+        ),
+        Err(Ok(PaymentError::PriceMismatch))
+    );
+    let payment_id = client.pay_for_tier_with_privacy(
+        &3,
+        &payer,
+        &event_id,
+        &1,
+        &250,
+        &token,
+        &PaymentPrivacy::Private,
+        &None,
+        &Some(stealth_key),
+    );
+    assert_eq!(client.get_payment_tier(&payment_id), Some(1));
+
+    // A purchase naming no tier buys a single-tier event's only tier.
+    client.sync_tier_prices(
+        &event_id,
+        &soroban_sdk::vec![
+            &env,
+            TierPrice {
+                tier_id: 3,
+                price: 100,
+                reference_priced: false,
+            },
+        ],
+    );
+    let payment_id = client.pay_for_ticket(
+        &4,
+        &payer,
+        &event_id,
+        &100,
+        &None,
+        &token,
+        &PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+    assert_eq!(client.get_payment_tier(&payment_id), Some(3));
+}
+
 #[test]
 fn test_pay_for_ticket_rejects_anonymous_when_disabled() {
     let env = Env::default();
@@ -802,6 +945,7 @@ fn test_refund_after_withdrawal() {
     client.withdraw_revenue(&event_id, &organizer);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     let payment_id = client.pay_for_ticket(
         &123,
         &payer,
@@ -846,27 +990,12 @@ fn test_withdraw_happy_path() {
     token_client.transfer(&admin, &payer2, &amount2);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
-    let pid1 = client.pay_for_ticket(
-        &1,
-        &payer1,
-        &event_id,
-        &amount1,
-        &None,
-        &token,
-        &PaymentPrivacy::Standard,
-        &None,
-        &None,
+    price_event(&client, &event_id, &[amount1, amount2]);
+    let pid1 = client.pay_for_tier(
+        &1, &payer1, &event_id, &0, &1, &amount1, &None, &token, &None,
     );
-    let pid2 = client.pay_for_ticket(
-        &1,
-        &payer2,
-        &event_id,
-        &amount2,
-        &None,
-        &token,
-        &PaymentPrivacy::Standard,
-        &None,
-        &None,
+    let pid2 = client.pay_for_tier(
+        &1, &payer2, &event_id, &1, &1, &amount2, &None, &token, &None,
     );
 
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Completed);
@@ -927,38 +1056,15 @@ fn test_mixed_refund_then_withdraw() {
     token_client.transfer(&admin, &payer3, &amount3);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
-    let pid1 = client.pay_for_ticket(
-        &1,
-        &payer1,
-        &event_id,
-        &amount1,
-        &None,
-        &token,
-        &PaymentPrivacy::Standard,
-        &None,
-        &None,
+    price_event(&client, &event_id, &[amount1, amount2, amount3]);
+    let pid1 = client.pay_for_tier(
+        &1, &payer1, &event_id, &0, &1, &amount1, &None, &token, &None,
     );
-    let pid2 = client.pay_for_ticket(
-        &1,
-        &payer2,
-        &event_id,
-        &amount2,
-        &None,
-        &token,
-        &PaymentPrivacy::Standard,
-        &None,
-        &None,
+    let pid2 = client.pay_for_tier(
+        &1, &payer2, &event_id, &1, &1, &amount2, &None, &token, &None,
     );
-    let pid3 = client.pay_for_ticket(
-        &1,
-        &payer3,
-        &event_id,
-        &amount3,
-        &None,
-        &token,
-        &PaymentPrivacy::Standard,
-        &None,
-        &None,
+    let pid3 = client.pay_for_tier(
+        &1, &payer3, &event_id, &2, &1, &amount3, &None, &token, &None,
     );
     client.refund(&admin, &pid2, &None);
     assert_eq!(client.get_event_revenue(&event_id), amount1 + amount3);
@@ -1047,7 +1153,9 @@ fn test_query_payments() {
     token_utils.transfer(&admin, &payer2, &(amount * 2));
 
     bind_event(&client, &event_contract, &event1, &organizer, &token);
+    price_event(&client, &event1, &[amount]);
     bind_event(&client, &event_contract, &event2, &organizer, &token);
+    price_event(&client, &event2, &[amount]);
     client.pay_for_ticket(
         &1,
         &payer1,
@@ -1663,6 +1771,7 @@ fn test_withdraw_unauthorized_organizer_rejected() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     client.pay_for_ticket(
         &1,
         &payer,
@@ -1707,6 +1816,7 @@ fn test_sync_event_config_invalid_payout_token_rejected() {
         &0,
         &None,
         &false,
+        &Vec::new(&env),
     );
     assert_eq!(result.err(), Some(Ok(PaymentError::InvalidPayoutToken)));
 
@@ -1731,6 +1841,7 @@ fn test_double_withdraw_rejected_after_revenue_cleared() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     client.pay_for_ticket(
         &1,
         &payer,
@@ -1793,6 +1904,7 @@ fn test_withdraw_before_completion_fails() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -2009,7 +2121,9 @@ fn test_max_tickets_per_user_enforcement() {
         &0,
         &None,
         &false,
+        &Vec::new(&env),
     );
+    price_event(&client, &event_id, &[amount]);
     client.pay_for_ticket(
         &1,
         &payer1,
@@ -2095,7 +2209,9 @@ fn test_event_supply_enforcement() {
         &0,
         &None,
         &false,
+        &Vec::new(&env),
     );
+    price_event(&client, &event_id, &[amount]);
 
     client.pay_for_ticket(
         &1,
@@ -2287,6 +2403,145 @@ fn test_withdraw_platform_revenue_no_revenue() {
     assert_eq!(result.err(), Some(Ok(PaymentError::NoPlatformRevenue)));
 }
 
+#[test]
+fn test_sweep_platform_revenue_pages_across_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, token, client, contract_id, token_contract, _) =
+        setup_contract_with_fee(&env, 1_000);
+    let wallet = Address::generate(&env);
+    client.set_platform_fee(&1_000, &wallet);
+    env.ledger().with_mut(|li| li.sequence_number = 20_000);
+
+    let events = [
+        symbol_short!("EV1"),
+        symbol_short!("EV2"),
+        symbol_short!("EV3"),
+    ];
+    for (i, event_id) in events.iter().enumerate() {
+        let payer = Address::generate(&env);
+        token_contract.mint(&payer, &1_000);
+        client.pay_for_ticket(
+            &(i as u64 + 1),
+            &payer,
+            event_id,
+            &1_000,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+        client.withdraw_revenue(event_id, &Address::generate(&env));
+    }
+    let totals = client.get_platform_totals();
+    assert_eq!(totals.len(), 1);
+    assert_eq!(totals.get(0).unwrap().owed, 300);
+
+    let page = client.sweep_platform_revenue(&token, &0, &2);
+    assert_eq!(page.amount, 200);
+    assert_eq!(page.next_cursor, Some(2));
+    let page = client.sweep_platform_revenue(&token, &2, &2);
+    assert_eq!(page.amount, 100);
+    assert_eq!(page.next_cursor, None);
+
+    let tc = token::Client::new(&env, &token);
+    assert_eq!(tc.balance(&wallet), 300);
+    assert_eq!(tc.balance(&contract_id), 0);
+    let total = client.get_platform_totals().get(0).unwrap();
+    assert_eq!((total.owed, total.withdrawn), (0, 300));
+    assert_eq!(client.get_platform_revenue(&events[0]), 0);
+    assert_eq!(
+        client.try_withdraw_platform_revenue(&events[0]),
+        Err(Ok(PaymentError::NoPlatformRevenue))
+    );
+}
+
+#[test]
+fn test_legacy_platform_fees_are_counted_before_payout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, token, client, contract_id, token_contract, _) =
+        setup_contract_with_fee(&env, 1_000);
+    let wallet = Address::generate(&env);
+    client.set_platform_fee(&1_000, &wallet);
+    env.ledger().with_mut(|li| li.sequence_number = 20_000);
+
+    let pay_and_settle = |nonce: u64, event_id: &Symbol| {
+        let payer = Address::generate(&env);
+        token_contract.mint(&payer, &1_000);
+        client.pay_for_ticket(
+            &nonce,
+            &payer,
+            event_id,
+            &1_000,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+        client.withdraw_revenue(event_id, &Address::generate(&env));
+    };
+    let events = [
+        symbol_short!("EV1"),
+        symbol_short!("EV2"),
+        symbol_short!("EV3"),
+    ];
+    for (i, event_id) in events.iter().enumerate() {
+        pay_and_settle(i as u64 + 1, event_id);
+    }
+    // Drop the sweep index and totals, as before they existed.
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        for event_id in events.iter() {
+            storage.remove(&storage::DataKey::PlatformFeeEventListed(
+                event_id.clone(),
+                token.clone(),
+            ));
+        }
+        storage.remove(&storage::DataKey::PlatformFeeEventCount(token.clone()));
+        storage.remove(&storage::DataKey::PlatformTokens);
+        storage.remove(&storage::DataKey::PlatformTotal(token.clone()));
+    });
+    assert!(client.get_platform_totals().is_empty());
+
+    client.withdraw_platform_revenue(&events[0]);
+    let total = client.get_platform_totals().get(0).unwrap();
+    assert_eq!((total.owed, total.withdrawn), (0, 100));
+
+    // A new fee counts the event's older ones along with it.
+    pay_and_settle(4, &events[2]);
+    assert_eq!(client.get_platform_totals().get(0).unwrap().owed, 200);
+
+    assert_eq!(
+        client.try_migrate_platform_fees(
+            &Address::generate(&env),
+            &soroban_sdk::vec![&env, events[1].clone()]
+        ),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    assert_eq!(
+        client.migrate_platform_fees(
+            &admin,
+            &soroban_sdk::vec![
+                &env,
+                events[1].clone(),
+                events[1].clone(),
+                events[2].clone()
+            ]
+        ),
+        1
+    );
+    assert_eq!(client.get_platform_totals().get(0).unwrap().owed, 300);
+
+    let page = client.sweep_platform_revenue(&token, &0, &10);
+    assert_eq!(page.amount, 300);
+    let total = client.get_platform_totals().get(0).unwrap();
+    assert_eq!((total.owed, total.withdrawn), (0, 400));
+    assert_eq!(token::Client::new(&env, &token).balance(&wallet), 400);
+}
+
 #[test]
 fn test_set_platform_fee() {
     let env = Env::default();
@@ -2314,6 +2569,92 @@ fn test_set_platform_fee_invalid_bps() {
     assert_eq!(result.err(), Some(Ok(PaymentError::InvalidFeeBps)));
 }
 
+#[test]
+fn test_fee_schedule_snapshot_tiers_caps_and_overrides() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, token, client, _, token_contract, event_contract) =
+        setup_contract_with_fee(&env, 250);
+    let organizer = Address::generate(&env);
+    let partner = Address::generate(&env);
+    let early = symbol_short!("EARLY");
+    let late = symbol_short!("LATE");
+    let schedule =
+        |primary_bps: u32, min_fee: i128, max_fee: i128, tiers: Vec<FeeTier>| FeeSchedule {
+            primary_bps,
+            resale_bps: primary_bps,
+            min_fee,
+            max_fee,
+            tiers,
+        };
+    let pay = |nonce: u64, event_id: &soroban_sdk::Symbol, amount: i128| {
+        let payer = Address::generate(&env);
+        token_contract.mint(&payer, &amount);
+        client.pay_for_ticket(
+            &nonce,
+            &payer,
+            event_id,
+            &amount,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+    };
+
+    // A platform change after creation does not reach the event.
+    bind_event(&client, &event_contract, &early, &organizer, &token);
+    price_event(&client, &early, &[1_000]);
+    client.set_fee_schedule(&schedule(1_000, 0, 0, Vec::new(&env)));
+    assert_eq!(client.get_platform_fee_bps(), 1_000);
+    assert_eq!(client.get_event_fee_schedule(&early).primary_bps, 250);
+
+    assert_eq!(
+        client.try_set_fee_schedule(&schedule(1_000, 50, 10, Vec::new(&env))),
+        Err(Ok(PaymentError::InvalidFeeSchedule))
+    );
+    let descending = soroban_sdk::vec![
+        &env,
+        FeeTier {
+            min_volume: 2_000,
+            bps: 200,
+        },
+        FeeTier {
+            min_volume: 1_000,
+            bps: 100,
+        },
+    ];
+    assert_eq!(
+        client.try_set_fee_schedule(&schedule(1_000, 0, 0, descending)),
+        Err(Ok(PaymentError::InvalidFeeSchedule))
+    );
+
+    // The partner's terms drop to 2% past 1_000 of sales, floored at 20 per
+    // ticket: 2% of 1_200 is 24, under the 40 floor for two tickets.
+    let tiers = soroban_sdk::vec![
+        &env,
+        FeeTier {
+            min_volume: 1_000,
+            bps: 200,
+        },
+    ];
+    client.set_organizer_fee_schedule(&partner, &Some(schedule(500, 20, 40, tiers)));
+    bind_event(&client, &event_contract, &late, &partner, &token);
+    price_event(&client, &late, &[600]);
+    pay(1, &late, 600);
+    pay(2, &late, 600);
+    env.ledger().with_mut(|li| li.sequence_number = 20_000);
+    client.withdraw_revenue(&late, &partner);
+    assert_eq!(client.get_platform_revenue(&late), 40);
+
+    // An admin override on a live event caps 50% of 1_000 at 100 per ticket.
+    client.set_event_fee_schedule(&early, &schedule(5_000, 0, 100, Vec::new(&env)));
+    pay(3, &early, 1_000);
+    client.withdraw_revenue(&early, &organizer);
+    assert_eq!(client.get_platform_revenue(&early), 100);
+}
+
 #[test]
 fn test_organizer_withdraw_with_fee() {
     let env = Env::default();
@@ -2325,6 +2666,7 @@ fn test_organizer_withdraw_with_fee() {
     let amount = 100_000_000i128;
     let organizer = Address::generate(&env);
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
 
     token_contract.mint(&admin, &amount);
     let token_client = token::Client::new(&env, &token);
@@ -2597,7 +2939,9 @@ fn test_per_user_limit_within_limit_succeeds() {
         &0,
         &None,
         &false,
+        &Vec::new(&env),
     );
+    price_event(&client, &event_id, &[amount]);
     assert_eq!(client.get_user_tickets(&event_id, &payer), 0);
     client.pay_for_ticket(
         &1,
@@ -2654,7 +2998,9 @@ fn test_per_user_limit_exceed_is_rejected() {
         &0,
         &None,
         &false,
+        &Vec::new(&env),
     );
+    price_event(&client, &event_id, &[amount]);
     client.pay_for_ticket(
         &1,
         &payer,
@@ -2718,7 +3064,9 @@ fn test_per_user_limit_counters_are_scoped_per_event_and_user() {
             &0,
             &None,
             &false,
+            &Vec::new(&env),
         );
+        price_event(&client, event_id, &[amount]);
     }
     client.pay_for_ticket(
         &1,
@@ -2788,6 +3136,7 @@ fn test_withdraw_cancelled_event_respects_dispute_window() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -2836,6 +3185,7 @@ fn test_withdraw_cancelled_event_after_dispute_window_succeeds() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -2887,6 +3237,7 @@ fn test_withdraw_revenue_sets_organizer_withdrawn() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -2923,6 +3274,7 @@ fn test_withdraw_revenue_then_organizer_withdraw_rejected() {
     token_client.transfer(&admin, &payer, &(amount * 2));
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -2983,6 +3335,7 @@ fn test_organizer_withdraw_then_withdraw_revenue_rejected() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -3030,6 +3383,7 @@ fn test_withdraw_revenue_twice_rejected() {
     token_client.transfer(&admin, &payer, &(amount * 2));
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     client.pay_for_ticket(
         &1,
@@ -3084,6 +3438,7 @@ fn test_withdraw_revenue_cannot_drain_cancellation_refund_pool() {
     token_client.transfer(&admin, &payer, &amount);
 
     bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
     set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
     let payment_id = client.pay_for_ticket(
         &1,
@@ -3127,3 +3482,166 @@ fn test_withdraw_revenue_cannot_drain_cancellation_refund_pool() {
     assert_eq!(token_client.balance(&payer), amount - organizer_share);
     assert_eq!(token_client.balance(&contract_id), 0);
 }
+
+#[test]
+fn test_process_cancellation_refunds_pages_and_resumes_from_cursor() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let (admin, token, client, contract_id, token_contract, event_contract) =
+        setup_contract_with_token_and_event(&env);
+    let organizer = Address::generate(&env);
+    let event_id = symbol_short!("EVTBULK");
+    let amount = 1_000i128;
+    let token_client = token::Client::new(&env, &token);
+
+    bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
+    set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
+
+    // Not cancelled yet: nothing to push.
+    assert_eq!(
+        client.try_process_cancellation_refunds(&event_id, &0, &10),
+        Err(Ok(PaymentError::EventNotActive))
+    );
+
+    let mut payers = soroban_sdk::Vec::new(&env);
+    let mut payment_ids = soroban_sdk::Vec::new(&env);
+    for i in 0..4u64 {
+        let payer = Address::generate(&env);
+        token_contract.mint(&admin, &amount);
+        token_client.transfer(&admin, &payer, &amount);
+        let payment_id = client.pay_for_ticket(
+            &(i + 1),
+            &payer,
+            &event_id,
+            &amount,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+        payers.push_back(payer);
+        payment_ids.push_back(payment_id);
+    }
+    // The second payer was already refunded in full and must be skipped.
+    client.refund(&admin, &payment_ids.get(1).unwrap(), &None);
+
+    env.ledger().with_mut(|li| {
+        li.sequence_number = 500;
+    });
+    client.cancel_event(&event_id, &organizer);
+    let refund_share = amount / 2;
+
+    // A cursor ahead of the persisted position cannot skip earlier payers.
+    let first = client.process_cancellation_refunds(&event_id, &3, &2);
+    assert_eq!(first.refunded_count, 1);
+    assert_eq!(first.next_cursor, Some(2));
+    assert_eq!(token_client.balance(&payers.get(0).unwrap()), refund_share);
+    assert_eq!(token_client.balance(&payers.get(1).unwrap()), amount);
+    assert_eq!(token_client.balance(&payers.get(2).unwrap()), 0);
+
+    let second = client.process_cancellation_refunds(&event_id, &3, &2);
+    assert_eq!(second.refunded_count, 2);
+    assert_eq!(second.next_cursor, None);
+    assert_eq!(token_client.balance(&payers.get(2).unwrap()), refund_share);
+    assert_eq!(token_client.balance(&payers.get(3).unwrap()), refund_share);
+    assert_eq!(
+        client.get_payment(&payment_ids.get(3).unwrap()).status,
+        PaymentStatus::Refunded
+    );
+
+    // Fully processed: further calls are no-ops and claims see nothing left.
+    let done = client.process_cancellation_refunds(&event_id, &4, &2);
+    assert_eq!(done.refunded_count, 0);
+    assert_eq!(done.next_cursor, None);
+    // Revisiting earlier payments pays nobody twice.
+    let revisit = client.process_cancellation_refunds(&event_id, &0, &2);
+    assert_eq!(revisit.refunded_count, 0);
+    assert_eq!(revisit.next_cursor, Some(2));
+    assert_eq!(token_client.balance(&payers.get(0).unwrap()), refund_share);
+    assert_eq!(
+        client
+            .process_cancellation_refunds(&event_id, &4, &2)
+            .next_cursor,
+        None
+    );
+    assert_eq!(
+        client.try_claim_refund(&payers.get(0).unwrap(), &payment_ids.get(0).unwrap()),
+        Err(Ok(PaymentError::PaymentAlreadyProcessed))
+    );
+    assert_eq!(
+        token_client.balance(&contract_id),
+        3 * (amount - refund_share)
+    );
+}
+
+#[test]
+fn test_process_cancellation_refunds_skips_payer_who_cannot_receive() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let client = PaymentsContractClient::new(&env, &env.register(PaymentsContract, ()));
+    let event_contract = env.register(MockEventContract, ());
+    let admin = Address::generate(&env);
+    // A revocable asset lets the issuer freeze a payer's balance.
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    sac.issuer()
+        .set_flag(soroban_sdk::testutils::IssuerFlags::RevocableFlag);
+    let token = sac.address();
+    let token_contract = token::StellarAssetClient::new(&env, &token);
+    client.initialize(
+        &admin,
+        &token,
+        &0,
+        &Address::generate(&env),
+        &event_contract,
+    );
+    let organizer = Address::generate(&env);
+    let event_id = symbol_short!("EVTFRZ");
+    let amount = 1_000i128;
+    let token_client = token::Client::new(&env, &token);
+
+    bind_event(&client, &event_contract, &event_id, &organizer, &token);
+    price_event(&client, &event_id, &[amount]);
+    set_event_status_for_test(&client, &admin, &event_id, &EventStatus::Active);
+
+    let mut payers = soroban_sdk::Vec::new(&env);
+    let mut payment_ids = soroban_sdk::Vec::new(&env);
+    for i in 0..3u64 {
+        let payer = Address::generate(&env);
+        token_contract.mint(&payer, &amount);
+        payment_ids.push_back(client.pay_for_ticket(
+            &(i + 1),
+            &payer,
+            &event_id,
+            &amount,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        ));
+        payers.push_back(payer);
+    }
+    client.cancel_event(&event_id, &organizer);
+
+    // The first payer's balance is frozen, so their refund cannot land.
+    let frozen = payers.get(0).unwrap();
+    token_contract.set_authorized(&frozen, &false);
+    let progress = client.process_cancellation_refunds(&event_id, &0, &10);
+    assert_eq!(progress.refunded_count, 2);
+    assert_eq!(progress.next_cursor, None);
+    assert_eq!(token_client.balance(&payers.get(1).unwrap()), amount);
+    assert_eq!(token_client.balance(&payers.get(2).unwrap()), amount);
+    assert_eq!(
+        client.get_payment(&payment_ids.get(0).unwrap()).status,
+        PaymentStatus::Held
+    );
+
+    // Once unfrozen, the skipped payer collects the refund directly.
+    token_contract.set_authorized(&frozen, &true);
+    client.claim_refund(&frozen, &payment_ids.get(0).unwrap());
+    assert_eq!(token_client.balance(&frozen), amount);
+}
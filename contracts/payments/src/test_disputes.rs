@@ -3,7 +3,7 @@
 use super::*;
 use mock_event_contract::MockEventContract;
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{symbol_short, token, Address, BytesN, Env, Symbol};
+use soroban_sdk::{symbol_short, token, vec, Address, BytesN, Env, Symbol};
 
 fn setup(
     env: &Env,
@@ -67,6 +67,18 @@ fn bind_event(
         &0,
         &None,
         &false,
+        &Vec::new(&client.env),
+    );
+    client.sync_tier_prices(
+        event_id,
+        &vec![
+            &client.env,
+            TierPrice {
+                tier_id: 0,
+                price: 1_000,
+                reference_priced: false,
+            },
+        ],
     );
 }
 
@@ -251,6 +263,54 @@ fn test_admin_approve_refund() {
     assert_eq!(tc.balance(&payer), balance_before + 1000);
 }
 
+#[test]
+fn test_approved_refund_keeps_withdrawal_accounting_balanced() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, token, client, _cid, _tc, event_contract) = setup(&env);
+    let organizer = Address::generate(&env);
+    let event_id = symbol_short!("EV");
+    let end_ledger = 1000u32;
+
+    // No vesting schedule: the organizer withdraws the escrow in one go.
+    bind_event(
+        &client,
+        &event_contract,
+        &event_id,
+        &organizer,
+        &token,
+        false,
+        end_ledger,
+    );
+    for nonce in 1..=2u64 {
+        let payer = Address::generate(&env);
+        fund(&env, &admin, &payer, &token, 1000);
+        client.pay_for_ticket(
+            &nonce,
+            &payer,
+            &event_id,
+            &1000,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+    }
+
+    env.ledger().with_mut(|l| l.sequence_number = end_ledger);
+    client.raise_dispute(&1, &2, &None);
+    client.approve_refund(&1);
+
+    // The refunded payment leaves the disputed total and must be booked as
+    // a refund, or the revenue invariant blocks the withdrawal.
+    client.set_event_status(&admin, &event_id, &EventStatus::Completed);
+    env.ledger()
+        .with_mut(|l| l.sequence_number = end_ledger + 17_281);
+    client.withdraw(&organizer, &event_id);
+    assert_eq!(token::Client::new(&env, &token).balance(&organizer), 1000);
+}
+
 #[test]
 fn test_admin_reject_dispute() {
     let env = Env::default();
@@ -382,10 +442,186 @@ fn test_anonymous_ticket_dispute() {
     );
     let ticket_id = 1u64;
 
+    // Raise dispute on anonymous ticket via ticket_id only, revealing the
+    // preimage against a commitment posted the ledger before.
+    let next: BytesN<32> = env
+        .crypto()
+        .sha256(&soroban_sdk::Bytes::from_slice(&env, &[2u8; 32]))
+        .into();
+    let mut proof = soroban_sdk::Bytes::from_array(&env, &next.to_array());
+    proof.append(&preimage);
+    let mut committed = proof.clone();
+    committed.append(&ticket_id.to_xdr(&env));
+    client.commit_shielded_action(&env.crypto().sha256(&committed).into());
     env.ledger().with_mut(|l| l.sequence_number = end_ledger);
-    // Raise dispute on anonymous ticket via ticket_id only
-    client.raise_dispute(&ticket_id, &2, &Some(preimage));
+    client.raise_dispute(&ticket_id, &2, &Some(proof));
+    assert_eq!(
+        client.get_ticket(&ticket_id).nullifier_commitment,
+        Some(next)
+    );
 
     let payment = client.get_payment(&1u64);
     assert_eq!(payment.status, PaymentStatus::Disputed);
 }
+
+#[test]
+fn test_vested_payout_leaves_unvested_balance_for_disputes() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, token, client, _cid, _tc, event_contract) = setup(&env);
+    let organizer = Address::generate(&env);
+    let event_id = symbol_short!("EV");
+    let end_ledger = 1000u32;
+
+    bind_event(
+        &client,
+        &event_contract,
+        &event_id,
+        &organizer,
+        &token,
+        false,
+        end_ledger,
+    );
+    // A quarter unlocks at event end, the rest over 1000 ledgers.
+    client.set_vesting_schedule(
+        &organizer,
+        &event_id,
+        &Some(VestingSchedule {
+            release_bps: 2_500,
+            vesting_ledgers: 1_000,
+        }),
+    );
+    for nonce in 1..=2u64 {
+        let payer = Address::generate(&env);
+        fund(&env, &admin, &payer, &token, 1000);
+        client.pay_for_ticket(
+            &nonce,
+            &payer,
+            &event_id,
+            &1000,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+    }
+    // Once tickets have sold only the admin may change the schedule.
+    assert_eq!(
+        client.try_set_vesting_schedule(&organizer, &event_id, &None),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+
+    env.ledger().with_mut(|l| l.sequence_number = end_ledger);
+    client.set_event_status(&admin, &event_id, &EventStatus::Completed);
+    assert_eq!(
+        client.try_withdraw(&organizer, &event_id),
+        Err(Ok(PaymentError::VestingActive))
+    );
+    assert_eq!(
+        client.try_release_if_expired(&event_id),
+        Err(Ok(PaymentError::VestingActive))
+    );
+
+    let tc = token::Client::new(&env, &token);
+    client.claim_vested(&event_id);
+    assert_eq!(tc.balance(&organizer), 500);
+    assert_eq!(
+        client.try_claim_vested(&event_id),
+        Err(Ok(PaymentError::NoRevenue))
+    );
+
+    // A dispute inside the window is paid from the unvested balance.
+    client.raise_dispute(&2u64, &0, &None);
+    client.approve_refund(&2u64);
+
+    env.ledger()
+        .with_mut(|l| l.sequence_number = end_ledger + 500);
+    assert_eq!(client.get_claimable_vested(&event_id, &token), 125);
+    client.claim_vested(&event_id);
+    assert_eq!(tc.balance(&organizer), 625);
+
+    env.ledger()
+        .with_mut(|l| l.sequence_number = end_ledger + 1_000);
+    client.claim_vested(&event_id);
+    assert_eq!(tc.balance(&organizer), 1000);
+    assert_eq!(client.get_event_revenue(&event_id), 0);
+}
+
+#[test]
+fn test_bond_covers_disputes_after_payout_and_returns_remainder() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, token, client, contract_id, _tc, event_contract) = setup(&env);
+    let organizer = Address::generate(&env);
+    let event_id = symbol_short!("EV");
+    let end_ledger = 1000u32;
+
+    bind_event(
+        &client,
+        &event_contract,
+        &event_id,
+        &organizer,
+        &token,
+        false,
+        end_ledger,
+    );
+    // 10% of face value, slashable once over a quarter of sales are refunded.
+    client.set_bond_policy(&1_000, &2_500);
+    fund(&env, &admin, &organizer, &token, 2000);
+    assert_eq!(client.lock_event_bond(&event_id, &20_000), 2000);
+    assert_eq!(
+        client.try_lock_event_bond(&event_id, &20_000),
+        Err(Ok(PaymentError::BondAlreadyLocked))
+    );
+    let bond = client.get_event_bond(&event_id);
+    assert_eq!(bond.status, BondStatus::Locked);
+    assert_eq!(bond.amount, 2000);
+
+    for nonce in 1..=2u64 {
+        let payer = Address::generate(&env);
+        fund(&env, &admin, &payer, &token, 1000);
+        client.pay_for_ticket(
+            &nonce,
+            &payer,
+            &event_id,
+            &1000,
+            &None,
+            &token,
+            &PaymentPrivacy::Standard,
+            &None,
+            &None,
+        );
+    }
+
+    // The organizer is paid out while attendees can still dispute.
+    env.ledger()
+        .with_mut(|l| l.sequence_number = end_ledger + 17280);
+    client.set_event_status(&admin, &event_id, &EventStatus::Completed);
+    client.withdraw(&organizer, &event_id);
+    let tc = token::Client::new(&env, &token);
+    assert_eq!(tc.balance(&organizer), 2000);
+
+    // One of two tickets refunded crosses the threshold; the bond pays it.
+    client.raise_dispute(&1u64, &0, &None);
+    client.approve_refund(&1u64);
+    let bond = client.get_event_bond(&event_id);
+    assert_eq!(bond.status, BondStatus::Slashed);
+    assert_eq!(bond.slashed, 1000);
+    assert_eq!(client.get_event_revenue(&event_id), 0);
+    assert_eq!(tc.balance(&contract_id), 1000);
+
+    assert_eq!(
+        client.try_return_bond(&event_id),
+        Err(Ok(PaymentError::BondNotReturnable))
+    );
+    env.ledger()
+        .with_mut(|l| l.sequence_number = end_ledger + 17280 * 7);
+    assert_eq!(client.return_bond(&event_id), 1000);
+    assert_eq!(tc.balance(&organizer), 3000);
+    assert_eq!(tc.balance(&contract_id), 0);
+    assert_eq!(
+        client.get_event_bond(&event_id).status,
+        BondStatus::Returned
+    );
+}
@@ -607,9 +607,9 @@ fn test_no_privacy_level_mutation_path_exists() {
 // ===================== Refund preserves privacy =====================
 
 #[test]
-fn test_anonymous_refund_returns_error() {
-    // Anonymous payments store no payer address, so an on-chain refund would
-    // strand the escrowed tokens. The contract rejects the refund outright.
+fn test_anonymous_refund_credits_voucher() {
+    // Anonymous payments store no payer address, so the refund is credited
+    // to a voucher the holder claims by revealing the commitment preimage.
     let env = Env::default();
     env.mock_all_auths();
     let (admin, token, client, _cid, _tc) = setup(&env);
@@ -629,19 +629,19 @@ fn test_anonymous_refund_returns_error() {
         &Some(commitment(&env)),
         &None,
     );
-    let result = client.try_refund(&admin, &pid, &None);
-    assert_eq!(result.err(), Some(Ok(PaymentError::RefundNotAllowed)));
+    client.refund(&admin, &pid, &None);
+    assert_eq!(client.get_claimable_refund(&pid), amount);
 
-    // Payment remains Held and unchanged.
     let p = client.get_payment(&pid);
-    assert_eq!(p.status, PaymentStatus::Held);
+    assert_eq!(p.status, PaymentStatus::Refunded);
     assert_eq!(p.privacy_level, PaymentPrivacy::Anonymous);
+    assert_eq!(p.payer, None);
 }
 
 #[test]
-fn test_private_refund_returns_error() {
-    // Private payments store only a hashed wallet, so an on-chain refund would
-    // strand the escrowed tokens. The contract rejects the refund outright.
+fn test_private_refund_credits_voucher() {
+    // Private payments store only a hashed wallet, so the refund is credited
+    // to a voucher claimable under the stealth delivery key.
     let env = Env::default();
     env.mock_all_auths();
     let (admin, token, client, _cid, _tc) = setup(&env);
@@ -661,12 +661,12 @@ fn test_private_refund_returns_error() {
         &None,
         &Some(stealth_key(&env)),
     );
-    let result = client.try_refund(&admin, &pid, &None);
-    assert_eq!(result.err(), Some(Ok(PaymentError::RefundNotAllowed)));
+    client.refund(&admin, &pid, &Some(400));
+    assert_eq!(client.get_claimable_refund(&pid), 400);
 
-    // Payment remains Held and unchanged.
     let p = client.get_payment(&pid);
     assert_eq!(p.status, PaymentStatus::Held);
+    assert_eq!(p.refunded_amount, 400);
     assert_eq!(p.privacy_level, PaymentPrivacy::Private);
 }
 
@@ -0,0 +1,788 @@
+//! Tests for reselling Private and Anonymous tickets without revealing their holders.
+
+use super::*;
+use ed25519_dalek::{Signer, SigningKey};
+use mock_event_contract::MockEventContract;
+use mock_oracle_contract::{Asset as OracleAsset, MockOracleContract, MockOracleContractClient};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::{symbol_short, token, vec, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+fn setup(env: &Env) -> (Address, Address, PaymentsContractClient<'_>, Address) {
+    let contract_id = env.register(PaymentsContract, ());
+    let client = PaymentsContractClient::new(env, &contract_id);
+    let event_contract_id = env.register(MockEventContract, ());
+
+    let admin = Address::generate(env);
+    let platform_wallet = Address::generate(env);
+    let token_contract = env.register_stellar_asset_contract_v2(admin.clone());
+    let token = token_contract.address();
+    client.initialize(&admin, &token, &0, &platform_wallet, &event_contract_id);
+    (admin, token, client, event_contract_id)
+}
+
+fn bind_event(
+    env: &Env,
+    client: &PaymentsContractClient,
+    event_contract: &Address,
+    event_id: &Symbol,
+    token: &Address,
+    accepted_tokens: &Vec<Address>,
+) {
+    client.sync_event_config(
+        event_contract,
+        event_id,
+        &Address::generate(env),
+        token,
+        &true,
+        &false,
+        &0,
+        &0,
+        &0,
+        &1000,
+        &17280,
+        &1000,
+        &Some(2_000),
+        &false,
+        accepted_tokens,
+    );
+    client.sync_tier_prices(
+        event_id,
+        &vec![
+            &client.env,
+            TierPrice {
+                tier_id: 0,
+                price: 1_000,
+                reference_priced: false,
+            },
+        ],
+    );
+}
+
+/// Price `tokens` at par on a fresh mock oracle, so a tier price converts
+/// one-to-one between them.
+fn price_at_par(env: &Env, client: &PaymentsContractClient, tokens: &[&Address]) {
+    let oracle_id = env.register(MockOracleContract, ());
+    let oracle = MockOracleContractClient::new(env, &oracle_id);
+    oracle.initialize(&14);
+    client.set_price_oracle(&oracle_id, &600);
+    for token in tokens {
+        client.allow_token(token, &7);
+        oracle.set_price(
+            &OracleAsset::Stellar((*token).clone()),
+            &100_000_000_000_000,
+            &env.ledger().timestamp(),
+        );
+    }
+}
+
+fn sign(env: &Env, key: &SigningKey, message: &Bytes) -> Bytes {
+    let mut buf = [0u8; 256];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buf[..len]);
+    Bytes::from_slice(env, &key.sign(&buf[..len]).to_bytes())
+}
+
+/// Commit to an Anonymous holder's action and move to the next ledger, where
+/// the returned proof can be revealed.
+fn commit_reveal(
+    env: &Env,
+    client: &PaymentsContractClient,
+    preimage: &Bytes,
+    next_commitment: &BytesN<32>,
+    message: &Bytes,
+) -> Bytes {
+    let mut proof = Bytes::from_array(env, &next_commitment.to_array());
+    proof.append(preimage);
+    let mut committed = proof.clone();
+    committed.append(message);
+    client.commit_shielded_action(&env.crypto().sha256(&committed).into());
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    proof
+}
+
+fn commitment_of(env: &Env, secret: &[u8]) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from_slice(env, secret)).into()
+}
+
+#[test]
+fn test_private_ticket_resale_rekeys_to_buyer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, token, client, event_contract) = setup(&env);
+    let event_id = symbol_short!("EV");
+    bind_event(
+        &env,
+        &client,
+        &event_contract,
+        &event_id,
+        &token,
+        &Vec::new(&env),
+    );
+
+    let seller = Address::generate(&env);
+    let payout = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let sac = token::StellarAssetClient::new(&env, &token);
+    sac.mint(&seller, &1_000);
+    sac.mint(&buyer, &1_500);
+
+    let seller_key = SigningKey::from_bytes(&[5u8; 32]);
+    let seller_stealth = BytesN::from_array(&env, &seller_key.verifying_key().to_bytes());
+    client.pay_for_ticket(
+        &1,
+        &seller,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Private,
+        &None,
+        &Some(seller_stealth.clone()),
+    );
+    let ticket_id = 1u64;
+
+    // A signature over other terms does not authorize this listing.
+    let other_terms = client.private_resale_message(&ticket_id, &payout, &1_200, &None);
+    assert!(client
+        .try_list_private_ticket_for_resale(
+            &ticket_id,
+            &payout,
+            &1_500,
+            &None,
+            &None,
+            &sign(&env, &seller_key, &other_terms),
+        )
+        .is_err());
+    // The event's resale cap still applies.
+    let over_cap = client.private_resale_message(&ticket_id, &payout, &2_500, &None);
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(
+            &ticket_id,
+            &payout,
+            &2_500,
+            &None,
+            &None,
+            &sign(&env, &seller_key, &over_cap),
+        ),
+        Err(Ok(PaymentError::InvalidAmount))
+    );
+
+    let message = client.private_resale_message(&ticket_id, &payout, &1_500, &None);
+    client.list_private_ticket_for_resale(
+        &ticket_id,
+        &payout,
+        &1_500,
+        &None,
+        &None,
+        &sign(&env, &seller_key, &message),
+    );
+    assert_eq!(
+        client.try_buy_resale_ticket(&buyer, &ticket_id),
+        Err(Ok(PaymentError::PrivacyLevelMismatch))
+    );
+
+    let buyer_key = SigningKey::from_bytes(&[6u8; 32]);
+    let buyer_stealth = BytesN::from_array(&env, &buyer_key.verifying_key().to_bytes());
+    client.buy_private_resale_ticket(&buyer, &ticket_id, &buyer_stealth);
+
+    // Proceeds less the 10% royalty go to the payout address.
+    let token_client = token::Client::new(&env, &token);
+    assert_eq!(token_client.balance(&payout), 1_350);
+    let ticket = client.get_ticket(&ticket_id);
+    assert_eq!(ticket.owner, None);
+    // The payment keeps the purchaser's key; the buyer's key now controls the
+    // ticket.
+    assert_eq!(
+        client.get_payment(&ticket.payment_id).stealth_delivery_key,
+        Some(seller_stealth)
+    );
+    assert_ne!(
+        ticket.hashed_owner,
+        client.get_payment(&ticket.payment_id).hashed_wallet
+    );
+    assert_eq!(client.get_resale_listing(&ticket_id), None);
+
+    // Only the new holder's key can list the ticket again.
+    let relist = client.private_resale_message(&ticket_id, &payout, &1_000, &None);
+    assert!(client
+        .try_list_private_ticket_for_resale(
+            &ticket_id,
+            &payout,
+            &1_000,
+            &None,
+            &None,
+            &sign(&env, &seller_key, &relist),
+        )
+        .is_err());
+    client.list_private_ticket_for_resale(
+        &ticket_id,
+        &payout,
+        &1_000,
+        &None,
+        &None,
+        &sign(&env, &buyer_key, &relist),
+    );
+}
+
+#[test]
+fn test_private_listing_signature_cannot_be_replayed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, token, client, event_contract) = setup(&env);
+    let event_id = symbol_short!("EV");
+    bind_event(
+        &env,
+        &client,
+        &event_contract,
+        &event_id,
+        &token,
+        &Vec::new(&env),
+    );
+
+    let seller = Address::generate(&env);
+    let payout = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&seller, &1_000);
+    let seller_key = SigningKey::from_bytes(&[7u8; 32]);
+    let seller_stealth = BytesN::from_array(&env, &seller_key.verifying_key().to_bytes());
+    client.pay_for_ticket(
+        &1,
+        &seller,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Private,
+        &None,
+        &Some(seller_stealth),
+    );
+    let ticket_id = 1u64;
+
+    // Another deployment asks for a different signature over the same terms.
+    let (_, _, other_client, _) = setup(&env);
+    let message = client.private_resale_message(&ticket_id, &payout, &1_500, &None);
+    assert_ne!(
+        other_client.private_resale_message(&ticket_id, &payout, &1_500, &None),
+        message
+    );
+
+    let signature = sign(&env, &seller_key, &message);
+    client.list_private_ticket_for_resale(&ticket_id, &payout, &1_500, &None, &None, &signature);
+    client.delist_ticket(&payout, &ticket_id);
+
+    // The observed signature no longer relists the ticket.
+    assert_ne!(
+        client.private_resale_message(&ticket_id, &payout, &1_500, &None),
+        message
+    );
+    assert!(client
+        .try_list_private_ticket_for_resale(&ticket_id, &payout, &1_500, &None, &None, &signature)
+        .is_err());
+    assert_eq!(client.get_resale_listing(&ticket_id), None);
+
+    let fresh = client.private_resale_message(&ticket_id, &payout, &1_500, &None);
+    client.list_private_ticket_for_resale(
+        &ticket_id,
+        &payout,
+        &1_500,
+        &None,
+        &None,
+        &sign(&env, &seller_key, &fresh),
+    );
+}
+
+#[test]
+fn test_anonymous_ticket_resale_with_commitment_preimage() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, token, client, event_contract) = setup(&env);
+    let event_id = symbol_short!("EV");
+    bind_event(
+        &env,
+        &client,
+        &event_contract,
+        &event_id,
+        &token,
+        &Vec::new(&env),
+    );
+
+    let seller = Address::generate(&env);
+    let payout = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let sac = token::StellarAssetClient::new(&env, &token);
+    sac.mint(&seller, &1_000);
+    sac.mint(&buyer, &800);
+
+    let preimage = Bytes::from_slice(&env, b"seller secret");
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+    client.pay_for_ticket(
+        &1,
+        &seller,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Anonymous,
+        &Some(commitment.clone()),
+        &None,
+    );
+    let ticket_id = 1u64;
+    let message = client.private_resale_message(&ticket_id, &payout, &800, &None);
+    let next_preimage = Bytes::from_slice(&env, b"seller next secret");
+    let next: BytesN<32> = env.crypto().sha256(&next_preimage).into();
+
+    let wrong = commit_reveal(
+        &env,
+        &client,
+        &Bytes::from_slice(&env, b"wrong secret"),
+        &next,
+        &message,
+    );
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&ticket_id, &payout, &800, &None, &None, &wrong),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+
+    // A reveal needs a commitment posted in an earlier ledger.
+    let mut proof = Bytes::from_array(&env, &next.to_array());
+    proof.append(&preimage);
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&ticket_id, &payout, &800, &None, &None, &proof),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    let mut committed = proof.clone();
+    committed.append(&message);
+    client.commit_shielded_action(&env.crypto().sha256(&committed).into());
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&ticket_id, &payout, &800, &None, &None, &proof),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+
+    // A front-runner copying the pending proof cannot redirect the proceeds:
+    // the commitment binds the payout, and a fresh one only opens next ledger.
+    let thief = Address::generate(&env);
+    let stolen = client.private_resale_message(&ticket_id, &thief, &800, &None);
+    let mut front_run = proof.clone();
+    front_run.append(&stolen);
+    client.commit_shielded_action(&env.crypto().sha256(&front_run).into());
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&ticket_id, &thief, &800, &None, &None, &proof),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+
+    client.list_private_ticket_for_resale(&ticket_id, &payout, &800, &None, &None, &proof);
+    // The ticket rotated to the seller's next commitment.
+    assert_eq!(
+        client.get_ticket(&ticket_id).nullifier_commitment,
+        Some(next.clone())
+    );
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&ticket_id, &thief, &800, &None, &None, &proof),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    // The revealed preimage no longer proves ownership, even with a fresh
+    // commitment behind it.
+    let replay = commit_reveal(
+        &env,
+        &client,
+        &preimage,
+        &commitment_of(&env, b"thief secret"),
+        &stolen,
+    );
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&ticket_id, &thief, &800, &None, &None, &replay),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    // The holder delists before listing on other terms.
+    let relist = client.private_resale_message(&ticket_id, &payout, &700, &None);
+    let relist_proof = commit_reveal(
+        &env,
+        &client,
+        &next_preimage,
+        &commitment_of(&env, b"seller third secret"),
+        &relist,
+    );
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(
+            &ticket_id,
+            &payout,
+            &700,
+            &None,
+            &None,
+            &relist_proof
+        ),
+        Err(Ok(PaymentError::TicketAlreadyListed))
+    );
+
+    // A spent commitment cannot be reused as the buyer's new identity.
+    assert_eq!(
+        client.try_buy_private_resale_ticket(&buyer, &ticket_id, &commitment),
+        Err(Ok(PaymentError::DuplicateRequest))
+    );
+    assert_eq!(
+        client.try_buy_private_resale_ticket(&buyer, &ticket_id, &next),
+        Err(Ok(PaymentError::DuplicateRequest))
+    );
+    let buyer_preimage = Bytes::from_slice(&env, b"buyer secret");
+    let buyer_commitment: BytesN<32> = env.crypto().sha256(&buyer_preimage).into();
+    client.buy_private_resale_ticket(&buyer, &ticket_id, &buyer_commitment);
+
+    assert_eq!(token::Client::new(&env, &token).balance(&payout), 720);
+    let ticket = client.get_ticket(&ticket_id);
+    assert_eq!(ticket.nullifier_commitment, Some(buyer_commitment));
+
+    // The seller's preimage no longer proves ownership; the buyer's does.
+    let resell = client.private_resale_message(&ticket_id, &buyer, &500, &None);
+    let seller_proof = commit_reveal(
+        &env,
+        &client,
+        &next_preimage,
+        &commitment_of(&env, b"seller fourth secret"),
+        &resell,
+    );
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(
+            &ticket_id,
+            &buyer,
+            &500,
+            &None,
+            &None,
+            &seller_proof
+        ),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    let buyer_proof = commit_reveal(
+        &env,
+        &client,
+        &buyer_preimage,
+        &commitment_of(&env, b"buyer next secret"),
+        &resell,
+    );
+    client.list_private_ticket_for_resale(&ticket_id, &buyer, &500, &None, &None, &buyer_proof);
+}
+
+#[test]
+fn test_resale_settles_and_books_fees_in_listing_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, token, client, event_contract) = setup(&env);
+    let event_id = symbol_short!("EV");
+    let organizer = Address::generate(&env);
+    let token2 = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.allow_token(&token2, &7);
+    // Fees are snapshotted when the event is first synced.
+    let platform_wallet = Address::generate(&env);
+    client.set_platform_fee(&500, &platform_wallet);
+    client.sync_event_config(
+        &event_contract,
+        &event_id,
+        &organizer,
+        &token,
+        &true,
+        &false,
+        &0,
+        &0,
+        &0,
+        &1000,
+        &17280,
+        &1000,
+        &None,
+        &false,
+        &vec![&env, token2.clone()],
+    );
+    client.sync_tier_prices(
+        &event_id,
+        &vec![
+            &env,
+            TierPrice {
+                tier_id: 0,
+                price: 1_000,
+                reference_priced: false,
+            },
+        ],
+    );
+    price_at_par(&env, &client, &[&token, &token2]);
+
+    let seller = Address::generate(&env);
+    let payout = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let other_payer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&seller, &1_000);
+    let sac2 = token::StellarAssetClient::new(&env, &token2);
+    sac2.mint(&other_payer, &1_000);
+    sac2.mint(&buyer, &800);
+
+    let preimage = Bytes::from_slice(&env, b"seller secret");
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+    client.pay_for_ticket(
+        &1,
+        &seller,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Anonymous,
+        &Some(commitment),
+        &None,
+    );
+    let ticket_id = 1u64;
+
+    // Only tokens the event accepts can price a resale.
+    let unlisted = Some(
+        env.register_stellar_asset_contract_v2(admin.clone())
+            .address(),
+    );
+    let message = client.private_resale_message(&ticket_id, &payout, &800, &unlisted);
+    let next = commitment_of(&env, b"seller next secret");
+    let proof = commit_reveal(&env, &client, &preimage, &next, &message);
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(
+            &ticket_id, &payout, &800, &None, &unlisted, &proof,
+        ),
+        Err(Ok(PaymentError::TokenNotAccepted))
+    );
+    client.pay_for_ticket(
+        &1,
+        &other_payer,
+        &event_id,
+        &1_000,
+        &None,
+        &token2,
+        &PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+    let message = client.private_resale_message(&ticket_id, &payout, &800, &Some(token2.clone()));
+    let proof = commit_reveal(&env, &client, &preimage, &next, &message);
+    client.list_private_ticket_for_resale(
+        &ticket_id,
+        &payout,
+        &800,
+        &None,
+        &Some(token2.clone()),
+        &proof,
+    );
+    assert_eq!(client.get_resale_listing(&ticket_id).unwrap().token, token2);
+
+    let buyer_commitment: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from_slice(&env, b"buyer secret"))
+        .into();
+    client.buy_private_resale_ticket(&buyer, &ticket_id, &buyer_commitment);
+
+    // 5% platform fee and 10% royalty stay in token2's buckets.
+    let token2_client = token::Client::new(&env, &token2);
+    assert_eq!(token2_client.balance(&payout), 680);
+    assert_eq!(client.get_event_token_revenue(&event_id, &token2), 1_080);
+    assert_eq!(client.get_event_token_revenue(&event_id, &token), 1_000);
+    assert_eq!(client.get_platform_token_revenue(&event_id, &token2), 40);
+    assert_eq!(client.get_platform_revenue(&event_id), 0);
+
+    // The revenue invariant accounts for the resale fees.
+    client.set_event_status(&admin, &event_id, &EventStatus::Completed);
+    client.withdraw_all_tokens(&organizer, &event_id);
+    assert_eq!(token2_client.balance(&organizer), 1_080);
+
+    client.withdraw_platform_revenue(&event_id);
+    assert_eq!(token2_client.balance(&platform_wallet), 40);
+    assert_eq!(client.get_platform_token_revenue(&event_id, &token2), 0);
+}
+
+#[test]
+fn test_capped_event_resells_only_in_payout_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (admin, token, client, event_contract) = setup(&env);
+    let event_id = symbol_short!("EV");
+    let token2 = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    client.allow_token(&token2, &7);
+    bind_event(
+        &env,
+        &client,
+        &event_contract,
+        &event_id,
+        &token,
+        &vec![&env, token2.clone()],
+    );
+    price_at_par(&env, &client, &[&token, &token2]);
+
+    let seller = Address::generate(&env);
+    let other_payer = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &token).mint(&seller, &1_000);
+    token::StellarAssetClient::new(&env, &token2).mint(&other_payer, &1_000);
+
+    let preimage = Bytes::from_slice(&env, b"seller secret");
+    client.pay_for_ticket(
+        &1,
+        &seller,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Anonymous,
+        &Some(env.crypto().sha256(&preimage).into()),
+        &None,
+    );
+    client.pay_for_ticket(
+        &1,
+        &other_payer,
+        &event_id,
+        &1_000,
+        &None,
+        &token2,
+        &PaymentPrivacy::Standard,
+        &None,
+        &None,
+    );
+
+    // The resale cap is denominated in the payout token.
+    let next = commitment_of(&env, b"seller next secret");
+    let message = client.private_resale_message(&1, &seller, &800, &Some(token2.clone()));
+    let proof = commit_reveal(&env, &client, &preimage, &next, &message);
+    assert_eq!(
+        client.try_list_private_ticket_for_resale(&1, &seller, &800, &None, &Some(token2), &proof,),
+        Err(Ok(PaymentError::TokenNotAccepted))
+    );
+    let message = client.private_resale_message(&1, &seller, &800, &Some(token.clone()));
+    let proof = commit_reveal(&env, &client, &preimage, &next, &message);
+    client.list_private_ticket_for_resale(&1, &seller, &800, &None, &Some(token), &proof);
+}
+
+#[test]
+fn test_cancelled_event_refunds_shielded_payments_through_vouchers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (_admin, token, client, event_contract) = setup(&env);
+    let event_id = symbol_short!("EV");
+    bind_event(
+        &env,
+        &client,
+        &event_contract,
+        &event_id,
+        &token,
+        &Vec::new(&env),
+    );
+    let sac = token::StellarAssetClient::new(&env, &token);
+    let tc = token::Client::new(&env, &token);
+
+    let private_payer = Address::generate(&env);
+    let stealth = SigningKey::from_bytes(&[3u8; 32]);
+    sac.mint(&private_payer, &1_000);
+    let private_id = client.pay_for_ticket(
+        &1,
+        &private_payer,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Private,
+        &None,
+        &Some(BytesN::from_array(
+            &env,
+            &stealth.verifying_key().to_bytes(),
+        )),
+    );
+    let anon_payer = Address::generate(&env);
+    let preimage = Bytes::from_slice(&env, b"anon secret");
+    sac.mint(&anon_payer, &1_000);
+    let anon_id = client.pay_for_ticket(
+        &2,
+        &anon_payer,
+        &event_id,
+        &1_000,
+        &None,
+        &token,
+        &PaymentPrivacy::Anonymous,
+        &Some(env.crypto().sha256(&preimage).into()),
+        &None,
+    );
+
+    // The private ticket changes hands before the event is cancelled.
+    let (private_ticket, anon_ticket) = (1u64, 2u64);
+    let payout = Address::generate(&env);
+    let listing = client.private_resale_message(&private_ticket, &payout, &1_000, &None);
+    client.list_private_ticket_for_resale(
+        &private_ticket,
+        &payout,
+        &1_000,
+        &None,
+        &None,
+        &sign(&env, &stealth, &listing),
+    );
+    let buyer = Address::generate(&env);
+    let buyer_key = SigningKey::from_bytes(&[4u8; 32]);
+    sac.mint(&buyer, &1_000);
+    client.buy_private_resale_ticket(
+        &buyer,
+        &private_ticket,
+        &BytesN::from_array(&env, &buyer_key.verifying_key().to_bytes()),
+    );
+
+    // Cancelled before it started: everything is refundable.
+    let organizer = client.get_event_config(&event_id).organizer;
+    client.cancel_event(&event_id, &organizer);
+    assert_eq!(
+        client.try_claim_refund(&private_payer, &private_id),
+        Err(Ok(PaymentError::RefundNotAllowed))
+    );
+
+    // The refund follows the ticket to the buyer, not the original payer.
+    let private_to = Address::generate(&env);
+    let message = client.refund_claim_message(&private_ticket, &private_to);
+    assert!(client
+        .try_claim_refund_voucher(
+            &private_ticket,
+            &private_to,
+            &sign(&env, &stealth, &message)
+        )
+        .is_err());
+    let signature = sign(&env, &buyer_key, &message);
+    assert!(client
+        .try_claim_refund_voucher(&private_ticket, &Address::generate(&env), &signature)
+        .is_err());
+    client.claim_refund_voucher(&private_ticket, &private_to, &signature);
+    assert_eq!(tc.balance(&private_to), 1_000);
+    assert_eq!(
+        client.get_payment(&private_id).status,
+        PaymentStatus::Refunded
+    );
+    assert_eq!(
+        client.try_claim_refund_voucher(&private_ticket, &private_to, &signature),
+        Err(Ok(PaymentError::NoClaimableRefund))
+    );
+
+    // The preimage is only revealed against a commitment to the recipient.
+    let anon_to = Address::generate(&env);
+    let next = commitment_of(&env, b"anon next secret");
+    let message = client.refund_claim_message(&anon_ticket, &anon_to);
+    let mut bare = Bytes::from_array(&env, &next.to_array());
+    bare.append(&preimage);
+    assert_eq!(
+        client.try_claim_refund_voucher(&anon_ticket, &anon_to, &bare),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    let proof = commit_reveal(&env, &client, &preimage, &next, &message);
+    // A front-runner copying the proof cannot redirect the refund.
+    assert_eq!(
+        client.try_claim_refund_voucher(&anon_ticket, &Address::generate(&env), &proof),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    client.claim_refund_voucher(&anon_ticket, &anon_to, &proof);
+    assert_eq!(tc.balance(&anon_to), 1_000);
+    assert_eq!(client.get_payment(&anon_id).status, PaymentStatus::Refunded);
+    assert_eq!(
+        client.get_ticket(&anon_ticket).nullifier_commitment,
+        Some(next)
+    );
+    assert_eq!(
+        client.try_claim_refund_voucher(&anon_ticket, &anon_to, &proof),
+        Err(Ok(PaymentError::Unauthorized))
+    );
+    // Only the 10% resale royalty stays with the event.
+    assert_eq!(client.get_event_revenue(&event_id), 100);
+}
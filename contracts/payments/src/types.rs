@@ -1,5 +1,5 @@
 pub use privacy_utils::PrivacyLevel;
-use soroban_sdk::{contracttype, Address, BytesN, Symbol};
+use soroban_sdk::{contracttype, Address, BytesN, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -109,13 +109,170 @@ pub enum FlagResolution {
     ReassignToPrimary = 1,
 }
 
+/// How a resale listing is priced.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ListingKind {
+    /// Sells at `price` to the first buyer.
+    FixedPrice = 0,
+    /// Escrowed ascending bids from `price`; the highest bid at or above
+    /// `reserve_price` wins once the listing expires.
+    EnglishAuction = 1,
+    /// The price falls linearly from `price` to `reserve_price` over the
+    /// listing's lifetime; the first buyer pays the current price.
+    DutchAuction = 2,
+}
+
+/// A resale listing as returned to callers, assembled from its stored
+/// `ListingRecord` and `ListingTerms`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ResaleListing {
+    pub ticket_id: u64,
+    pub event_id: Symbol,
+    pub seller: Address,
+    pub kind: ListingKind,
+    /// Fixed price, or the opening price of an auction
+    pub price: i128,
+    /// Lowest price the seller accepts; 0 for fixed-price listings
+    pub reserve_price: i128,
+    /// Token the listing is priced and settled in
+    pub token: Address,
+    pub listed_at_ledger: u32,
+    /// Last ledger the listing can be bought or bid on; `None` never expires
+    pub expires_at_ledger: Option<u32>,
+    /// Escrowed leading bid of an English auction
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+}
+
+/// Price and seller of a listing, stored under `DataKey::ResaleListing` in
+/// the shape listings have always had.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingRecord {
     pub price: i128,
     pub seller: Address,
 }
 
+/// Auction, expiry and token terms of a listing, stored under
+/// `DataKey::ResaleListingTerms`. Listings without an entry are fixed-price,
+/// never expire and settle in the event's payout token.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingTerms {
+    pub kind: ListingKind,
+    pub reserve_price: i128,
+    pub token: Address,
+    pub listed_at_ledger: u32,
+    pub expires_at_ledger: Option<u32>,
+    pub highest_bidder: Option<Address>,
+    pub highest_bid: i128,
+}
+
+/// A page of resale listings; `next_cursor` is `None` once the index is
+/// exhausted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingPage {
+    pub listings: Vec<ResaleListing>,
+    pub next_cursor: Option<u64>,
+}
+
+/// Price feed used to quote reference-priced payments.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    pub oracle: Address,
+    /// Oldest price, in seconds, a quote may use
+    pub max_price_age: u64,
+}
+
+/// Ticket tier price mirrored from the event contract, in payout-token units
+/// unless `reference_priced`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierPrice {
+    pub tier_id: u32,
+    pub price: i128,
+    /// `price` is in the oracle's reference unit (e.g. USD) and is quoted
+    /// into whichever accepted token the attendee pays in
+    pub reference_priced: bool,
+}
+
+/// One scheduled top-up of an installment plan, due by ledger timestamp
+/// `due_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Installment {
+    pub amount: i128,
+    pub due_at: u64,
+}
+
+/// Deposit taken when an installment plan starts, followed by the top-ups.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentSchedule {
+    pub deposit: i128,
+    pub installments: Vec<Installment>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InstallmentStatus {
+    Active = 0,
+    Completed = 1,
+    Defaulted = 2,
+    /// Closed without penalty because the event was cancelled
+    Cancelled = 3,
+}
+
+/// A ticket bought in installments. Every charge is an ordinary held
+/// payment in the payout token; the ledger ticket is issued with the last.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InstallmentPlan {
+    pub event_id: Symbol,
+    pub payer: Address,
+    pub tier_id: u32,
+    pub schedule: InstallmentSchedule,
+    /// Share of the deposit kept if the plan defaults
+    pub penalty_bps: u32,
+    /// Top-ups paid so far, not counting the deposit
+    pub paid_count: u32,
+    /// Deposit first, then each top-up
+    pub payment_ids: Vec<u64>,
+    pub status: InstallmentStatus,
+}
+
+/// Organizer payout schedule: `release_bps` of the escrow unlocks at event
+/// end and the rest vests linearly over `vesting_ledgers`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub release_bps: u32,
+    pub vesting_ledgers: u32,
+}
+
+/// Allowlisted payment token and the metadata needed to price in it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenInfo {
+    pub token: Address,
+    pub decimals: u32,
+}
+
+/// A buyer's escrowed offer for a ticket, listed or not.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResaleOffer {
+    pub ticket_id: u64,
+    pub buyer: Address,
+    pub amount: i128,
+    pub token: Address,
+    pub expires_at_ledger: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DisputeRecord {
@@ -125,3 +282,91 @@ pub struct DisputeRecord {
     pub reason_code: u32,
     pub raised_at_ledger: u32,
 }
+
+/// Platform bond requirement: organizers lock `bond_bps` of an event's face
+/// value when it is created. Zero disables bonds.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondPolicy {
+    pub bond_bps: u32,
+    /// Approved disputes, as a share of tickets sold, above which the bond
+    /// starts covering refunds the escrow can no longer pay
+    pub slash_threshold_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BondStatus {
+    None = 0,
+    Locked = 1,
+    Slashed = 2,
+    Returned = 3,
+}
+
+/// Organizer performance bond held in the payout token against one event.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventBond {
+    pub amount: i128,
+    /// Portion paid into the escrow to fund approved dispute refunds
+    pub slashed: i128,
+    /// Policy threshold snapshotted when the bond was locked
+    pub slash_threshold_bps: u32,
+    pub approved_disputes: u32,
+    pub status: BondStatus,
+}
+
+/// Platform rate that replaces the base primary rate once an event's gross
+/// payout-token sales reach `min_volume`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    pub min_volume: i128,
+    pub bps: u32,
+}
+
+/// Platform fee terms. Events snapshot the terms in force when they are
+/// created, so later changes only reach new events.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    /// Rate on primary sales, charged when the organizer is paid
+    pub primary_bps: u32,
+    /// Rate on resales, charged at settlement
+    pub resale_bps: u32,
+    /// Per-ticket floor in the payout token; 0 disables it
+    pub min_fee: i128,
+    /// Per-ticket ceiling in the payout token; 0 disables it
+    pub max_fee: i128,
+    /// Volume tiers in ascending `min_volume` order
+    pub tiers: Vec<FeeTier>,
+}
+
+/// Platform fees in one token across every event: still held by the
+/// contract (`owed`) and already paid to the platform wallet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformTotal {
+    pub token: Address,
+    pub owed: i128,
+    pub withdrawn: i128,
+}
+
+/// One page of `sweep_platform_revenue`. `next_cursor` is `None` once every
+/// event holding fees in the token has been visited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlatformSweep {
+    pub token: Address,
+    pub amount: i128,
+    pub next_cursor: Option<u64>,
+}
+
+/// Outcome of one `process_cancellation_refunds` page. `next_cursor` is
+/// `None` once every payment of the event has been visited.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundProgress {
+    pub refunded_count: u32,
+    pub next_cursor: Option<u64>,
+}
@@ -0,0 +1,17 @@
+use crate::types::TicketAttestation;
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+/// Read interface for partners that gate access on ticket ownership.
+///
+/// Other Soroban contracts can call a deployed `TicketContract` through the
+/// generated `TicketAccessClient` without depending on its storage layout.
+#[contractclient(name = "TicketAccessClient")]
+pub trait TicketAccess {
+    /// Whether `owner` holds a valid ticket to the event, optionally in a
+    /// specific tier.
+    fn has_valid_ticket(env: Env, owner: Address, event_id: Symbol, tier_id: Option<u32>) -> bool;
+
+    /// Whether an attestation was issued by this contract, has not expired,
+    /// and still describes a valid ticket held by its holder.
+    fn verify_attestation(env: Env, attestation: TicketAttestation) -> bool;
+}
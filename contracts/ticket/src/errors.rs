@@ -26,4 +26,19 @@ pub enum TicketError {
     UnsupportedVersion = 16,       // CommonErrorCode::UnsupportedVersion
     RecoveryKeyNotFound = 17,      // CommonErrorCode::NotFound
     InvalidRecoverySignature = 18, // CommonErrorCode::InvalidInput
+    BadgeAlreadyMinted = 19,       // CommonErrorCode::AlreadyExists
+    BadgeNotFound = 20,            // CommonErrorCode::NotFound
+    NotApprovedOrOwner = 21,       // CommonErrorCode::Unauthorized
+    MetadataTooLong = 22,          // CommonErrorCode::InvalidInput
+    InvalidExpirationLedger = 23,  // CommonErrorCode::InvalidInput
+    TransferPolicyViolation = 24,  // CommonErrorCode::Unauthorized
+    TransferLocked = 25,           // CommonErrorCode::NotActive
+    InvalidGuardianSet = 26,       // CommonErrorCode::InvalidInput
+    NotGuardian = 27,              // CommonErrorCode::Unauthorized
+    RecoveryNotPending = 28,       // CommonErrorCode::NotFound
+    RecoveryAlreadyPending = 29,   // CommonErrorCode::AlreadyExists
+    RecoveryNotReady = 30,         // CommonErrorCode::NotActive
+    EventNotClosed = 31,           // CommonErrorCode::NotActive
+    ArchiveGracePeriodActive = 32, // CommonErrorCode::NotActive
+    EventAlreadyArchived = 33,     // CommonErrorCode::AlreadyProcessed
 }
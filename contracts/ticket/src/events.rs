@@ -1,4 +1,7 @@
-use soroban_sdk::{contractevent, Address, Env, Symbol};
+use crate::types::{
+    EventTransferConfig, GuardianSet, RevocationReason, TicketAttestation, TransferPolicy,
+};
+use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol};
 
 #[contractevent(data_format = "vec", topics = ["ticket_transferred"])]
 pub struct TicketTransferred {
@@ -34,6 +37,47 @@ pub struct TicketCancelled {
     pub cancelled_at: u64,
 }
 
+#[contractevent(data_format = "vec", topics = ["ticket_revoked"])]
+pub struct TicketRevoked {
+    pub ticket_id: u64,
+    pub event_id: Symbol,
+    pub owner: Address,
+    pub reason: RevocationReason,
+    pub revoked_at: u64,
+}
+
+#[contractevent(data_format = "vec", topics = ["ticket_tier_changed"])]
+pub struct TicketTierChanged {
+    pub ticket_id: u64,
+    pub event_id: Symbol,
+    pub from_tier_id: u32,
+    pub to_tier_id: u32,
+    pub changed_at: u64,
+}
+
+#[contractevent(data_format = "vec", topics = ["ticket_attested"])]
+pub struct TicketAttested {
+    pub ticket_id: u64,
+    pub holder: Address,
+    pub digest: BytesN<32>,
+    pub expires_at_ledger: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["event_closed"])]
+pub struct EventClosed {
+    pub event_id: Symbol,
+    pub cancelled: bool,
+    pub closed_at_ledger: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["event_archived"])]
+pub struct EventArchived {
+    pub event_id: Symbol,
+    pub archived_tickets: u64,
+    pub issuance_digest: BytesN<32>,
+    pub archived_at_ledger: u32,
+}
+
 #[contractevent(data_format = "vec", topics = ["ticket_recovery_key_set"])]
 pub struct TicketRecoveryKeySet {
     pub ticket_id: u64,
@@ -49,6 +93,80 @@ pub struct TicketRecovered {
     pub recovered_at: u64,
 }
 
+#[contractevent(data_format = "vec", topics = ["badge_minted"])]
+pub struct AttendanceBadgeMinted {
+    pub ticket_id: u64,
+    pub event_id: Symbol,
+    pub holder: Address,
+    pub issued_at: u64,
+}
+
+#[contractevent(data_format = "vec", topics = ["ticket_approval"])]
+pub struct TicketApproval {
+    pub ticket_id: u64,
+    pub owner: Address,
+    pub approved: Option<Address>,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["approval_for_all"])]
+pub struct ApprovalForAll {
+    pub owner: Address,
+    pub operator: Address,
+    pub approved: bool,
+    pub expiration_ledger: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["transfer_policy_synced"])]
+pub struct TransferPolicySynced {
+    pub event_id: Symbol,
+    pub policy: TransferPolicy,
+    pub lock_ledgers: u32,
+    pub event_start_ledger: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["owner_recovery_key_set"])]
+pub struct OwnerRecoveryKeySet {
+    pub owner: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(data_format = "vec", topics = ["recovery_guardians_set"])]
+pub struct RecoveryGuardiansSet {
+    pub owner: Address,
+    pub guardian_count: u32,
+    pub threshold: u32,
+    pub delay_ledgers: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["recovery_initiated"])]
+pub struct RecoveryInitiated {
+    pub ticket_id: u64,
+    pub guardian: Address,
+    pub new_owner: Address,
+    pub executable_at_ledger: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["recovery_approved"])]
+pub struct RecoveryApproved {
+    pub ticket_id: u64,
+    pub guardian: Address,
+    pub approvals: u32,
+}
+
+#[contractevent(data_format = "vec", topics = ["recovery_vetoed"])]
+pub struct RecoveryVetoed {
+    pub ticket_id: u64,
+    pub owner: Address,
+}
+
+#[contractevent(data_format = "vec", topics = ["anon_badge_minted"])]
+pub struct AnonymousBadgeMinted {
+    pub event_id: Symbol,
+    pub commitment: BytesN<32>,
+    pub issued_at: u64,
+}
+
 pub fn emit_ticket_transferred(
     env: &Env,
     ticket_id: u64,
@@ -104,6 +222,40 @@ pub fn emit_ticket_cancelled(env: &Env, ticket_id: u64, event_id: Symbol, owner:
     .publish(env);
 }
 
+pub fn emit_ticket_revoked(
+    env: &Env,
+    ticket_id: u64,
+    event_id: Symbol,
+    owner: Address,
+    reason: RevocationReason,
+) {
+    TicketRevoked {
+        ticket_id,
+        event_id,
+        owner,
+        reason,
+        revoked_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub fn emit_ticket_tier_changed(
+    env: &Env,
+    ticket_id: u64,
+    event_id: Symbol,
+    from_tier_id: u32,
+    to_tier_id: u32,
+) {
+    TicketTierChanged {
+        ticket_id,
+        event_id,
+        from_tier_id,
+        to_tier_id,
+        changed_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
 pub fn emit_ticket_recovery_key_set(env: &Env, ticket_id: u64, owner: Address) {
     TicketRecoveryKeySet {
         ticket_id,
@@ -122,3 +274,141 @@ pub fn emit_ticket_recovered(env: &Env, ticket_id: u64, old_owner: Address, new_
     }
     .publish(env);
 }
+
+pub fn emit_owner_recovery_key_set(env: &Env, owner: Address, enabled: bool) {
+    OwnerRecoveryKeySet { owner, enabled }.publish(env);
+}
+
+pub fn emit_recovery_guardians_set(env: &Env, owner: Address, guardians: &GuardianSet) {
+    RecoveryGuardiansSet {
+        owner,
+        guardian_count: guardians.guardians.len(),
+        threshold: guardians.threshold,
+        delay_ledgers: guardians.delay_ledgers,
+    }
+    .publish(env);
+}
+
+pub fn emit_recovery_initiated(
+    env: &Env,
+    ticket_id: u64,
+    guardian: Address,
+    new_owner: Address,
+    executable_at_ledger: u32,
+) {
+    RecoveryInitiated {
+        ticket_id,
+        guardian,
+        new_owner,
+        executable_at_ledger,
+    }
+    .publish(env);
+}
+
+pub fn emit_recovery_approved(env: &Env, ticket_id: u64, guardian: Address, approvals: u32) {
+    RecoveryApproved {
+        ticket_id,
+        guardian,
+        approvals,
+    }
+    .publish(env);
+}
+
+pub fn emit_recovery_vetoed(env: &Env, ticket_id: u64, owner: Address) {
+    RecoveryVetoed { ticket_id, owner }.publish(env);
+}
+
+pub fn emit_badge_minted(env: &Env, ticket_id: u64, event_id: Symbol, holder: Address) {
+    AttendanceBadgeMinted {
+        ticket_id,
+        event_id,
+        holder,
+        issued_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub fn emit_anonymous_badge_minted(env: &Env, event_id: Symbol, commitment: BytesN<32>) {
+    AnonymousBadgeMinted {
+        event_id,
+        commitment,
+        issued_at: env.ledger().timestamp(),
+    }
+    .publish(env);
+}
+
+pub fn emit_ticket_approval(
+    env: &Env,
+    ticket_id: u64,
+    owner: Address,
+    approved: Option<Address>,
+    expiration_ledger: u32,
+) {
+    TicketApproval {
+        ticket_id,
+        owner,
+        approved,
+        expiration_ledger,
+    }
+    .publish(env);
+}
+
+pub fn emit_approval_for_all(
+    env: &Env,
+    owner: Address,
+    operator: Address,
+    approved: bool,
+    expiration_ledger: u32,
+) {
+    ApprovalForAll {
+        owner,
+        operator,
+        approved,
+        expiration_ledger,
+    }
+    .publish(env);
+}
+
+pub fn emit_transfer_policy_synced(env: &Env, event_id: Symbol, config: EventTransferConfig) {
+    TransferPolicySynced {
+        event_id,
+        policy: config.policy,
+        lock_ledgers: config.lock_ledgers,
+        event_start_ledger: config.event_start_ledger,
+    }
+    .publish(env);
+}
+
+pub fn emit_event_closed(env: &Env, event_id: Symbol, cancelled: bool) {
+    EventClosed {
+        event_id,
+        cancelled,
+        closed_at_ledger: env.ledger().sequence(),
+    }
+    .publish(env);
+}
+
+pub fn emit_event_archived(
+    env: &Env,
+    event_id: Symbol,
+    archived_tickets: u64,
+    issuance_digest: BytesN<32>,
+) {
+    EventArchived {
+        event_id,
+        archived_tickets,
+        issuance_digest,
+        archived_at_ledger: env.ledger().sequence(),
+    }
+    .publish(env);
+}
+
+pub fn emit_ticket_attested(env: &Env, attestation: &TicketAttestation) {
+    TicketAttested {
+        ticket_id: attestation.ticket_id,
+        holder: attestation.holder.clone(),
+        digest: attestation.digest.clone(),
+        expires_at_ledger: attestation.expires_at_ledger,
+    }
+    .publish(env);
+}
@@ -1,4 +1,5 @@
 #![no_std]
+mod attestation;
 mod errors;
 mod events;
 mod storage;
@@ -10,10 +11,35 @@ mod migration_test;
 #[cfg(test)]
 mod test;
 
-use crate::errors::TicketError;
+pub use crate::attestation::{TicketAccess, TicketAccessClient};
+pub use crate::errors::TicketError;
 use crate::storage::DataKey;
-pub use crate::types::{Ticket, TicketStatus};
-use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+pub use crate::types::{
+    AnonymousAttendanceBadge, ApprovalGrant, AttendanceBadge, EventArchive, EventTransferConfig,
+    GuardianSet, PendingRecovery, RevocationReason, Ticket, TicketAttestation, TicketMetadata,
+    TicketPage, TicketStatus, TransferPolicy,
+};
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, String, Symbol, Vec,
+};
+
+const TOKEN_NAME: &str = "Zicket Ticket";
+const TOKEN_SYMBOL: &str = "ZTKT";
+const MAX_BASE_URI_LEN: u32 = 200;
+const MAX_SEAT_LEN: u32 = 32;
+/// Upper bound on a rendered `token_uri`: base URI, ticket id, tier and seat.
+const MAX_TOKEN_URI_LEN: usize = 320;
+/// Maximum number of index entries scanned by one paginated query.
+const MAX_PAGE_SIZE: u32 = 100;
+const RECOVERY_DOMAIN: &[u8] = b"zicket:ticket-recovery:v1";
+const MAX_GUARDIANS: u32 = 10;
+/// Shortest guardian-recovery delay an owner may configure (~1 day).
+const MIN_RECOVERY_DELAY_LEDGERS: u32 = 17_280;
+/// Ledgers after an event closes before its tickets may be archived (~7 days).
+const ARCHIVE_GRACE_LEDGERS: u32 = 120_960;
+const ATTESTATION_DOMAIN: &[u8] = b"zicket:ticket-attestation:v1";
+/// Longest lifetime of a ticket attestation (~1 day).
+const MAX_ATTESTATION_LEDGERS: u32 = 17_280;
 
 #[contract]
 pub struct TicketContract;
@@ -25,6 +51,7 @@ impl TicketContract {
         event_id: Symbol,
         organizer: Address,
         owner: Address,
+        tier_id: u32,
     ) -> Result<u64, TicketError> {
         if let Ok(event_contract) = storage::get_event_contract(&env) {
             event_contract.require_auth();
@@ -35,6 +62,7 @@ impl TicketContract {
         }
 
         let ticket_id = read_next_ticket_id(&env);
+        let transfer_policy = event_transfer_policy(&env, &event_id);
 
         let ticket = Ticket {
             ticket_id,
@@ -43,7 +71,7 @@ impl TicketContract {
             owner: owner.clone(),
             issued_at: env.ledger().timestamp(),
             status: TicketStatus::Valid,
-            is_transferable: true,
+            is_transferable: transfer_policy != TransferPolicy::Disallowed,
             is_used: false,
         };
 
@@ -55,10 +83,15 @@ impl TicketContract {
             storage::TTL_THRESHOLD,
             storage::TTL_BUMP,
         );
+        storage::set_ticket_transfer_policy(&env, ticket_id, &transfer_policy);
+        if tier_id != 0 {
+            storage::set_ticket_tier(&env, ticket_id, tier_id);
+        }
 
         // Use map-based indexing instead of vector storage
         storage::add_owner_ticket(&env, &owner, ticket_id);
         storage::add_event_ticket(&env, &event_id, ticket_id);
+        storage::add_owner_event_ticket(&env, &owner, &event_id, ticket_id);
 
         write_next_ticket_id(&env, ticket_id + 1);
         events::emit_ticket_minted(
@@ -78,6 +111,7 @@ impl TicketContract {
         event_id: Symbol,
         organizer: Address,
         owner: Address,
+        tier_id: u32,
         count: u32,
     ) -> Result<soroban_sdk::Vec<u64>, TicketError> {
         if count == 0 || count > 100 {
@@ -94,6 +128,7 @@ impl TicketContract {
 
         let mut ticket_ids = soroban_sdk::Vec::new(&env);
         let mut next_id = read_next_ticket_id(&env);
+        let transfer_policy = event_transfer_policy(&env, &event_id);
 
         for _ in 0..count {
             let ticket = Ticket {
@@ -103,16 +138,21 @@ impl TicketContract {
                 owner: owner.clone(),
                 issued_at: env.ledger().timestamp(),
                 status: TicketStatus::Valid,
-                is_transferable: true,
+                is_transferable: transfer_policy != TransferPolicy::Disallowed,
                 is_used: false,
             };
 
             env.storage()
                 .persistent()
                 .set(&DataKey::Ticket(next_id), &ticket);
+            storage::set_ticket_transfer_policy(&env, next_id, &transfer_policy);
+            if tier_id != 0 {
+                storage::set_ticket_tier(&env, next_id, tier_id);
+            }
 
             storage::add_owner_ticket(&env, &owner, next_id);
             storage::add_event_ticket(&env, &event_id, next_id);
+            storage::add_owner_event_ticket(&env, &owner, &event_id, next_id);
 
             events::emit_ticket_minted(
                 &env,
@@ -166,6 +206,8 @@ impl TicketContract {
             return Err(TicketError::TicketNotTransferable);
         }
 
+        check_transfer_allowed(&env, &ticket, false)?;
+
         ticket.owner = to.clone();
         env.storage()
             .persistent()
@@ -176,15 +218,15 @@ impl TicketContract {
             storage::TTL_BUMP,
         );
 
-        // Use map-based indexing: remove from old owner, add to new owner
-        storage::remove_owner_ticket(&env, &from, ticket_id);
-        storage::add_owner_ticket(&env, &to, ticket_id);
+        reindex_owner(&env, ticket_id, &ticket.event_id, &from, &to);
 
         events::emit_ticket_transferred(&env, ticket_id, ticket.event_id.clone(), from, to);
 
         Ok(())
     }
 
+    /// All of the owner's ticket ids. Unbounded; prefer
+    /// `get_owner_tickets_paginated` for owners with many tickets.
     pub fn get_tickets_by_owner(env: Env, owner: Address) -> Vec<u64> {
         storage::get_tickets_by_owner(&env, owner)
     }
@@ -216,6 +258,9 @@ impl TicketContract {
             TicketStatus::Valid => {}
             TicketStatus::Cancelled => return Err(TicketError::EventNotActive),
             TicketStatus::Used => return Err(TicketError::TicketAlreadyUsed),
+            TicketStatus::Revoked | TicketStatus::Expired => {
+                return Err(TicketError::TicketNotActive)
+            }
         }
         ticket.is_used = true;
         ticket.status = TicketStatus::Used;
@@ -227,6 +272,8 @@ impl TicketContract {
             storage::TTL_THRESHOLD,
             storage::TTL_BUMP,
         );
+        storage::remove_approved(&env, ticket_id);
+        storage::mark_owner_event_ticket_spent(&env, &ticket.owner, &ticket.event_id);
         events::emit_ticket_used(
             &env,
             ticket_id,
@@ -234,17 +281,229 @@ impl TicketContract {
             ticket.owner.clone(),
         );
 
+        // Check-in mints the holder's soulbound attendance badge. A holder who
+        // uses several tickets for the same event keeps the first badge.
+        if !storage::has_badge(&env, &ticket.event_id, &ticket.owner) {
+            let badge = AttendanceBadge {
+                event_id: ticket.event_id.clone(),
+                ticket_id,
+                holder: ticket.owner.clone(),
+                issued_at: env.ledger().timestamp(),
+            };
+            storage::save_badge(&env, &badge);
+            events::emit_badge_minted(&env, ticket_id, badge.event_id, badge.holder);
+        }
+
+        Ok(())
+    }
+    pub fn get_badge(
+        env: Env,
+        event_id: Symbol,
+        holder: Address,
+    ) -> Result<AttendanceBadge, TicketError> {
+        storage::get_badge(&env, &event_id, &holder).ok_or(TicketError::BadgeNotFound)
+    }
+    pub fn has_attended(env: Env, holder: Address, event_id: Symbol) -> bool {
+        storage::has_badge(&env, &event_id, &holder)
+    }
+    /// Events the holder has checked in to, in check-in order.
+    pub fn get_attended_events(env: Env, holder: Address, start: u64, limit: u64) -> Vec<Symbol> {
+        storage::get_attended_events(&env, &holder, start, limit)
+    }
+
+    /// Mint a commitment-based attendance badge for an Anonymous event.
+    ///
+    /// Only the linked event contract may call this, after it has checked the
+    /// anonymous ticket in. `commitment` is `sha256(preimage)` for a secret
+    /// chosen by the attendee, so no address is ever linked to the badge.
+    pub fn mint_anonymous_badge(
+        env: Env,
+        event_id: Symbol,
+        commitment: BytesN<32>,
+    ) -> Result<(), TicketError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        if storage::get_anonymous_badge(&env, &event_id, &commitment).is_some() {
+            return Err(TicketError::BadgeAlreadyMinted);
+        }
+
+        let badge = AnonymousAttendanceBadge {
+            event_id: event_id.clone(),
+            commitment: commitment.clone(),
+            issued_at: env.ledger().timestamp(),
+        };
+        storage::save_anonymous_badge(&env, &badge);
+        events::emit_anonymous_badge_minted(&env, event_id, commitment);
+
         Ok(())
     }
+    pub fn get_anonymous_badge(
+        env: Env,
+        event_id: Symbol,
+        commitment: BytesN<32>,
+    ) -> Result<AnonymousAttendanceBadge, TicketError> {
+        storage::get_anonymous_badge(&env, &event_id, &commitment).ok_or(TicketError::BadgeNotFound)
+    }
+    /// Check an anonymous badge by revealing the preimage of its commitment.
+    pub fn verify_anonymous_badge(env: Env, event_id: Symbol, preimage: Bytes) -> bool {
+        let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+        storage::get_anonymous_badge(&env, &event_id, &commitment).is_some()
+    }
+
     pub fn get_ticket(env: Env, ticket_id: u64) -> Result<Ticket, TicketError> {
         storage::get_ticket(&env, ticket_id)
     }
+    /// Event tier the ticket currently belongs to. Moved by `set_ticket_tier`
+    /// when the holder upgrades or downgrades.
+    pub fn get_ticket_tier(env: Env, ticket_id: u64) -> Result<u32, TicketError> {
+        storage::get_ticket(&env, ticket_id)?;
+        Ok(storage::get_ticket_tier(&env, ticket_id))
+    }
+    /// Event transfer policy at mint time. Later policy changes do not apply
+    /// to tickets that were already issued.
+    pub fn get_ticket_transfer_policy(
+        env: Env,
+        ticket_id: u64,
+    ) -> Result<TransferPolicy, TicketError> {
+        storage::get_ticket(&env, ticket_id)?;
+        Ok(storage::get_ticket_transfer_policy(&env, ticket_id))
+    }
+    /// All of the owner's ticket ids. Unbounded; prefer
+    /// `get_owner_tickets_paginated` for owners with many tickets.
     pub fn get_owner_tickets(env: Env, owner: Address) -> Vec<u64> {
         storage::get_tickets_by_owner(&env, owner)
     }
+    /// All ticket ids for an event. Unbounded; prefer
+    /// `get_event_tickets_paginated` for large events.
     pub fn get_event_tickets(env: Env, event_id: Symbol) -> Vec<u64> {
         storage::get_tickets_by_event(&env, event_id)
     }
+
+    /// Page through the owner's tickets, optionally restricted to one event
+    /// and/or one status.
+    ///
+    /// At most `limit` (capped at `MAX_PAGE_SIZE`) index entries are scanned
+    /// per call, so a filtered page can hold fewer ids than `limit` while
+    /// `next_cursor` is still `Some`.
+    pub fn get_owner_tickets_paginated(
+        env: Env,
+        owner: Address,
+        cursor: u64,
+        limit: u32,
+        event_id: Option<Symbol>,
+        status: Option<TicketStatus>,
+    ) -> TicketPage {
+        let limit = page_limit(limit);
+        let keep = |ticket_id: u64| ticket_matches(&env, ticket_id, &status);
+        match event_id {
+            Some(event_id) => {
+                storage::get_owner_event_tickets_page(&env, &owner, &event_id, cursor, limit, keep)
+            }
+            None => storage::get_owner_tickets_page(&env, &owner, cursor, limit, keep),
+        }
+    }
+
+    /// Page through an event's tickets, optionally restricted to one status.
+    /// Paging works as in `get_owner_tickets_paginated`.
+    pub fn get_event_tickets_paginated(
+        env: Env,
+        event_id: Symbol,
+        cursor: u64,
+        limit: u32,
+        status: Option<TicketStatus>,
+    ) -> TicketPage {
+        let limit = page_limit(limit);
+        storage::get_event_tickets_page(&env, &event_id, cursor, limit, |ticket_id| {
+            ticket_matches(&env, ticket_id, &status)
+        })
+    }
+
+    /// Number of tickets the owner holds for an event, in any status.
+    pub fn get_owner_event_ticket_count(env: Env, owner: Address, event_id: Symbol) -> u64 {
+        storage::get_owner_event_tickets_count(&env, &owner, &event_id)
+    }
+
+    /// Whether the owner holds at least one unused, uncancelled ticket for the
+    /// event, optionally in `tier_id`. Without a tier this is constant-time,
+    /// backed by a per-(owner, event) counter; with one it reads the owner's
+    /// tickets to the event.
+    pub fn has_valid_ticket(
+        env: Env,
+        owner: Address,
+        event_id: Symbol,
+        tier_id: Option<u32>,
+    ) -> bool {
+        if storage::get_valid_ticket_count(&env, &owner, &event_id) == 0 {
+            return false;
+        }
+        let Some(tier_id) = tier_id else {
+            return true;
+        };
+        !storage::get_owner_event_tickets_page(&env, &owner, &event_id, 0, u64::MAX, |id| {
+            storage::get_ticket(&env, id).is_ok_and(|t| t.status == TicketStatus::Valid)
+                && storage::get_ticket_tier(&env, id) == tier_id
+        })
+        .ticket_ids
+        .is_empty()
+    }
+
+    /// Issue an attestation that `holder` holds `ticket_id`, valid for
+    /// `valid_for_ledgers` (at most `MAX_ATTESTATION_LEDGERS`). Partners check
+    /// it with `verify_attestation`; it stops verifying early if the ticket
+    /// is transferred, used, cancelled or changes tier.
+    pub fn issue_attestation(
+        env: Env,
+        holder: Address,
+        ticket_id: u64,
+        valid_for_ledgers: u32,
+    ) -> Result<TicketAttestation, TicketError> {
+        holder.require_auth();
+
+        if valid_for_ledgers == 0 || valid_for_ledgers > MAX_ATTESTATION_LEDGERS {
+            return Err(TicketError::InvalidInput);
+        }
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.owner != holder {
+            return Err(TicketError::Unauthorized);
+        }
+        if ticket.is_used || ticket.status != TicketStatus::Valid {
+            return Err(TicketError::TicketNotActive);
+        }
+
+        let issued_at_ledger = env.ledger().sequence();
+        let mut attestation = TicketAttestation {
+            ticket_id,
+            event_id: ticket.event_id,
+            tier_id: storage::get_ticket_tier(&env, ticket_id),
+            holder,
+            issued_at_ledger,
+            expires_at_ledger: issued_at_ledger.saturating_add(valid_for_ledgers),
+            digest: BytesN::from_array(&env, &[0; 32]),
+        };
+        attestation.digest = attestation_digest(&env, &attestation);
+        storage::record_attestation(&env, &attestation.digest, ticket_id, valid_for_ledgers);
+        events::emit_ticket_attested(&env, &attestation);
+
+        Ok(attestation)
+    }
+
+    pub fn verify_attestation(env: Env, attestation: TicketAttestation) -> bool {
+        if env.ledger().sequence() > attestation.expires_at_ledger
+            || attestation.digest != attestation_digest(&env, &attestation)
+            || storage::get_attested_ticket(&env, &attestation.digest)
+                != Some(attestation.ticket_id)
+        {
+            return false;
+        }
+        storage::get_ticket(&env, attestation.ticket_id).is_ok_and(|ticket| {
+            ticket.status == TicketStatus::Valid
+                && !ticket.is_used
+                && ticket.owner == attestation.holder
+                && ticket.event_id == attestation.event_id
+                && storage::get_ticket_tier(&env, ticket.ticket_id) == attestation.tier_id
+        })
+    }
     pub fn cancel_ticket(env: Env, ticket_id: u64, caller: Address) -> Result<(), TicketError> {
         caller.require_auth();
 
@@ -264,6 +523,8 @@ impl TicketContract {
 
         ticket.status = TicketStatus::Cancelled;
         storage::update_ticket(&env, &ticket);
+        storage::remove_approved(&env, ticket_id);
+        storage::mark_owner_event_ticket_spent(&env, &ticket.owner, &ticket.event_id);
 
         events::emit_ticket_cancelled(
             &env,
@@ -275,6 +536,53 @@ impl TicketContract {
         Ok(())
     }
 
+    /// Void a valid ticket on behalf of its organizer or the platform.
+    ///
+    /// Only the linked event contract may call this; it checks who is
+    /// revoking and settles the refund with the payments contract.
+    pub fn revoke_ticket(
+        env: Env,
+        ticket_id: u64,
+        reason: RevocationReason,
+    ) -> Result<(), TicketError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.is_used || ticket.status != TicketStatus::Valid {
+            return Err(TicketError::TicketNotActive);
+        }
+
+        ticket.status = TicketStatus::Revoked;
+        storage::update_ticket(&env, &ticket);
+        storage::remove_approved(&env, ticket_id);
+        storage::remove_pending_recovery(&env, ticket_id);
+        storage::mark_owner_event_ticket_spent(&env, &ticket.owner, &ticket.event_id);
+
+        events::emit_ticket_revoked(&env, ticket_id, ticket.event_id, ticket.owner, reason);
+
+        Ok(())
+    }
+
+    /// Move a valid ticket to another tier of its event. Capacity and price
+    /// differences are settled by the event contract before it calls this.
+    pub fn set_ticket_tier(env: Env, ticket_id: u64, tier_id: u32) -> Result<(), TicketError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.is_used || ticket.status != TicketStatus::Valid {
+            return Err(TicketError::TicketNotActive);
+        }
+
+        let from_tier_id = storage::get_ticket_tier(&env, ticket_id);
+        storage::set_ticket_tier(&env, ticket_id, tier_id);
+
+        events::emit_ticket_tier_changed(&env, ticket_id, ticket.event_id, from_tier_id, tier_id);
+
+        Ok(())
+    }
+
     pub fn set_recovery_key(
         env: Env,
         owner: Address,
@@ -299,39 +607,224 @@ impl TicketContract {
         Ok(())
     }
 
+    /// Set or clear a recovery key covering every ticket the owner holds,
+    /// including ones received later. A per-ticket key takes precedence.
+    pub fn set_owner_recovery_key(
+        env: Env,
+        owner: Address,
+        public_key: Option<BytesN<32>>,
+    ) -> Result<(), TicketError> {
+        owner.require_auth();
+
+        storage::set_owner_recovery_key(&env, &owner, &public_key);
+        events::emit_owner_recovery_key_set(&env, owner, public_key.is_some());
+
+        Ok(())
+    }
+
+    /// Current recovery nonce for a ticket; bumped by every recovery.
+    pub fn get_recovery_nonce(env: Env, ticket_id: u64) -> u64 {
+        storage::get_recovery_nonce(&env, ticket_id)
+    }
+
+    /// The message a recovery key must sign to move `ticket_id` to
+    /// `new_owner`. It binds the network, this contract, the ticket and its
+    /// current nonce, so a signature cannot be replayed on another ticket,
+    /// deployment or network, or reused after a recovery.
+    pub fn recovery_message(env: Env, ticket_id: u64, new_owner: Address) -> Bytes {
+        let nonce = storage::get_recovery_nonce(&env, ticket_id);
+        build_recovery_message(&env, ticket_id, &new_owner, nonce)
+    }
+
     pub fn recover_ticket(
         env: Env,
         ticket_id: u64,
         new_owner: Address,
         signature: BytesN<64>,
     ) -> Result<(), TicketError> {
-        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+        let ticket = storage::get_ticket(&env, ticket_id)?;
 
         if ticket.is_used || ticket.status != TicketStatus::Valid {
             return Err(TicketError::TicketNotTransferable);
         }
 
-        let public_key =
-            storage::get_recovery_key(&env, ticket_id).ok_or(TicketError::RecoveryKeyNotFound)?;
+        let public_key = storage::get_recovery_key(&env, ticket_id)
+            .or_else(|| storage::get_owner_recovery_key(&env, &ticket.owner))
+            .ok_or(TicketError::RecoveryKeyNotFound)?;
 
-        let message = new_owner.clone().to_xdr(&env);
+        let nonce = storage::get_recovery_nonce(&env, ticket_id);
+        let message = build_recovery_message(&env, ticket_id, &new_owner, nonce);
         env.crypto()
             .ed25519_verify(&public_key, &message, &signature);
 
-        let old_owner = ticket.owner.clone();
-        ticket.owner = new_owner.clone();
-        storage::update_ticket(&env, &ticket);
+        complete_recovery(&env, ticket, new_owner)
+    }
+
+    /// Configure the owner's social-recovery guardians for all their tickets.
+    ///
+    /// `threshold` approvals out of `guardians` are needed, and a recovery can
+    /// only execute `delay_ledgers` after it was initiated, during which the
+    /// owner may veto it.
+    pub fn set_recovery_guardians(
+        env: Env,
+        owner: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+        delay_ledgers: u32,
+    ) -> Result<(), TicketError> {
+        owner.require_auth();
+
+        if guardians.is_empty()
+            || guardians.len() > MAX_GUARDIANS
+            || threshold == 0
+            || threshold > guardians.len()
+            || delay_ledgers < MIN_RECOVERY_DELAY_LEDGERS
+        {
+            return Err(TicketError::InvalidGuardianSet);
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardian == owner || guardians.first_index_of(&guardian) != Some(i as u32) {
+                return Err(TicketError::InvalidGuardianSet);
+            }
+        }
+
+        let guardian_set = GuardianSet {
+            guardians,
+            threshold,
+            delay_ledgers,
+        };
+        storage::set_guardian_set(&env, &owner, &guardian_set);
+        events::emit_recovery_guardians_set(&env, owner, &guardian_set);
+
+        Ok(())
+    }
+
+    pub fn clear_recovery_guardians(env: Env, owner: Address) -> Result<(), TicketError> {
+        owner.require_auth();
+        storage::remove_guardian_set(&env, &owner);
+        Ok(())
+    }
+
+    pub fn get_recovery_guardians(env: Env, owner: Address) -> Option<GuardianSet> {
+        storage::get_guardian_set(&env, &owner)
+    }
 
-        // Use map-based indexing: remove from old owner, add to new owner
-        storage::remove_owner_ticket(&env, &old_owner, ticket_id);
-        storage::add_owner_ticket(&env, &new_owner, ticket_id);
-        storage::remove_recovery_key(&env, ticket_id);
+    /// Start a guardian recovery of `ticket_id` to `new_owner`. Counts as the
+    /// initiating guardian's approval.
+    pub fn initiate_recovery(
+        env: Env,
+        guardian: Address,
+        ticket_id: u64,
+        new_owner: Address,
+    ) -> Result<(), TicketError> {
+        guardian.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.is_used || ticket.status != TicketStatus::Valid {
+            return Err(TicketError::TicketNotTransferable);
+        }
+        if new_owner == ticket.owner {
+            return Err(TicketError::TransferToSelf);
+        }
+
+        let guardian_set = storage::get_guardian_set(&env, &ticket.owner)
+            .filter(|set| set.guardians.contains(&guardian))
+            .ok_or(TicketError::NotGuardian)?;
+
+        // A recovery raised against a previous holder has lapsed.
+        if storage::get_pending_recovery(&env, ticket_id)
+            .is_some_and(|pending| pending.owner == ticket.owner)
+        {
+            return Err(TicketError::RecoveryAlreadyPending);
+        }
+
+        let executable_at_ledger = env
+            .ledger()
+            .sequence()
+            .saturating_add(guardian_set.delay_ledgers);
+        let pending = PendingRecovery {
+            owner: ticket.owner,
+            new_owner: new_owner.clone(),
+            approvals: Vec::from_array(&env, [guardian.clone()]),
+            executable_at_ledger,
+        };
+        storage::set_pending_recovery(&env, ticket_id, &pending);
+        events::emit_recovery_initiated(&env, ticket_id, guardian, new_owner, executable_at_ledger);
+
+        Ok(())
+    }
+
+    pub fn approve_recovery(
+        env: Env,
+        guardian: Address,
+        ticket_id: u64,
+    ) -> Result<(), TicketError> {
+        guardian.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        let mut pending = live_pending_recovery(&env, &ticket)?;
+
+        let is_guardian = storage::get_guardian_set(&env, &ticket.owner)
+            .is_some_and(|set| set.guardians.contains(&guardian));
+        if !is_guardian {
+            return Err(TicketError::NotGuardian);
+        }
+
+        if !pending.approvals.contains(&guardian) {
+            pending.approvals.push_back(guardian.clone());
+            storage::set_pending_recovery(&env, ticket_id, &pending);
+            events::emit_recovery_approved(&env, ticket_id, guardian, pending.approvals.len());
+        }
+
+        Ok(())
+    }
+
+    /// Execute a guardian recovery once it has enough approvals from the
+    /// owner's current guardians and its delay has passed. Callable by anyone.
+    pub fn execute_recovery(env: Env, ticket_id: u64) -> Result<(), TicketError> {
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.is_used || ticket.status != TicketStatus::Valid {
+            return Err(TicketError::TicketNotTransferable);
+        }
+
+        let pending = live_pending_recovery(&env, &ticket)?;
+        let guardian_set =
+            storage::get_guardian_set(&env, &ticket.owner).ok_or(TicketError::NotGuardian)?;
+
+        let approvals = pending
+            .approvals
+            .iter()
+            .filter(|guardian| guardian_set.guardians.contains(guardian))
+            .count() as u32;
+        if approvals < guardian_set.threshold
+            || env.ledger().sequence() < pending.executable_at_ledger
+        {
+            return Err(TicketError::RecoveryNotReady);
+        }
+
+        complete_recovery(&env, ticket, pending.new_owner)
+    }
+
+    /// Cancel a pending guardian recovery of one of the caller's tickets.
+    pub fn veto_recovery(env: Env, owner: Address, ticket_id: u64) -> Result<(), TicketError> {
+        owner.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.owner != owner {
+            return Err(TicketError::Unauthorized);
+        }
+        live_pending_recovery(&env, &ticket)?;
 
-        events::emit_ticket_recovered(&env, ticket_id, old_owner, new_owner);
+        storage::remove_pending_recovery(&env, ticket_id);
+        events::emit_recovery_vetoed(&env, ticket_id, owner);
 
         Ok(())
     }
 
+    pub fn get_pending_recovery(env: Env, ticket_id: u64) -> Option<PendingRecovery> {
+        storage::get_pending_recovery(&env, ticket_id)
+    }
+
     pub fn initialize(
         env: Env,
         admin: Address,
@@ -374,6 +867,130 @@ impl TicketContract {
         Ok(())
     }
 
+    /// Record an event's transfer policy and lock window. Only the linked
+    /// event contract may call this; it re-syncs whenever the policy or the
+    /// event start ledger changes.
+    pub fn sync_transfer_policy(
+        env: Env,
+        event_id: Symbol,
+        policy: TransferPolicy,
+        lock_ledgers: u32,
+        event_start_ledger: u32,
+    ) -> Result<(), TicketError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        let config = EventTransferConfig {
+            policy,
+            lock_ledgers,
+            event_start_ledger,
+        };
+        storage::set_event_transfer_config(&env, &event_id, &config);
+        events::emit_transfer_policy_synced(&env, event_id, config);
+
+        Ok(())
+    }
+    pub fn get_event_transfer_config(env: Env, event_id: Symbol) -> Option<EventTransferConfig> {
+        storage::get_event_transfer_config(&env, &event_id)
+    }
+
+    /// Record that an event completed or was cancelled, starting the grace
+    /// period before its tickets can be archived. Only the linked event
+    /// contract may call this; repeat calls keep the first closure.
+    pub fn close_event(env: Env, event_id: Symbol, cancelled: bool) -> Result<(), TicketError> {
+        let event_contract = storage::get_event_contract(&env)?;
+        event_contract.require_auth();
+
+        if storage::get_event_archive(&env, &event_id).is_some() {
+            return Ok(());
+        }
+
+        storage::set_event_archive(
+            &env,
+            &EventArchive {
+                event_id: event_id.clone(),
+                cancelled,
+                closed_at_ledger: env.ledger().sequence(),
+                total_tickets: storage::get_event_tickets_count(&env, &event_id),
+                archived_tickets: 0,
+                used_tickets: 0,
+                expired_tickets: 0,
+                issuance_digest: BytesN::from_array(&env, &[0; 32]),
+                archived_at_ledger: None,
+            },
+        );
+        events::emit_event_closed(&env, event_id, cancelled);
+
+        Ok(())
+    }
+
+    /// Archive up to `limit` tickets of a closed event, starting at `cursor`
+    /// in the event's ticket index. Anyone may call this once
+    /// `ARCHIVE_GRACE_LEDGERS` have passed since the event closed.
+    ///
+    /// Unused valid tickets become `Expired`. Every archived ticket is folded
+    /// into the event's issuance digest and dropped from the owner,
+    /// (owner, event) and event indexes, and expired records are no longer
+    /// bumped. Its metadata, tier, approval, recovery and transfer-policy
+    /// entries are deleted. Returns the cursor for the next call, or `None`
+    /// once the event is fully archived.
+    pub fn archive_event_tickets(
+        env: Env,
+        event_id: Symbol,
+        cursor: u64,
+        limit: u32,
+    ) -> Result<Option<u64>, TicketError> {
+        let mut archive =
+            storage::get_event_archive(&env, &event_id).ok_or(TicketError::EventNotClosed)?;
+        if archive.archived_at_ledger.is_some() {
+            return Err(TicketError::EventAlreadyArchived);
+        }
+        if env.ledger().sequence()
+            < archive
+                .closed_at_ledger
+                .saturating_add(ARCHIVE_GRACE_LEDGERS)
+        {
+            return Err(TicketError::ArchiveGracePeriodActive);
+        }
+        // Tickets are folded into the digest in index order, so batches must
+        // resume exactly where the previous one stopped.
+        if cursor != archive.archived_tickets {
+            return Err(TicketError::InvalidInput);
+        }
+
+        archive.total_tickets = storage::get_event_tickets_count(&env, &event_id);
+        let end = cursor
+            .saturating_add(page_limit(limit))
+            .min(archive.total_tickets);
+        for index in cursor..end {
+            if let Some(ticket_id) = storage::take_event_ticket_index(&env, &event_id, index) {
+                archive_ticket(&env, &mut archive, ticket_id);
+            }
+        }
+        archive.archived_tickets = end;
+
+        let next_cursor = if end < archive.total_tickets {
+            Some(end)
+        } else {
+            storage::remove_event_tickets_count(&env, &event_id);
+            archive.archived_at_ledger = Some(env.ledger().sequence());
+            events::emit_event_archived(
+                &env,
+                event_id,
+                archive.archived_tickets,
+                archive.issuance_digest.clone(),
+            );
+            None
+        };
+        storage::set_event_archive(&env, &archive);
+
+        Ok(next_cursor)
+    }
+
+    pub fn get_event_archive(env: Env, event_id: Symbol) -> Option<EventArchive> {
+        storage::get_event_archive(&env, &event_id)
+    }
+
     pub fn admin_transfer_ticket(
         env: Env,
         admin: Address,
@@ -398,18 +1015,218 @@ impl TicketContract {
             return Err(TicketError::TicketNotTransferable);
         }
 
+        check_transfer_allowed(&env, &ticket, true)?;
+
+        ticket.owner = to.clone();
+        storage::update_ticket(&env, &ticket);
+
+        reindex_owner(&env, ticket_id, &ticket.event_id, &from, &to);
+
+        events::emit_ticket_transferred(&env, ticket_id, ticket.event_id.clone(), from, to);
+
+        Ok(())
+    }
+
+    pub fn name(env: Env) -> String {
+        String::from_str(&env, TOKEN_NAME)
+    }
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, TOKEN_SYMBOL)
+    }
+    pub fn balance_of(env: Env, owner: Address) -> u64 {
+        storage::get_owner_tickets_count(&env, &owner)
+    }
+    pub fn owner_of(env: Env, ticket_id: u64) -> Result<Address, TicketError> {
+        Ok(storage::get_ticket(&env, ticket_id)?.owner)
+    }
+
+    /// Approve `approved` to transfer a single ticket until `expiration_ledger`
+    /// (inclusive), or clear the approval with `None`. The owner or one of the
+    /// owner's operators may call this. Approvals are cleared whenever the
+    /// ticket is transferred, used or cancelled.
+    pub fn approve(
+        env: Env,
+        caller: Address,
+        approved: Option<Address>,
+        ticket_id: u64,
+        expiration_ledger: u32,
+    ) -> Result<(), TicketError> {
+        caller.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if caller != ticket.owner && !storage::is_operator(&env, &ticket.owner, &caller) {
+            return Err(TicketError::NotApprovedOrOwner);
+        }
+
+        match &approved {
+            Some(spender) => {
+                if *spender == ticket.owner {
+                    return Err(TicketError::InvalidInput);
+                }
+                if ticket.is_used || ticket.status != TicketStatus::Valid {
+                    return Err(TicketError::TicketNotTransferable);
+                }
+                if expiration_ledger < env.ledger().sequence() {
+                    return Err(TicketError::InvalidExpirationLedger);
+                }
+                let grant = ApprovalGrant {
+                    spender: spender.clone(),
+                    expiration_ledger,
+                };
+                storage::set_approved(&env, ticket_id, &grant);
+            }
+            None => storage::remove_approved(&env, ticket_id),
+        }
+        events::emit_ticket_approval(&env, ticket_id, ticket.owner, approved, expiration_ledger);
+
+        Ok(())
+    }
+    pub fn get_approved(env: Env, ticket_id: u64) -> Option<Address> {
+        storage::get_approved(&env, ticket_id).map(|grant| grant.spender)
+    }
+    pub fn get_approval(env: Env, ticket_id: u64) -> Option<ApprovalGrant> {
+        storage::get_approved(&env, ticket_id)
+    }
+
+    /// Grant `operator` the right to manage all of `owner`'s tickets until
+    /// `expiration_ledger` (inclusive), or revoke it with `approved = false`.
+    pub fn set_approval_for_all(
+        env: Env,
+        owner: Address,
+        operator: Address,
+        approved: bool,
+        expiration_ledger: u32,
+    ) -> Result<(), TicketError> {
+        owner.require_auth();
+
+        if owner == operator {
+            return Err(TicketError::InvalidInput);
+        }
+
+        if approved {
+            if expiration_ledger < env.ledger().sequence() {
+                return Err(TicketError::InvalidExpirationLedger);
+            }
+            storage::set_operator(&env, &owner, &operator, expiration_ledger);
+        } else {
+            storage::remove_operator(&env, &owner, &operator);
+        }
+        events::emit_approval_for_all(&env, owner, operator, approved, expiration_ledger);
+
+        Ok(())
+    }
+    pub fn is_approved_for_all(env: Env, owner: Address, operator: Address) -> bool {
+        storage::is_operator(&env, &owner, &operator)
+    }
+
+    /// Transfer a ticket as the owner, its approved address or an operator.
+    ///
+    /// The same restrictions as `transfer_ticket` apply. Expired approvals and
+    /// operator grants are ignored.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        ticket_id: u64,
+    ) -> Result<(), TicketError> {
+        spender.require_auth();
+
+        if from == to {
+            return Err(TicketError::TransferToSelf);
+        }
+
+        let mut ticket = storage::get_ticket(&env, ticket_id)?;
+
+        if ticket.owner != from {
+            return Err(TicketError::Unauthorized);
+        }
+
+        if spender != from
+            && storage::get_approved(&env, ticket_id).map(|grant| grant.spender)
+                != Some(spender.clone())
+            && !storage::is_operator(&env, &from, &spender)
+        {
+            return Err(TicketError::NotApprovedOrOwner);
+        }
+
+        if !ticket.is_transferable || ticket.is_used || ticket.status != TicketStatus::Valid {
+            return Err(TicketError::TicketNotTransferable);
+        }
+
+        check_transfer_allowed(&env, &ticket, false)?;
+
         ticket.owner = to.clone();
         storage::update_ticket(&env, &ticket);
 
-        // Use map-based indexing: remove from old owner, add to new owner
-        storage::remove_owner_ticket(&env, &from, ticket_id);
-        storage::add_owner_ticket(&env, &to, ticket_id);
+        reindex_owner(&env, ticket_id, &ticket.event_id, &from, &to);
 
         events::emit_ticket_transferred(&env, ticket_id, ticket.event_id.clone(), from, to);
 
         Ok(())
     }
 
+    pub fn set_base_uri(env: Env, admin: Address, base_uri: String) -> Result<(), TicketError> {
+        admin.require_auth();
+        let stored_admin = storage::get_admin(&env)?;
+        if admin != stored_admin {
+            return Err(TicketError::Unauthorized);
+        }
+        if base_uri.len() > MAX_BASE_URI_LEN {
+            return Err(TicketError::MetadataTooLong);
+        }
+        storage::set_base_uri(&env, &base_uri);
+        Ok(())
+    }
+
+    /// Attach seat information to a ticket. Organizer only.
+    pub fn set_ticket_metadata(
+        env: Env,
+        organizer: Address,
+        ticket_id: u64,
+        seat: String,
+    ) -> Result<(), TicketError> {
+        organizer.require_auth();
+
+        let ticket = storage::get_ticket(&env, ticket_id)?;
+        if ticket.organizer != organizer {
+            return Err(TicketError::Unauthorized);
+        }
+        if seat.len() > MAX_SEAT_LEN {
+            return Err(TicketError::MetadataTooLong);
+        }
+
+        storage::set_ticket_metadata(&env, ticket_id, &TicketMetadata { seat });
+        Ok(())
+    }
+    pub fn get_ticket_metadata(env: Env, ticket_id: u64) -> Option<TicketMetadata> {
+        storage::get_ticket_metadata(&env, ticket_id)
+    }
+
+    /// Render `{base_uri}{ticket_id}`, followed by `?tier={tier_id}&seat={seat}`
+    /// when the organizer has attached a seat to the ticket. The tier is the
+    /// ticket's current one, so it follows upgrades and downgrades.
+    pub fn token_uri(env: Env, ticket_id: u64) -> Result<String, TicketError> {
+        storage::get_ticket(&env, ticket_id)?;
+
+        let mut uri = match storage::get_base_uri(&env) {
+            Some(base_uri) => base_uri.to_bytes(),
+            None => Bytes::new(&env),
+        };
+        append_decimal(&mut uri, ticket_id);
+        if let Some(metadata) = storage::get_ticket_metadata(&env, ticket_id) {
+            uri.extend_from_slice(b"?tier=");
+            append_decimal(&mut uri, storage::get_ticket_tier(&env, ticket_id) as u64);
+            uri.extend_from_slice(b"&seat=");
+            uri.append(&metadata.seat.to_bytes());
+        }
+
+        let len = uri.len() as usize;
+        let mut buf = [0u8; MAX_TOKEN_URI_LEN];
+        uri.copy_into_slice(&mut buf[..len]);
+        Ok(String::from_bytes(&env, &buf[..len]))
+    }
+
     /// Get the current contract version.
     pub fn contract_version(env: Env) -> u32 {
         storage::get_contract_version(&env)
@@ -447,6 +1264,149 @@ impl TicketContract {
     }
 }
 
+fn build_recovery_message(env: &Env, ticket_id: u64, new_owner: &Address, nonce: u64) -> Bytes {
+    let mut message = Bytes::from_slice(env, RECOVERY_DOMAIN);
+    message.extend_from_slice(&env.ledger().network_id().to_array());
+    message.append(&env.current_contract_address().to_xdr(env));
+    message.extend_from_slice(&ticket_id.to_be_bytes());
+    message.extend_from_slice(&nonce.to_be_bytes());
+    message.append(&new_owner.clone().to_xdr(env));
+    message
+}
+
+/// SHA-256 over the attestation fields, bound to the network and this
+/// contract. The `digest` field itself is not part of the preimage.
+fn attestation_digest(env: &Env, attestation: &TicketAttestation) -> BytesN<32> {
+    let mut preimage = Bytes::from_slice(env, ATTESTATION_DOMAIN);
+    preimage.extend_from_slice(&env.ledger().network_id().to_array());
+    preimage.append(&env.current_contract_address().to_xdr(env));
+    preimage.extend_from_slice(&attestation.ticket_id.to_be_bytes());
+    preimage.append(&attestation.event_id.clone().to_xdr(env));
+    preimage.extend_from_slice(&attestation.tier_id.to_be_bytes());
+    preimage.append(&attestation.holder.clone().to_xdr(env));
+    preimage.extend_from_slice(&attestation.issued_at_ledger.to_be_bytes());
+    preimage.extend_from_slice(&attestation.expires_at_ledger.to_be_bytes());
+    env.crypto().sha256(&preimage).into()
+}
+
+/// The ticket's pending recovery, if it was raised against the current owner.
+fn live_pending_recovery(env: &Env, ticket: &Ticket) -> Result<PendingRecovery, TicketError> {
+    storage::get_pending_recovery(env, ticket.ticket_id)
+        .filter(|pending| pending.owner == ticket.owner)
+        .ok_or(TicketError::RecoveryNotPending)
+}
+
+fn complete_recovery(env: &Env, mut ticket: Ticket, new_owner: Address) -> Result<(), TicketError> {
+    if new_owner == ticket.owner {
+        return Err(TicketError::TransferToSelf);
+    }
+
+    let ticket_id = ticket.ticket_id;
+    let old_owner = ticket.owner.clone();
+    ticket.owner = new_owner.clone();
+    storage::update_ticket(env, &ticket);
+
+    reindex_owner(env, ticket_id, &ticket.event_id, &old_owner, &new_owner);
+
+    events::emit_ticket_recovered(env, ticket_id, old_owner, new_owner);
+
+    Ok(())
+}
+
+/// Move a ticket between owners in every owner-keyed index and drop any
+/// single-ticket approval. Recovery rights belong to the holder, so the
+/// ticket's recovery key and pending recovery are dropped and the nonce bumped
+/// to void signatures the previous holder already produced.
+fn reindex_owner(env: &Env, ticket_id: u64, event_id: &Symbol, from: &Address, to: &Address) {
+    storage::remove_owner_ticket(env, from, ticket_id);
+    storage::add_owner_ticket(env, to, ticket_id);
+    storage::remove_owner_event_ticket(env, from, event_id, ticket_id);
+    storage::add_owner_event_ticket(env, to, event_id, ticket_id);
+    storage::remove_approved(env, ticket_id);
+    storage::remove_recovery_key(env, ticket_id);
+    storage::remove_pending_recovery(env, ticket_id);
+    storage::increment_recovery_nonce(env, ticket_id);
+}
+
+/// Expire an unused ticket, fold its final record into the event's issuance
+/// digest and drop everything that only served live tickets.
+fn archive_ticket(env: &Env, archive: &mut EventArchive, ticket_id: u64) {
+    let Some(mut ticket) = storage::load_ticket(env, ticket_id) else {
+        return;
+    };
+
+    if ticket.status == TicketStatus::Valid && !ticket.is_used {
+        ticket.status = TicketStatus::Expired;
+        storage::update_ticket(env, &ticket);
+        archive.expired_tickets += 1;
+    }
+    if ticket.is_used {
+        archive.used_tickets += 1;
+    }
+
+    let mut preimage = Bytes::from_array(env, &archive.issuance_digest.to_array());
+    preimage.append(&ticket.clone().to_xdr(env));
+    archive.issuance_digest = env.crypto().sha256(&preimage).into();
+
+    storage::remove_owner_ticket(env, &ticket.owner, ticket_id);
+    storage::remove_owner_event_record(env, &ticket.owner, &ticket.event_id);
+    storage::remove_approved(env, ticket_id);
+    storage::remove_recovery_key(env, ticket_id);
+    storage::remove_pending_recovery(env, ticket_id);
+    storage::remove_recovery_nonce(env, ticket_id);
+    storage::remove_ticket_metadata(env, ticket_id);
+    storage::remove_ticket_tier(env, ticket_id);
+    storage::remove_ticket_transfer_policy(env, ticket_id);
+}
+
+fn page_limit(limit: u32) -> u64 {
+    u64::from(limit.min(MAX_PAGE_SIZE))
+}
+
+fn ticket_matches(env: &Env, ticket_id: u64, status: &Option<TicketStatus>) -> bool {
+    match status {
+        None => true,
+        Some(status) => storage::get_ticket(env, ticket_id)
+            .map(|ticket| ticket.status == *status)
+            .unwrap_or(false),
+    }
+}
+
+fn event_transfer_policy(env: &Env, event_id: &Symbol) -> TransferPolicy {
+    storage::get_event_transfer_config(env, event_id)
+        .map(|config| config.policy)
+        .unwrap_or(TransferPolicy::Open)
+}
+
+/// Apply the ticket's recorded transfer policy and the event's lock window.
+/// `via_marketplace` is true for resale settlements from the payments contract.
+fn check_transfer_allowed(
+    env: &Env,
+    ticket: &Ticket,
+    via_marketplace: bool,
+) -> Result<(), TicketError> {
+    let allowed = match storage::get_ticket_transfer_policy(env, ticket.ticket_id) {
+        TransferPolicy::Open => true,
+        TransferPolicy::Disallowed => false,
+        TransferPolicy::FreeOnly => !via_marketplace,
+        TransferPolicy::MarketplaceOnly => via_marketplace,
+    };
+    if !allowed {
+        return Err(TicketError::TransferPolicyViolation);
+    }
+
+    if let Some(config) = storage::get_event_transfer_config(env, &ticket.event_id) {
+        let lock_ledger = config
+            .event_start_ledger
+            .saturating_sub(config.lock_ledgers);
+        if config.lock_ledgers > 0 && env.ledger().sequence() >= lock_ledger {
+            return Err(TicketError::TransferLocked);
+        }
+    }
+
+    Ok(())
+}
+
 fn read_next_ticket_id(env: &Env) -> u64 {
     let key = DataKey::NextTicketId;
     let id: Option<u64> = env.storage().persistent().get(&key);
@@ -467,3 +1427,17 @@ fn write_next_ticket_id(env: &Env, next_id: u64) {
         .persistent()
         .extend_ttl(&key, storage::TTL_THRESHOLD, storage::TTL_BUMP);
 }
+
+fn append_decimal(bytes: &mut Bytes, mut value: u64) {
+    let mut digits = [0u8; 20];
+    let mut i = digits.len();
+    loop {
+        i -= 1;
+        digits[i] = b'0' + (value % 10) as u8;
+        value /= 10;
+        if value == 0 {
+            break;
+        }
+    }
+    bytes.extend_from_slice(&digits[i..]);
+}
@@ -123,9 +123,9 @@ mod tests {
         let owner = Address::generate(&env);
 
         // Mint multiple tickets
-        let ticket_id_1 = client.mint_ticket(&event_id, &organizer, &owner);
-        let ticket_id_2 = client.mint_ticket(&event_id, &organizer, &owner);
-        let ticket_id_3 = client.mint_ticket(&event_id, &organizer, &owner);
+        let ticket_id_1 = client.mint_ticket(&event_id, &organizer, &owner, &0);
+        let ticket_id_2 = client.mint_ticket(&event_id, &organizer, &owner, &0);
+        let ticket_id_3 = client.mint_ticket(&event_id, &organizer, &owner, &0);
 
         // Verify map-based storage is being used correctly
         // Note: This test validates functionality, not gas costs.
@@ -153,7 +153,7 @@ mod tests {
         let owner2 = Address::generate(&env);
 
         // Mint a ticket
-        let ticket_id = client.mint_ticket(&event_id, &organizer, &owner1);
+        let ticket_id = client.mint_ticket(&event_id, &organizer, &owner1, &0);
 
         // Verify initial ownership
         let contract_id = client.address.clone();
@@ -181,7 +181,7 @@ mod tests {
         let owner2 = Address::generate(&env);
 
         // Mint a ticket
-        let ticket_id = client.mint_ticket(&event_id, &organizer, &owner1);
+        let ticket_id = client.mint_ticket(&event_id, &organizer, &owner1, &0);
 
         // Generate valid Ed25519 keypair for recovery
         let keypair_bytes = env.crypto().sha256(&owner2.clone().to_xdr(&env));
@@ -234,7 +234,7 @@ mod tests {
         client.initialize(&admin, &payments_contract);
 
         // Mint a ticket
-        let ticket_id = client.mint_ticket(&event_id, &organizer, &owner1);
+        let ticket_id = client.mint_ticket(&event_id, &organizer, &owner1, &0);
 
         // Verify initial state
         let contract_id = client.address.clone();
@@ -1,7 +1,11 @@
-use soroban_sdk::{contracttype, Address, BytesN, Env, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Symbol, Vec};
 
 use crate::errors::TicketError;
-use crate::types::Ticket;
+use crate::types::{
+    AnonymousAttendanceBadge, ApprovalGrant, AttendanceBadge, EventArchive, EventTransferConfig,
+    GuardianSet, OwnerEventTickets, PendingRecovery, Ticket, TicketMetadata, TicketPage,
+    TicketStatus, TransferPolicy,
+};
 
 /// TTL refresh threshold in ledgers (~30 days at 5s/ledger).
 pub const TTL_THRESHOLD: u32 = 518_400;
@@ -23,6 +27,12 @@ pub enum DataKey {
     ContractVersion,
     Admin,
     RecoveryKey(u64),
+    /// Recovery key covering every ticket held by an owner
+    OwnerRecoveryKey(Address),
+    /// Per-ticket counter bound into recovery signatures to stop replays
+    RecoveryNonce(u64),
+    RecoveryGuardians(Address),
+    PendingRecovery(u64),
     PaymentsContract,
     EventContract,
     /// Indexed storage for owner tickets
@@ -31,27 +41,66 @@ pub enum DataKey {
     /// Indexed storage for event tickets
     EventTicketIndex(Symbol, u64),
     EventTicketsCount(Symbol),
+    /// Soulbound attendance badge for a holder at an event
+    Badge(Symbol, Address),
+    /// Indexed storage for the events a holder has attended
+    HolderBadgeIndex(Address, u64),
+    HolderBadgesCount(Address),
+    /// Commitment-based attendance badge for Anonymous events
+    AnonymousBadge(Symbol, BytesN<32>),
+    /// Single address approved to transfer a ticket on the owner's behalf
+    Approved(u64),
+    /// Owner-wide operator grant: (owner, operator) -> expiration ledger
+    Operator(Address, Address),
+    /// Prefix for `token_uri`
+    BaseUri,
+    TicketMetadata(u64),
+    /// Transfer policy and lock window synced from the event contract
+    EventTransferConfig(Symbol),
+    /// An owner's tickets to a single event plus how many of them are valid.
+    /// Kept as one entry so minting and transfers touch a single extra key.
+    OwnerEventTickets(Address, Symbol),
+    /// Closure and archival summary for a completed or cancelled event
+    EventArchive(Symbol),
+    /// Digest of an issued ticket attestation (temporary storage)
+    Attestation(BytesN<32>),
+    /// Event transfer policy a ticket was minted under. Absent means `Open`,
+    /// which also covers tickets minted before policies existed.
+    TicketTransferPolicy(u64),
+    /// Event tier a ticket currently belongs to. Absent means tier 0, which
+    /// also covers tickets minted before tiers were tracked.
+    TicketTier(u64),
 }
 
 pub fn get_ticket(env: &Env, ticket_id: u64) -> Result<Ticket, TicketError> {
     let key = DataKey::Ticket(ticket_id);
-    let ticket = env
+    let ticket: Ticket = env
         .storage()
         .persistent()
         .get(&key)
         .ok_or(TicketError::TicketNotFound)?;
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    // Expired tickets belong to archived events and are left to lapse.
+    if ticket.status != TicketStatus::Expired {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
     Ok(ticket)
 }
 
+/// Read a ticket without refreshing its TTL
+pub fn load_ticket(env: &Env, ticket_id: u64) -> Option<Ticket> {
+    env.storage().persistent().get(&DataKey::Ticket(ticket_id))
+}
+
 pub fn update_ticket(env: &Env, ticket: &Ticket) {
     let key = DataKey::Ticket(ticket.ticket_id);
     env.storage().persistent().set(&key, ticket);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    if ticket.status != TicketStatus::Expired {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
 }
 
 /// Add an owner-ticket relationship (map-based)
@@ -197,6 +246,26 @@ pub fn get_event_tickets_count(env: &Env, event_id: &Symbol) -> u64 {
     count.unwrap_or(0)
 }
 
+/// Remove and return the event index entry at `index`, along with the
+/// matching event-ticket membership. Later positions are not shifted.
+pub fn take_event_ticket_index(env: &Env, event_id: &Symbol, index: u64) -> Option<u64> {
+    let idx_key = DataKey::EventTicketIndex(event_id.clone(), index);
+    let ticket_id: Option<u64> = env.storage().persistent().get(&idx_key);
+    if let Some(ticket_id) = ticket_id {
+        env.storage().persistent().remove(&idx_key);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::EventTicket(event_id.clone(), ticket_id));
+    }
+    ticket_id
+}
+
+pub fn remove_event_tickets_count(env: &Env, event_id: &Symbol) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::EventTicketsCount(event_id.clone()));
+}
+
 /// Get the count of tickets owned by an address
 pub fn get_owner_tickets_count(env: &Env, owner: &Address) -> u64 {
     let key = DataKey::OwnerTicketsCount(owner.clone());
@@ -209,6 +278,186 @@ pub fn get_owner_tickets_count(env: &Env, owner: &Address) -> u64 {
     count.unwrap_or(0)
 }
 
+fn get_owner_event_record(env: &Env, owner: &Address, event_id: &Symbol) -> OwnerEventTickets {
+    let key = DataKey::OwnerEventTickets(owner.clone(), event_id.clone());
+    let record: Option<OwnerEventTickets> = env.storage().persistent().get(&key);
+    match record {
+        Some(record) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+            record
+        }
+        None => OwnerEventTickets {
+            ticket_ids: Vec::new(env),
+            valid_count: 0,
+        },
+    }
+}
+
+fn set_owner_event_record(
+    env: &Env,
+    owner: &Address,
+    event_id: &Symbol,
+    record: &OwnerEventTickets,
+) {
+    let key = DataKey::OwnerEventTickets(owner.clone(), event_id.clone());
+    if record.ticket_ids.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, record);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+}
+
+/// Add a valid ticket to the owner's (owner, event) index
+pub fn add_owner_event_ticket(env: &Env, owner: &Address, event_id: &Symbol, ticket_id: u64) {
+    let mut record = get_owner_event_record(env, owner, event_id);
+    record.ticket_ids.push_back(ticket_id);
+    record.valid_count = record.valid_count.saturating_add(1);
+    set_owner_event_record(env, owner, event_id, &record);
+}
+
+/// Remove a valid ticket from the owner's (owner, event) index
+pub fn remove_owner_event_ticket(env: &Env, owner: &Address, event_id: &Symbol, ticket_id: u64) {
+    let mut record = get_owner_event_record(env, owner, event_id);
+    if let Some(idx) = record.ticket_ids.first_index_of(ticket_id) {
+        record.ticket_ids.remove(idx);
+        record.valid_count = record.valid_count.saturating_sub(1);
+        set_owner_event_record(env, owner, event_id, &record);
+    }
+}
+
+/// Drop the owner's whole (owner, event) index entry
+pub fn remove_owner_event_record(env: &Env, owner: &Address, event_id: &Symbol) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::OwnerEventTickets(owner.clone(), event_id.clone()));
+}
+
+/// Record that one of the owner's tickets to the event was used or cancelled.
+/// The ticket stays in the index; only the valid count drops.
+pub fn mark_owner_event_ticket_spent(env: &Env, owner: &Address, event_id: &Symbol) {
+    let mut record = get_owner_event_record(env, owner, event_id);
+    if record.valid_count > 0 {
+        record.valid_count -= 1;
+        set_owner_event_record(env, owner, event_id, &record);
+    }
+}
+
+/// Get the count of an owner's tickets to a single event
+pub fn get_owner_event_tickets_count(env: &Env, owner: &Address, event_id: &Symbol) -> u64 {
+    get_owner_event_record(env, owner, event_id)
+        .ticket_ids
+        .len() as u64
+}
+
+/// Get the number of valid tickets an owner holds for an event
+pub fn get_valid_ticket_count(env: &Env, owner: &Address, event_id: &Symbol) -> u32 {
+    get_owner_event_record(env, owner, event_id).valid_count
+}
+
+/// Read up to `limit` entries of an index starting at `cursor`. The scan is
+/// bounded by `limit`, so filtered pages may hold fewer ids than `limit`.
+fn read_index_page<F, P>(
+    env: &Env,
+    count: u64,
+    cursor: u64,
+    limit: u64,
+    index_key: F,
+    mut keep: P,
+) -> TicketPage
+where
+    F: Fn(u64) -> DataKey,
+    P: FnMut(u64) -> bool,
+{
+    let end = cursor.saturating_add(limit).min(count);
+    let mut ticket_ids = Vec::new(env);
+
+    for i in cursor..end {
+        let idx_key = index_key(i);
+        if let Some(ticket_id) = env.storage().persistent().get::<DataKey, u64>(&idx_key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(&idx_key, TTL_THRESHOLD, TTL_BUMP);
+            if keep(ticket_id) {
+                ticket_ids.push_back(ticket_id);
+            }
+        }
+    }
+
+    TicketPage {
+        ticket_ids,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+/// Get a page of an owner's tickets, optionally only those matching `keep`
+pub fn get_owner_tickets_page<P: FnMut(u64) -> bool>(
+    env: &Env,
+    owner: &Address,
+    cursor: u64,
+    limit: u64,
+    keep: P,
+) -> TicketPage {
+    let count = get_owner_tickets_count(env, owner);
+    read_index_page(
+        env,
+        count,
+        cursor,
+        limit,
+        |i| DataKey::OwnerTicketIndex(owner.clone(), i),
+        keep,
+    )
+}
+
+/// Get a page of an owner's tickets to a single event
+pub fn get_owner_event_tickets_page<P: FnMut(u64) -> bool>(
+    env: &Env,
+    owner: &Address,
+    event_id: &Symbol,
+    cursor: u64,
+    limit: u64,
+    mut keep: P,
+) -> TicketPage {
+    let ticket_ids = get_owner_event_record(env, owner, event_id).ticket_ids;
+    let count = ticket_ids.len() as u64;
+    let end = cursor.saturating_add(limit).min(count);
+    let mut page = Vec::new(env);
+    for i in cursor..end {
+        let ticket_id = ticket_ids.get_unchecked(i as u32);
+        if keep(ticket_id) {
+            page.push_back(ticket_id);
+        }
+    }
+
+    TicketPage {
+        ticket_ids: page,
+        next_cursor: if end < count { Some(end) } else { None },
+    }
+}
+
+/// Get a page of an event's tickets, optionally only those matching `keep`
+pub fn get_event_tickets_page<P: FnMut(u64) -> bool>(
+    env: &Env,
+    event_id: &Symbol,
+    cursor: u64,
+    limit: u64,
+    keep: P,
+) -> TicketPage {
+    let count = get_event_tickets_count(env, event_id);
+    read_index_page(
+        env,
+        count,
+        cursor,
+        limit,
+        |i| DataKey::EventTicketIndex(event_id.clone(), i),
+        keep,
+    )
+}
+
 /// Get all tickets by owner using indexed storage
 pub fn get_tickets_by_owner(env: &Env, owner: Address) -> Vec<u64> {
     let count = get_owner_tickets_count(env, &owner);
@@ -292,6 +541,106 @@ pub fn remove_recovery_key(env: &Env, ticket_id: u64) {
         .remove(&DataKey::RecoveryKey(ticket_id));
 }
 
+pub fn get_owner_recovery_key(env: &Env, owner: &Address) -> Option<BytesN<32>> {
+    let key = DataKey::OwnerRecoveryKey(owner.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    value
+}
+
+pub fn set_owner_recovery_key(env: &Env, owner: &Address, public_key: &Option<BytesN<32>>) {
+    let key = DataKey::OwnerRecoveryKey(owner.clone());
+    match public_key {
+        Some(public_key) => {
+            env.storage().persistent().set(&key, public_key);
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+pub fn get_recovery_nonce(env: &Env, ticket_id: u64) -> u64 {
+    let key = DataKey::RecoveryNonce(ticket_id);
+    let nonce: Option<u64> = env.storage().persistent().get(&key);
+    if nonce.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    nonce.unwrap_or(0)
+}
+
+pub fn increment_recovery_nonce(env: &Env, ticket_id: u64) {
+    let key = DataKey::RecoveryNonce(ticket_id);
+    let nonce = get_recovery_nonce(env, ticket_id);
+    env.storage().persistent().set(&key, &(nonce + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_recovery_nonce(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RecoveryNonce(ticket_id));
+}
+
+pub fn get_guardian_set(env: &Env, owner: &Address) -> Option<GuardianSet> {
+    let key = DataKey::RecoveryGuardians(owner.clone());
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    value
+}
+
+pub fn set_guardian_set(env: &Env, owner: &Address, guardians: &GuardianSet) {
+    let key = DataKey::RecoveryGuardians(owner.clone());
+    env.storage().persistent().set(&key, guardians);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_guardian_set(env: &Env, owner: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::RecoveryGuardians(owner.clone()));
+}
+
+pub fn get_pending_recovery(env: &Env, ticket_id: u64) -> Option<PendingRecovery> {
+    let key = DataKey::PendingRecovery(ticket_id);
+    let value = env.storage().persistent().get(&key);
+    if value.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    value
+}
+
+pub fn set_pending_recovery(env: &Env, ticket_id: u64, pending: &PendingRecovery) {
+    let key = DataKey::PendingRecovery(ticket_id);
+    env.storage().persistent().set(&key, pending);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_pending_recovery(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PendingRecovery(ticket_id));
+}
+
 pub fn get_payments_contract(env: &Env) -> Result<Address, TicketError> {
     let key = DataKey::PaymentsContract;
     let address = env
@@ -355,3 +704,311 @@ pub fn set_admin(env: &Env, admin: &Address) {
         .persistent()
         .extend_ttl(&DataKey::Admin, TTL_THRESHOLD, TTL_BUMP);
 }
+
+pub fn get_badge(env: &Env, event_id: &Symbol, holder: &Address) -> Option<AttendanceBadge> {
+    let key = DataKey::Badge(event_id.clone(), holder.clone());
+    let badge = env.storage().persistent().get(&key);
+    if badge.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    badge
+}
+
+pub fn has_badge(env: &Env, event_id: &Symbol, holder: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Badge(event_id.clone(), holder.clone()))
+}
+
+/// Store a badge and append its event to the holder's attended-events index
+pub fn save_badge(env: &Env, badge: &AttendanceBadge) {
+    let key = DataKey::Badge(badge.event_id.clone(), badge.holder.clone());
+    env.storage().persistent().set(&key, badge);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+
+    let count = get_holder_badges_count(env, &badge.holder);
+    let idx_key = DataKey::HolderBadgeIndex(badge.holder.clone(), count);
+    env.storage().persistent().set(&idx_key, &badge.event_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&idx_key, TTL_THRESHOLD, TTL_BUMP);
+
+    let count_key = DataKey::HolderBadgesCount(badge.holder.clone());
+    env.storage().persistent().set(&count_key, &(count + 1));
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+/// Get the count of badges held by an address
+pub fn get_holder_badges_count(env: &Env, holder: &Address) -> u64 {
+    let key = DataKey::HolderBadgesCount(holder.clone());
+    let count: Option<u64> = env.storage().persistent().get(&key);
+    if count.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    count.unwrap_or(0)
+}
+
+/// Get a page of the events a holder has attended, in check-in order
+pub fn get_attended_events(env: &Env, holder: &Address, start: u64, limit: u64) -> Vec<Symbol> {
+    let count = get_holder_badges_count(env, holder);
+    let end = start.saturating_add(limit).min(count);
+    let mut events = Vec::new(env);
+
+    for i in start..end {
+        let idx_key = DataKey::HolderBadgeIndex(holder.clone(), i);
+        if let Some(event_id) = env.storage().persistent().get(&idx_key) {
+            env.storage()
+                .persistent()
+                .extend_ttl(&idx_key, TTL_THRESHOLD, TTL_BUMP);
+            events.push_back(event_id);
+        }
+    }
+
+    events
+}
+
+pub fn get_anonymous_badge(
+    env: &Env,
+    event_id: &Symbol,
+    commitment: &BytesN<32>,
+) -> Option<AnonymousAttendanceBadge> {
+    let key = DataKey::AnonymousBadge(event_id.clone(), commitment.clone());
+    let badge = env.storage().persistent().get(&key);
+    if badge.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    badge
+}
+
+pub fn save_anonymous_badge(env: &Env, badge: &AnonymousAttendanceBadge) {
+    let key = DataKey::AnonymousBadge(badge.event_id.clone(), badge.commitment.clone());
+    env.storage().persistent().set(&key, badge);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+/// Get the live approval for a ticket, ignoring grants past their expiry
+pub fn get_approved(env: &Env, ticket_id: u64) -> Option<ApprovalGrant> {
+    let key = DataKey::Approved(ticket_id);
+    let grant: Option<ApprovalGrant> = env.storage().persistent().get(&key);
+    match grant {
+        Some(grant) if grant.expiration_ledger >= env.ledger().sequence() => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+            Some(grant)
+        }
+        _ => None,
+    }
+}
+
+pub fn set_approved(env: &Env, ticket_id: u64, grant: &ApprovalGrant) {
+    let key = DataKey::Approved(ticket_id);
+    env.storage().persistent().set(&key, grant);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_approved(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approved(ticket_id));
+}
+
+/// Check for a live operator grant, ignoring grants past their expiry
+pub fn is_operator(env: &Env, owner: &Address, operator: &Address) -> bool {
+    let key = DataKey::Operator(owner.clone(), operator.clone());
+    let expiration_ledger: Option<u32> = env.storage().persistent().get(&key);
+    match expiration_ledger {
+        Some(expiration_ledger) if expiration_ledger >= env.ledger().sequence() => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+            true
+        }
+        _ => false,
+    }
+}
+
+pub fn set_operator(env: &Env, owner: &Address, operator: &Address, expiration_ledger: u32) {
+    let key = DataKey::Operator(owner.clone(), operator.clone());
+    env.storage().persistent().set(&key, &expiration_ledger);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_operator(env: &Env, owner: &Address, operator: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Operator(owner.clone(), operator.clone()));
+}
+
+pub fn get_base_uri(env: &Env) -> Option<String> {
+    let key = DataKey::BaseUri;
+    let uri = env.storage().persistent().get(&key);
+    if uri.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    uri
+}
+
+pub fn set_base_uri(env: &Env, uri: &String) {
+    env.storage().persistent().set(&DataKey::BaseUri, uri);
+    env.storage()
+        .persistent()
+        .extend_ttl(&DataKey::BaseUri, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_ticket_metadata(env: &Env, ticket_id: u64) -> Option<TicketMetadata> {
+    let key = DataKey::TicketMetadata(ticket_id);
+    let metadata = env.storage().persistent().get(&key);
+    if metadata.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    metadata
+}
+
+pub fn set_ticket_metadata(env: &Env, ticket_id: u64, metadata: &TicketMetadata) {
+    let key = DataKey::TicketMetadata(ticket_id);
+    env.storage().persistent().set(&key, metadata);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_ticket_metadata(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TicketMetadata(ticket_id));
+}
+
+pub fn get_event_transfer_config(env: &Env, event_id: &Symbol) -> Option<EventTransferConfig> {
+    let key = DataKey::EventTransferConfig(event_id.clone());
+    let config = env.storage().persistent().get(&key);
+    if config.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    config
+}
+
+pub fn set_event_transfer_config(env: &Env, event_id: &Symbol, config: &EventTransferConfig) {
+    let key = DataKey::EventTransferConfig(event_id.clone());
+    env.storage().persistent().set(&key, config);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn get_ticket_transfer_policy(env: &Env, ticket_id: u64) -> TransferPolicy {
+    let key = DataKey::TicketTransferPolicy(ticket_id);
+    let policy = env.storage().persistent().get(&key);
+    if policy.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    policy.unwrap_or(TransferPolicy::Open)
+}
+
+/// Record the policy a ticket was minted under. `Open` is the default and is
+/// not stored, so unrestricted events keep minting at their old footprint.
+pub fn set_ticket_transfer_policy(env: &Env, ticket_id: u64, policy: &TransferPolicy) {
+    if *policy == TransferPolicy::Open {
+        return;
+    }
+    let key = DataKey::TicketTransferPolicy(ticket_id);
+    env.storage().persistent().set(&key, policy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_ticket_transfer_policy(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TicketTransferPolicy(ticket_id));
+}
+
+pub fn get_ticket_tier(env: &Env, ticket_id: u64) -> u32 {
+    let key = DataKey::TicketTier(ticket_id);
+    let tier_id = env.storage().persistent().get(&key);
+    if tier_id.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    tier_id.unwrap_or(0)
+}
+
+/// Tier 0 is the default and is not stored.
+pub fn set_ticket_tier(env: &Env, ticket_id: u64, tier_id: u32) {
+    let key = DataKey::TicketTier(ticket_id);
+    if tier_id == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &tier_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+pub fn remove_ticket_tier(env: &Env, ticket_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TicketTier(ticket_id));
+}
+
+pub fn get_event_archive(env: &Env, event_id: &Symbol) -> Option<EventArchive> {
+    let key = DataKey::EventArchive(event_id.clone());
+    let archive = env.storage().persistent().get(&key);
+    if archive.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+    }
+    archive
+}
+
+pub fn set_event_archive(env: &Env, archive: &EventArchive) {
+    let key = DataKey::EventArchive(archive.event_id.clone());
+    env.storage().persistent().set(&key, archive);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, TTL_THRESHOLD, TTL_BUMP);
+}
+
+/// Record an attestation digest until the attestation expires. Temporary
+/// storage lets the entry lapse on its own.
+pub fn record_attestation(env: &Env, digest: &BytesN<32>, ticket_id: u64, live_ledgers: u32) {
+    let key = DataKey::Attestation(digest.clone());
+    env.storage().temporary().set(&key, &ticket_id);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, live_ledgers, live_ledgers);
+}
+
+pub fn get_attested_ticket(env: &Env, digest: &BytesN<32>) -> Option<u64> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::Attestation(digest.clone()))
+}
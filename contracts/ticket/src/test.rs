@@ -1,7 +1,10 @@
 use super::*;
 use crate::storage::DataKey;
 use crate::types::{Ticket, TicketStatus};
-use soroban_sdk::{testutils::Address as _, vec, Address, Env, Symbol};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, Symbol,
+};
 fn setup_test_ticket(
     env: &Env,
     contract_id: &Address,
@@ -625,7 +628,7 @@ fn test_set_event_contract_and_authorized_mint() {
         .unwrap();
     assert_eq!(stored_event_contract, event_contract);
 
-    let ticket_id = client.mint_ticket(&event_id, &organizer, &owner);
+    let ticket_id = client.mint_ticket(&event_id, &organizer, &owner, &0);
     assert_eq!(ticket_id, 1);
     assert_eq!(client.get_tickets_by_owner(&owner), vec![&env, 1]);
 }
@@ -661,6 +664,822 @@ fn test_mint_ticket_unauthorized_fails() {
 
     // Without auth mocked or provided, mint_ticket should fail authorization
     env.set_auths(&[]);
-    let result = client.try_mint_ticket(&event_id, &organizer, &owner);
+    let result = client.try_mint_ticket(&event_id, &organizer, &owner, &0);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_use_ticket_mints_attendance_badge() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &owner,
+        1,
+        TicketStatus::Valid,
+    );
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &owner,
+        2,
+        TicketStatus::Valid,
+    );
+    assert!(!client.has_attended(&owner, &event_id));
+    assert_eq!(
+        client.try_get_badge(&event_id, &owner),
+        Err(Ok(TicketError::BadgeNotFound))
+    );
+
+    client.use_ticket(&organizer, &owner, &1);
+    client.use_ticket(&organizer, &owner, &2);
+
+    // One badge per (event, holder), taken from the first check-in.
+    let badge = client.get_badge(&event_id, &owner);
+    assert_eq!(badge.ticket_id, 1);
+    assert_eq!(badge.holder, owner);
+    assert!(client.has_attended(&owner, &event_id));
+    assert_eq!(
+        client.get_attended_events(&owner, &0, &10),
+        vec![&env, event_id]
+    );
+}
+
+#[test]
+fn test_anonymous_badge_minted_by_event_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let payments_contract = Address::generate(&env);
+    let event_contract = Address::generate(&env);
+    let event_id = Symbol::new(&env, "anon_evt");
+
+    client.initialize(&admin, &payments_contract);
+    client.set_event_contract(&admin, &event_contract);
+
+    let preimage = soroban_sdk::Bytes::from_slice(&env, b"attendee secret");
+    let commitment: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+    client.mint_anonymous_badge(&event_id, &commitment);
+    assert!(client.verify_anonymous_badge(&event_id, &preimage));
+    assert!(!client.verify_anonymous_badge(
+        &event_id,
+        &soroban_sdk::Bytes::from_slice(&env, b"wrong secret")
+    ));
+    assert_eq!(
+        client.try_mint_anonymous_badge(&event_id, &commitment),
+        Err(Ok(TicketError::BadgeAlreadyMinted))
+    );
+}
+
+#[test]
+fn test_nft_interface_balance_owner_and_name() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        1,
+        TicketStatus::Valid,
+    );
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        2,
+        TicketStatus::Valid,
+    );
+
+    assert_eq!(client.name(), String::from_str(&env, "Zicket Ticket"));
+    assert_eq!(client.symbol(), String::from_str(&env, "ZTKT"));
+    assert_eq!(client.balance_of(&alice), 2);
+    assert_eq!(client.balance_of(&organizer), 0);
+    assert_eq!(client.owner_of(&1), alice);
+    assert_eq!(
+        client.try_owner_of(&99),
+        Err(Ok(TicketError::TicketNotFound))
+    );
+}
+
+#[test]
+fn test_transfer_from_with_approval_and_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    let wallet = Address::generate(&env);
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        1,
+        TicketStatus::Valid,
+    );
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        2,
+        TicketStatus::Valid,
+    );
+
+    // Unapproved spenders are rejected.
+    assert_eq!(
+        client.try_transfer_from(&marketplace, &alice, &bob, &1),
+        Err(Ok(TicketError::NotApprovedOrOwner))
+    );
+
+    // A per-ticket approval lets the spender move that ticket once.
+    client.approve(&alice, &Some(marketplace.clone()), &1, &1_000);
+    assert_eq!(client.get_approved(&1), Some(marketplace.clone()));
+    client.transfer_from(&marketplace, &alice, &bob, &1);
+    assert_eq!(client.owner_of(&1), bob);
+    assert_eq!(client.get_approved(&1), None);
+    assert_eq!(
+        client.try_transfer_from(&marketplace, &alice, &bob, &2),
+        Err(Ok(TicketError::NotApprovedOrOwner))
+    );
+
+    // Operators can move any of the owner's tickets until revoked.
+    client.set_approval_for_all(&alice, &wallet, &true, &1_000);
+    assert!(client.is_approved_for_all(&alice, &wallet));
+    client.transfer_from(&wallet, &alice, &bob, &2);
+    assert_eq!(client.balance_of(&bob), 2);
+    assert_eq!(client.balance_of(&alice), 0);
+
+    client.set_approval_for_all(&alice, &wallet, &false, &0);
+    assert!(!client.is_approved_for_all(&alice, &wallet));
+}
+
+#[test]
+fn test_transfer_from_keeps_transfer_restrictions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let operator = Address::generate(&env);
+    setup_test_ticket_with_transferable(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        1,
+        TicketStatus::Valid,
+        false,
+    );
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        2,
+        TicketStatus::Used,
+    );
+
+    client.set_approval_for_all(&alice, &operator, &true, &1_000);
+    assert_eq!(
+        client.try_transfer_from(&operator, &alice, &bob, &1),
+        Err(Ok(TicketError::TicketNotTransferable))
+    );
+    assert_eq!(
+        client.try_transfer_from(&operator, &alice, &bob, &2),
+        Err(Ok(TicketError::TicketNotTransferable))
+    );
+    assert_eq!(
+        client.try_transfer_from(&operator, &bob, &alice, &1),
+        Err(Ok(TicketError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_token_uri_includes_tier_and_seat() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    client.initialize(&admin, &Address::generate(&env));
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        42,
+        TicketStatus::Valid,
+    );
+
+    client.set_base_uri(&admin, &String::from_str(&env, "https://zicket.io/t/"));
+    assert_eq!(
+        client.token_uri(&42),
+        String::from_str(&env, "https://zicket.io/t/42")
+    );
+
+    client.set_ticket_metadata(&organizer, &42, &String::from_str(&env, "B-12"));
+    assert_eq!(
+        client.token_uri(&42),
+        String::from_str(&env, "https://zicket.io/t/42?tier=0&seat=B-12")
+    );
+    assert_eq!(
+        client.try_set_ticket_metadata(&alice, &42, &String::from_str(&env, "A-1")),
+        Err(Ok(TicketError::Unauthorized))
+    );
+
+    // The tier follows the ticket's authoritative tier after a move.
+    client.set_event_contract(&admin, &Address::generate(&env));
+    client.set_ticket_tier(&42, &3);
+    assert_eq!(
+        client.token_uri(&42),
+        String::from_str(&env, "https://zicket.io/t/42?tier=3&seat=B-12")
+    );
+}
+
+#[test]
+fn test_approvals_expire_after_expiration_ledger() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    let custodian = Address::generate(&env);
+    setup_test_ticket(
+        &env,
+        &contract_id,
+        &organizer,
+        &alice,
+        1,
+        TicketStatus::Valid,
+    );
+
+    assert_eq!(
+        client.try_approve(&alice, &Some(marketplace.clone()), &1, &99),
+        Err(Ok(TicketError::InvalidExpirationLedger))
+    );
+    assert_eq!(
+        client.try_set_approval_for_all(&alice, &custodian, &true, &99),
+        Err(Ok(TicketError::InvalidExpirationLedger))
+    );
+
+    client.approve(&alice, &Some(marketplace.clone()), &1, &150);
+    client.set_approval_for_all(&alice, &custodian, &true, &150);
+    assert_eq!(
+        client.get_approval(&1),
+        Some(ApprovalGrant {
+            spender: marketplace.clone(),
+            expiration_ledger: 150,
+        })
+    );
+
+    env.ledger().set_sequence_number(151);
+    assert_eq!(client.get_approved(&1), None);
+    assert!(!client.is_approved_for_all(&alice, &custodian));
+    assert_eq!(
+        client.try_transfer_from(&marketplace, &alice, &bob, &1),
+        Err(Ok(TicketError::NotApprovedOrOwner))
+    );
+    assert_eq!(
+        client.try_transfer_from(&custodian, &alice, &bob, &1),
+        Err(Ok(TicketError::NotApprovedOrOwner))
+    );
+}
+
+#[test]
+fn test_approval_cleared_on_transfer_use_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let marketplace = Address::generate(&env);
+    for ticket_id in 1..=3 {
+        setup_test_ticket(
+            &env,
+            &contract_id,
+            &organizer,
+            &alice,
+            ticket_id,
+            TicketStatus::Valid,
+        );
+        client.approve(&alice, &Some(marketplace.clone()), &ticket_id, &1_000);
+    }
+
+    client.transfer_ticket(&alice, &bob, &1);
+    assert_eq!(client.get_approved(&1), None);
+
+    client.use_ticket(&organizer, &alice, &2);
+    assert_eq!(client.get_approved(&2), None);
+
+    client.cancel_ticket(&3, &alice);
+    assert_eq!(client.get_approved(&3), None);
+    assert_eq!(
+        client.try_approve(&alice, &Some(marketplace.clone()), &3, &1_000),
+        Err(Ok(TicketError::TicketNotTransferable))
+    );
+}
+
+#[test]
+fn test_mint_records_event_transfer_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let payments_contract = Address::generate(&env);
+    let event_contract = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    client.initialize(&admin, &payments_contract);
+    client.set_event_contract(&admin, &event_contract);
+
+    // FreeOnly: direct transfers pass, marketplace settlement is rejected.
+    client.sync_transfer_policy(&event_id, &TransferPolicy::FreeOnly, &0, &0);
+    let free_id = client.mint_ticket(&event_id, &organizer, &alice, &0);
+    assert_eq!(
+        client.get_ticket_transfer_policy(&free_id),
+        TransferPolicy::FreeOnly
+    );
+    assert_eq!(
+        client.try_admin_transfer_ticket(&payments_contract, &alice, &bob, &free_id),
+        Err(Ok(TicketError::TransferPolicyViolation))
+    );
+    client.transfer_ticket(&alice, &bob, &free_id);
+
+    // MarketplaceOnly: only the payments contract can move the ticket.
+    client.sync_transfer_policy(&event_id, &TransferPolicy::MarketplaceOnly, &0, &0);
+    let market_id = client.mint_ticket(&event_id, &organizer, &alice, &0);
+    assert_eq!(
+        client.try_transfer_ticket(&alice, &bob, &market_id),
+        Err(Ok(TicketError::TransferPolicyViolation))
+    );
+    client.admin_transfer_ticket(&payments_contract, &alice, &bob, &market_id);
+
+    // Tickets keep the policy they were minted with.
+    assert_eq!(
+        client.get_ticket_transfer_policy(&free_id),
+        TransferPolicy::FreeOnly
+    );
+
+    // Disallowed tickets are minted non-transferable.
+    client.sync_transfer_policy(&event_id, &TransferPolicy::Disallowed, &0, &0);
+    let locked_id = client.mint_ticket(&event_id, &organizer, &alice, &0);
+    assert!(!client.get_ticket(&locked_id).is_transferable);
+    assert_eq!(
+        client.try_transfer_ticket(&alice, &bob, &locked_id),
+        Err(Ok(TicketError::TicketNotTransferable))
+    );
+}
+
+#[test]
+fn test_transfers_locked_near_event_start() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let event_contract = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    client.initialize(&admin, &Address::generate(&env));
+    client.set_event_contract(&admin, &event_contract);
+    client.sync_transfer_policy(&event_id, &TransferPolicy::Open, &50, &200);
+    let ticket_id = client.mint_ticket(&event_id, &organizer, &alice, &0);
+
+    env.ledger().set_sequence_number(149);
+    client.transfer_ticket(&alice, &bob, &ticket_id);
+
+    env.ledger().set_sequence_number(150);
+    assert_eq!(
+        client.try_transfer_ticket(&bob, &alice, &ticket_id),
+        Err(Ok(TicketError::TransferLocked))
+    );
+}
+
+#[test]
+fn test_paginated_ticket_queries_with_filters() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let event_a = Symbol::new(&env, "event_a");
+    let event_b = Symbol::new(&env, "event_b");
+
+    let a_ids = client.batch_mint_ticket(&event_a, &organizer, &alice, &0, &3);
+    let b_id = client.mint_ticket(&event_b, &organizer, &alice, &0);
+    client.use_ticket(&organizer, &alice, &a_ids.get(1).unwrap());
+
+    let first = client.get_owner_tickets_paginated(&alice, &0, &2, &None, &None);
+    assert_eq!(
+        first.ticket_ids,
+        vec![&env, a_ids.get(0).unwrap(), a_ids.get(1).unwrap()]
+    );
+    assert_eq!(first.next_cursor, Some(2));
+    let second = client.get_owner_tickets_paginated(&alice, &2, &2, &None, &None);
+    assert_eq!(second.ticket_ids, vec![&env, a_ids.get(2).unwrap(), b_id]);
+    assert_eq!(second.next_cursor, None);
+
+    let valid_a = client.get_owner_tickets_paginated(
+        &alice,
+        &0,
+        &10,
+        &Some(event_a.clone()),
+        &Some(TicketStatus::Valid),
+    );
+    assert_eq!(
+        valid_a.ticket_ids,
+        vec![&env, a_ids.get(0).unwrap(), a_ids.get(2).unwrap()]
+    );
+
+    let used = client.get_event_tickets_paginated(&event_a, &0, &10, &Some(TicketStatus::Used));
+    assert_eq!(used.ticket_ids, vec![&env, a_ids.get(1).unwrap()]);
+    assert_eq!(used.next_cursor, None);
+    assert_eq!(client.get_owner_event_ticket_count(&alice, &event_b), 1);
+}
+
+#[test]
+fn test_has_valid_ticket_tracks_transfers_use_and_cancel() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    assert!(!client.has_valid_ticket(&alice, &event_id, &None));
+    let first = client.mint_ticket(&event_id, &organizer, &alice, &0);
+    let second = client.mint_ticket(&event_id, &organizer, &alice, &0);
+    assert!(client.has_valid_ticket(&alice, &event_id, &None));
+
+    client.transfer_ticket(&alice, &bob, &first);
+    assert!(client.has_valid_ticket(&bob, &event_id, &None));
+    assert_eq!(client.get_owner_event_ticket_count(&alice, &event_id), 1);
+
+    client.use_ticket(&organizer, &bob, &first);
+    assert!(!client.has_valid_ticket(&bob, &event_id, &None));
+    assert_eq!(client.get_owner_event_ticket_count(&bob, &event_id), 1);
+
+    client.cancel_ticket(&second, &alice);
+    assert!(!client.has_valid_ticket(&alice, &event_id, &None));
+}
+
+#[test]
+fn test_owner_recovery_key_signature_bound_to_ticket_and_nonce() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    let first = client.mint_ticket(&event_id, &organizer, &alice, &0);
+    let second = client.mint_ticket(&event_id, &organizer, &alice, &0);
+
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    client.set_owner_recovery_key(&alice, &Some(public_key));
+
+    let sign = |ticket_id: u64| {
+        let message = client.recovery_message(&ticket_id, &bob);
+        let mut buf = [0u8; 512];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut buf[..len]);
+        BytesN::from_array(&env, &signing_key.sign(&buf[..len]).to_bytes())
+    };
+
+    let signature = sign(first);
+    // The signature names ticket `first`, so it cannot move `second`.
+    assert!(client
+        .try_recover_ticket(&second, &bob, &signature)
+        .is_err());
+
+    client.recover_ticket(&first, &bob, &signature);
+    assert_eq!(client.get_ticket(&first).owner, bob);
+    assert_eq!(client.get_recovery_nonce(&first), 1);
+    assert!(client.has_valid_ticket(&bob, &event_id, &None));
+
+    // The bulk key still covers alice's remaining ticket.
+    client.recover_ticket(&second, &bob, &sign(second));
+    assert_eq!(client.get_ticket(&second).owner, bob);
+}
+
+#[test]
+fn test_transfer_revokes_previous_holder_recovery() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let mallory = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+    let ticket_id = client.mint_ticket(&event_id, &organizer, &alice, &0);
+
+    let alice_key = SigningKey::from_bytes(&[3u8; 32]);
+    client.set_recovery_key(
+        &alice,
+        &ticket_id,
+        &BytesN::from_array(&env, &alice_key.verifying_key().to_bytes()),
+    );
+    let message = client.recovery_message(&ticket_id, &mallory);
+    let mut buf = [0u8; 512];
+    let len = message.len() as usize;
+    message.copy_into_slice(&mut buf[..len]);
+    let stale_signature = BytesN::from_array(&env, &alice_key.sign(&buf[..len]).to_bytes());
+
+    client.transfer_ticket(&alice, &bob, &ticket_id);
+    assert_eq!(client.get_recovery_nonce(&ticket_id), 1);
+
+    // The seller's per-ticket key went with the sale.
+    assert_eq!(
+        client.try_recover_ticket(&ticket_id, &mallory, &stale_signature),
+        Err(Ok(TicketError::RecoveryKeyNotFound))
+    );
+
+    // Even under the same key, the bumped nonce voids the old signature.
+    client.set_recovery_key(
+        &bob,
+        &ticket_id,
+        &BytesN::from_array(&env, &alice_key.verifying_key().to_bytes()),
+    );
+    assert!(client
+        .try_recover_ticket(&ticket_id, &mallory, &stale_signature)
+        .is_err());
+    assert_eq!(client.get_ticket(&ticket_id).owner, bob);
+}
+
+#[test]
+fn test_guardian_recovery_threshold_delay_and_veto() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(1_000);
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let g1 = Address::generate(&env);
+    let g2 = Address::generate(&env);
+    let g3 = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+    let ticket_id = client.mint_ticket(&event_id, &organizer, &alice, &0);
+
+    assert_eq!(
+        client.try_set_recovery_guardians(&alice, &vec![&env, g1.clone(), g1.clone()], &1, &17_280),
+        Err(Ok(TicketError::InvalidGuardianSet))
+    );
+    client.set_recovery_guardians(
+        &alice,
+        &vec![&env, g1.clone(), g2.clone(), g3.clone()],
+        &2,
+        &17_280,
+    );
+
+    assert_eq!(
+        client.try_initiate_recovery(&outsider, &ticket_id, &new_wallet),
+        Err(Ok(TicketError::NotGuardian))
+    );
+
+    // The owner vetoes the first attempt.
+    client.initiate_recovery(&g1, &ticket_id, &outsider);
+    client.veto_recovery(&alice, &ticket_id);
+    assert_eq!(client.get_pending_recovery(&ticket_id), None);
+
+    client.initiate_recovery(&g1, &ticket_id, &new_wallet);
+    assert_eq!(
+        client.try_execute_recovery(&ticket_id),
+        Err(Ok(TicketError::RecoveryNotReady))
+    );
+    client.approve_recovery(&g2, &ticket_id);
+
+    env.ledger().set_sequence_number(1_000 + 17_279);
+    assert_eq!(
+        client.try_execute_recovery(&ticket_id),
+        Err(Ok(TicketError::RecoveryNotReady))
+    );
+
+    env.ledger().set_sequence_number(1_000 + 17_280);
+    client.execute_recovery(&ticket_id);
+    assert_eq!(client.get_ticket(&ticket_id).owner, new_wallet);
+    assert_eq!(client.get_pending_recovery(&ticket_id), None);
+    assert_eq!(client.get_recovery_nonce(&ticket_id), 1);
+}
+
+#[test]
+fn test_archive_event_tickets_after_grace_period() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let event_contract = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    client.initialize(&admin, &Address::generate(&env));
+    client.set_event_contract(&admin, &event_contract);
+    let alice_ids = client.batch_mint_ticket(&event_id, &organizer, &alice, &0, &2);
+    client.sync_transfer_policy(&event_id, &TransferPolicy::FreeOnly, &0, &0);
+    let bob_id = client.mint_ticket(&event_id, &organizer, &bob, &2);
+    client.set_ticket_metadata(&organizer, &bob_id, &String::from_str(&env, "A-1"));
+    client.use_ticket(&organizer, &alice, &alice_ids.get(0).unwrap());
+    client.transfer_ticket(&alice, &bob, &alice_ids.get(1).unwrap());
+    assert_eq!(client.get_recovery_nonce(&alice_ids.get(1).unwrap()), 1);
+    assert_eq!(client.get_ticket_tier(&bob_id), 2);
+    assert_eq!(
+        client.get_ticket_transfer_policy(&bob_id),
+        TransferPolicy::FreeOnly
+    );
+
+    assert_eq!(
+        client.try_archive_event_tickets(&event_id, &0, &10),
+        Err(Ok(TicketError::EventNotClosed))
+    );
+
+    env.ledger().set_sequence_number(100);
+    client.close_event(&event_id, &false);
+    assert_eq!(
+        client.try_archive_event_tickets(&event_id, &0, &10),
+        Err(Ok(TicketError::ArchiveGracePeriodActive))
+    );
+
+    env.ledger().set_sequence_number(100 + 120_960);
+    assert_eq!(client.archive_event_tickets(&event_id, &0, &2), Some(2));
+    assert_eq!(
+        client.try_archive_event_tickets(&event_id, &0, &2),
+        Err(Ok(TicketError::InvalidInput))
+    );
+    assert_eq!(client.archive_event_tickets(&event_id, &2, &2), None);
+
+    assert_eq!(
+        client.get_ticket(&alice_ids.get(0).unwrap()).status,
+        TicketStatus::Used
+    );
+    assert_eq!(
+        client.get_ticket(&alice_ids.get(1).unwrap()).status,
+        TicketStatus::Expired
+    );
+    assert_eq!(client.get_ticket(&bob_id).status, TicketStatus::Expired);
+    assert_eq!(client.get_tickets_by_owner(&alice).len(), 0);
+    assert!(!client.has_valid_ticket(&bob, &event_id, &None));
+    assert_eq!(
+        client
+            .get_event_tickets_paginated(&event_id, &0, &10, &None)
+            .ticket_ids
+            .len(),
+        0
+    );
+
+    let archive = client.get_event_archive(&event_id).unwrap();
+    assert_eq!(archive.total_tickets, 3);
+    assert_eq!(archive.archived_tickets, 3);
+    assert_eq!(archive.used_tickets, 1);
+    assert_eq!(archive.expired_tickets, 2);
+    assert_ne!(archive.issuance_digest, BytesN::from_array(&env, &[0; 32]));
+    assert_eq!(archive.archived_at_ledger, Some(100 + 120_960));
+    assert_eq!(
+        client.try_archive_event_tickets(&event_id, &3, &2),
+        Err(Ok(TicketError::EventAlreadyArchived))
+    );
+
+    // Per-ticket state that only served live tickets is gone too.
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert!(!storage.has(&DataKey::RecoveryNonce(alice_ids.get(1).unwrap())));
+        assert!(!storage.has(&DataKey::TicketMetadata(bob_id)));
+        assert!(!storage.has(&DataKey::TicketTier(bob_id)));
+        assert!(!storage.has(&DataKey::TicketTransferPolicy(bob_id)));
+    });
+}
+
+#[test]
+fn test_ticket_attestation_and_tier_gated_access() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TicketContract, ());
+    let client = TicketContractClient::new(&env, &contract_id);
+    let access = TicketAccessClient::new(&env, &contract_id);
+
+    let organizer = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let event_id = Symbol::new(&env, "event_1");
+
+    let ticket_id = client.mint_ticket(&event_id, &organizer, &alice, &2);
+    assert!(access.has_valid_ticket(&alice, &event_id, &None));
+    assert!(access.has_valid_ticket(&alice, &event_id, &Some(2)));
+    assert!(!access.has_valid_ticket(&alice, &event_id, &Some(1)));
+
+    assert_eq!(
+        client.try_issue_attestation(&bob, &ticket_id, &100),
+        Err(Ok(TicketError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_issue_attestation(&alice, &ticket_id, &(17_280 + 1)),
+        Err(Ok(TicketError::InvalidInput))
+    );
+
+    env.ledger().set_sequence_number(10);
+    let attestation = client.issue_attestation(&alice, &ticket_id, &100);
+    assert_eq!(attestation.tier_id, 2);
+    assert_eq!(attestation.expires_at_ledger, 110);
+    assert!(access.verify_attestation(&attestation));
+
+    let mut forged = attestation.clone();
+    forged.holder = bob.clone();
+    assert!(!access.verify_attestation(&forged));
+
+    env.ledger().set_sequence_number(111);
+    assert!(!access.verify_attestation(&attestation));
+
+    env.ledger().set_sequence_number(20);
+    client.transfer_ticket(&alice, &bob, &ticket_id);
+    assert!(!access.verify_attestation(&attestation));
+    assert!(access.has_valid_ticket(&bob, &event_id, &Some(2)));
+}
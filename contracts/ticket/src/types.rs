@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, Symbol};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, PartialEq)]
@@ -6,6 +6,50 @@ pub enum TicketStatus {
     Valid,
     Used,
     Cancelled,
+    /// Voided by the organizer or platform, e.g. for fraud or a chargeback.
+    Revoked,
+    /// Still unused when its event's tickets were archived.
+    Expired,
+}
+
+/// Audit reason recorded when an organizer or the platform revokes a ticket.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RevocationReason {
+    Fraud,
+    Chargeback,
+    PolicyViolation,
+    Duplicate,
+    Other,
+}
+
+/// How a ticket may change hands, as configured by the event organizer.
+///
+/// Direct transfers are `transfer_ticket` and `transfer_from`; marketplace
+/// transfers are resale settlements routed through the payments contract.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TransferPolicy {
+    /// Direct and marketplace transfers are both allowed.
+    Open,
+    /// The ticket cannot be transferred at all.
+    Disallowed,
+    /// Only direct transfers are allowed; the ticket cannot be resold.
+    FreeOnly,
+    /// Only marketplace transfers are allowed, so resale royalties and price
+    /// caps always apply.
+    MarketplaceOnly,
+}
+
+/// Transfer rules the event contract has synced for an event.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventTransferConfig {
+    pub policy: TransferPolicy,
+    /// Transfers are locked from `event_start_ledger - lock_ledgers` onwards.
+    /// Zero disables the lock.
+    pub lock_ledgers: u32,
+    pub event_start_ledger: u32,
 }
 
 #[contracttype]
@@ -20,3 +64,121 @@ pub struct Ticket {
     pub is_transferable: bool,
     pub is_used: bool,
 }
+
+/// Single-ticket transfer approval. The grant stops being honoured once the
+/// ledger sequence passes `expiration_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ApprovalGrant {
+    pub spender: Address,
+    pub expiration_ledger: u32,
+}
+
+/// Display metadata attached to a ticket by its organizer and surfaced to
+/// wallets and marketplaces through `token_uri`. The tier is not part of it:
+/// it lives under its own key and moves with upgrades.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TicketMetadata {
+    pub seat: String,
+}
+
+/// Social-recovery guardians covering every ticket an owner holds.
+///
+/// `threshold` of the `guardians` must approve a recovery, which then only
+/// executes `delay_ledgers` after it was initiated so the owner can veto it.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GuardianSet {
+    pub guardians: Vec<Address>,
+    pub threshold: u32,
+    pub delay_ledgers: u32,
+}
+
+/// A guardian-initiated recovery waiting for approvals and its delay.
+/// `owner` is the holder it was raised against; it lapses if the ticket moves.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingRecovery {
+    pub owner: Address,
+    pub new_owner: Address,
+    pub approvals: Vec<Address>,
+    pub executable_at_ledger: u32,
+}
+
+/// One page of ticket ids from a paginated query. `next_cursor` is the cursor
+/// to pass for the following page, or `None` once the index is exhausted.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TicketPage {
+    pub ticket_ids: Vec<u64>,
+    pub next_cursor: Option<u64>,
+}
+
+/// Short-lived proof that `holder` held a valid ticket, for third-party apps.
+///
+/// `digest` is a SHA-256 hash binding every other field to the network and
+/// the issuing contract; `verify_attestation` recomputes it and checks that
+/// the contract recorded it and that the ticket is still valid.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct TicketAttestation {
+    pub ticket_id: u64,
+    pub event_id: Symbol,
+    pub tier_id: u32,
+    pub holder: Address,
+    pub issued_at_ledger: u32,
+    pub expires_at_ledger: u32,
+    pub digest: BytesN<32>,
+}
+
+/// Lifecycle summary for a completed or cancelled event. It outlives the
+/// index entries removed by `archive_event_tickets` and carries a running
+/// SHA-256 digest over every archived ticket record as proof of issuance.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventArchive {
+    pub event_id: Symbol,
+    pub cancelled: bool,
+    pub closed_at_ledger: u32,
+    pub total_tickets: u64,
+    pub archived_tickets: u64,
+    pub used_tickets: u64,
+    pub expired_tickets: u64,
+    pub issuance_digest: BytesN<32>,
+    /// Set once every ticket of the event has been archived.
+    pub archived_at_ledger: Option<u32>,
+}
+
+/// Tickets an owner holds for one event, in acquisition order, and the number
+/// of them still valid. Bounded in practice by per-attendee purchase limits.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnerEventTickets {
+    pub ticket_ids: Vec<u64>,
+    pub valid_count: u32,
+}
+
+/// Non-transferable proof-of-attendance record minted when a ticket is used.
+///
+/// Badges are keyed by `(event_id, holder)`, so a holder checking in several
+/// tickets for the same event keeps the badge from the first check-in. There is
+/// no transfer entry point: a badge stays with the address that attended.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttendanceBadge {
+    pub event_id: Symbol,
+    pub ticket_id: u64,
+    pub holder: Address,
+    pub issued_at: u64,
+}
+
+/// Commitment-based attendance badge for Anonymous events. Only the commitment
+/// is stored; the attendee proves the badge by revealing its preimage.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnonymousAttendanceBadge {
+    pub event_id: Symbol,
+    pub commitment: BytesN<32>,
+    pub issued_at: u64,
+}